@@ -0,0 +1,134 @@
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams, Texture};
+use tetra::input::actions::{ActionMap, Binding, BindingCapture, VectorBinding};
+use tetra::input::{GamepadButton, GamepadStick, Key, MouseButton};
+use tetra::math::Vec2;
+use tetra::{Context, ContextBuilder, State};
+
+const SPEED: f32 = 6.0;
+
+const HELP: &str = "Move: WASD / arrows / D-pad / left stick\nReset: R / Start\nTint: hold the tint button\nPress Enter to rebind tint";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Control {
+    Move,
+    Reset,
+    Tint,
+    Rebind,
+}
+
+struct GameState {
+    texture: Texture,
+    help: Text,
+    actions: ActionMap<Control>,
+    capture: Option<BindingCapture>,
+    position: Vec2<f32>,
+    tinted: bool,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        let mut actions = ActionMap::new();
+
+        // The keyboard and the gamepad can be used interchangeably.
+        actions.bind_vector(Control::Move, VectorBinding::wasd());
+        actions.bind_vector(Control::Move, VectorBinding::arrow_keys());
+        actions.bind_vector(Control::Move, VectorBinding::dpad());
+        actions.bind_vector(
+            Control::Move,
+            VectorBinding::GamepadStick(GamepadStick::LeftStick),
+        );
+
+        actions.bind(Control::Reset, Binding::Key(Key::R));
+        actions.bind(Control::Reset, Binding::GamepadButton(GamepadButton::Start));
+
+        actions.bind(Control::Tint, Binding::Key(Key::Space));
+        actions.bind(Control::Tint, Binding::MouseButton(MouseButton::Left));
+        actions.bind(Control::Tint, Binding::GamepadButton(GamepadButton::A));
+
+        actions.bind(Control::Rebind, Binding::Key(Key::Enter));
+
+        Ok(GameState {
+            texture: Texture::new(ctx, "./examples/resources/player.png")?,
+            help: Text::new(
+                HELP,
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 16.0)?,
+            ),
+            actions,
+            capture: None,
+            position: Vec2::new(320.0, 240.0),
+            tinted: false,
+        })
+    }
+}
+
+impl State for GameState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        // While rebinding, the next input the player touches replaces the tint bindings.
+        if let Some(capture) = &mut self.capture {
+            if let Some(binding) = capture.update(ctx).and_then(|input| input.to_binding()) {
+                self.actions.clear_bindings(&Control::Tint);
+                self.actions.bind(Control::Tint, binding);
+                self.capture = None;
+
+                let conflicts = self.actions.find_conflicts();
+
+                if conflicts.is_empty() {
+                    self.help
+                        .set_content(format!("{}\n\nTint is now {:?}", HELP, binding));
+                } else {
+                    self.help.set_content(format!(
+                        "{}\n\n{:?} is also used by {:?}",
+                        HELP, binding, conflicts[0].names
+                    ));
+                }
+            }
+
+            return Ok(());
+        }
+
+        if self.actions.is_action_pressed(ctx, &Control::Rebind) {
+            self.capture = Some(BindingCapture::new());
+            self.help
+                .set_content(format!("{}\n\nPress any button...", HELP));
+        }
+
+        self.position += self.actions.get_vector_value(ctx, &Control::Move) * SPEED;
+
+        if self.actions.is_action_pressed(ctx, &Control::Reset) {
+            self.position = Vec2::new(320.0, 240.0);
+        }
+
+        self.tinted = self.actions.is_action_down(ctx, &Control::Tint);
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.392, 0.584, 0.929));
+
+        self.texture.draw(
+            ctx,
+            DrawParams::new()
+                .position(self.position)
+                .origin(Vec2::new(8.0, 8.0))
+                .scale(Vec2::new(4.0, 4.0))
+                .color(if self.tinted {
+                    Color::rgb(1.0, 0.4, 0.4)
+                } else {
+                    Color::WHITE
+                }),
+        );
+
+        self.help.draw(ctx, Vec2::new(16.0, 16.0));
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Action Mapping", 640, 480)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
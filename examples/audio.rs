@@ -89,6 +89,9 @@ impl State for GameState {
 fn main() -> tetra::Result {
     ContextBuilder::new("Audio Playback", 640, 480)
         .quit_on_escape(true)
+        // The music will stop while the window is in the background.
+        .pause_on_focus_lost(true)
+        .background_fps(Some(10.0))
         .build()?
         .run(GameState::new)
 }
@@ -118,12 +118,16 @@ impl State for GameState {
             self.texture.draw(ctx, bunny.position);
         }
 
+        let stats = time::get_frame_stats(ctx);
+
         window::set_title(
             ctx,
             &format!(
-                "BunnyMark - {} bunnies - {:.0} FPS",
+                "BunnyMark - {} bunnies - {:.0} FPS - {:.2}ms draw - {:.2}ms 99th percentile",
                 self.bunnies.len(),
-                time::get_fps(ctx)
+                time::get_fps(ctx),
+                stats.draw_time.as_secs_f64() * 1000.0,
+                stats.p99_frame_time.as_secs_f64() * 1000.0,
             ),
         );
 
@@ -0,0 +1,53 @@
+use tetra::fs;
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams, Texture};
+use tetra::math::Vec2;
+use tetra::{Context, ContextBuilder, State};
+
+struct GameState {
+    texture: Texture,
+    text: Text,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        // The whole resources folder is compiled into the executable, so the
+        // game can be distributed without it. In debug builds, any changes to
+        // the files on disk will still be picked up without recompiling.
+        let bundle = tetra::include_bundle!("$CARGO_MANIFEST_DIR/examples/resources");
+        fs::mount_bundle(ctx, bundle, 0);
+
+        Ok(GameState {
+            texture: Texture::new(ctx, "player.png")?,
+            text: Text::new(
+                "Loaded from an embedded bundle!",
+                Font::vector(ctx, "DejaVuSansMono.ttf", 16.0)?,
+            ),
+        })
+    }
+}
+
+impl State for GameState {
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.769, 0.812, 0.631));
+
+        self.texture.draw(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(32.0, 32.0))
+                .origin(Vec2::new(8.0, 8.0))
+                .scale(Vec2::new(2.0, 2.0)),
+        );
+
+        self.text.draw(ctx, Vec2::new(64.0, 24.0));
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Embedded Assets", 640, 480)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
@@ -2,7 +2,7 @@
 
 use tetra::graphics::text::{Font, Text};
 use tetra::graphics::{self, Color, DrawParams, Rectangle, Texture};
-use tetra::input::{self, GamepadAxis, GamepadButton, GamepadStick};
+use tetra::input::{self, GamepadAxis, GamepadButton, GamepadSettings, GamepadStick};
 use tetra::math::Vec2;
 use tetra::{Context, ContextBuilder, State};
 
@@ -84,6 +84,12 @@ struct GameState {
 
 impl GameState {
     fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        // Ignore small movements of the sticks, so that worn controllers don't drift.
+        let mut settings = GamepadSettings::default();
+        settings.left_stick.inner_dead_zone = 0.2;
+        settings.right_stick.inner_dead_zone = 0.2;
+        input::set_default_gamepad_settings(ctx, settings);
+
         Ok(GameState {
             texture: Texture::new(ctx, "./examples/resources/controls.png")?,
             active_color: Color::rgb(1.0, 0.5, 0.5),
@@ -0,0 +1,127 @@
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Camera, Color, DrawParams, Texture};
+use tetra::input;
+use tetra::input::gestures::{Gesture, GestureRecognizer};
+use tetra::math::Vec2;
+use tetra::{Context, ContextBuilder, State};
+
+const HELP: &str = "Drag to pan, pinch or scroll to zoom, twist to rotate\nDouble tap to reset";
+
+struct GameState {
+    texture: Texture,
+    status: Text,
+    camera: Camera,
+    gestures: GestureRecognizer,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        Ok(GameState {
+            texture: Texture::new(ctx, "./examples/resources/player.png")?,
+            status: Text::new(
+                HELP,
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 16.0)?,
+            ),
+            camera: Camera::new(640.0, 480.0),
+            gestures: GestureRecognizer::new(),
+        })
+    }
+
+    /// Applies a change to the camera, while keeping the point under `anchor` in
+    /// the same place on the screen.
+    fn transform_around(&mut self, anchor: Vec2<f32>, change: impl FnOnce(&mut Camera)) {
+        let before = self.camera.project(anchor);
+        change(&mut self.camera);
+        let after = self.camera.project(anchor);
+
+        self.camera.position += before - after;
+    }
+}
+
+impl State for GameState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        for gesture in self.gestures.update(ctx) {
+            match gesture {
+                Gesture::Drag { delta, .. } => {
+                    // The drag is in screen co-ordinates, so it needs to be converted
+                    // to world co-ordinates before it can move the camera.
+                    let mut delta = delta / self.camera.scale;
+                    delta.rotate_z(-self.camera.rotation);
+
+                    self.camera.position -= delta;
+                }
+
+                Gesture::Pinch { center, scale } => {
+                    self.transform_around(center, |camera| camera.scale *= scale);
+                }
+
+                Gesture::Rotate { center, angle } => {
+                    self.transform_around(center, |camera| camera.rotation += angle);
+                }
+
+                Gesture::DoubleTap { .. } => {
+                    self.camera = Camera::new(640.0, 480.0);
+                }
+
+                Gesture::Tap { position } | Gesture::LongPress { position } => {
+                    let world = self.camera.project(position);
+
+                    self.status.set_content(format!(
+                        "{}\n\n{:?} at ({:.0}, {:.0})",
+                        HELP, gesture, world.x, world.y
+                    ));
+                }
+
+                Gesture::Swipe { direction, .. } => {
+                    self.status
+                        .set_content(format!("{}\n\nSwiped {:?}", HELP, direction));
+                }
+
+                _ => {}
+            }
+        }
+
+        let scroll = input::get_mouse_wheel_movement(ctx).y;
+
+        if scroll != 0 {
+            let zoom = 1.1_f32.powi(scroll);
+            let mouse = input::get_mouse_position(ctx);
+
+            self.transform_around(mouse, |camera| camera.scale *= zoom);
+        }
+
+        self.camera.update();
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.769, 0.812, 0.631));
+        graphics::set_transform_matrix(ctx, self.camera.as_matrix());
+
+        for x in -8..=8 {
+            for y in -8..=8 {
+                self.texture.draw(
+                    ctx,
+                    DrawParams::new()
+                        .position(Vec2::new(x as f32 * 64.0, y as f32 * 64.0))
+                        .origin(Vec2::new(8.0, 8.0))
+                        .scale(Vec2::new(2.0, 2.0)),
+                );
+            }
+        }
+
+        graphics::reset_transform_matrix(ctx);
+
+        self.status.draw(ctx, Vec2::new(16.0, 16.0));
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Gestures", 640, 480)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
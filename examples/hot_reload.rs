@@ -0,0 +1,84 @@
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams, Shader, Texture};
+use tetra::math::Vec2;
+use tetra::{Context, ContextBuilder, Event, State};
+
+struct GameState {
+    texture: Texture,
+    shader: Shader,
+    status: Text,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        let overlay = Texture::new(ctx, "./examples/resources/overlay.png")?;
+
+        let shader = Shader::from_fragment_file(ctx, "./examples/resources/disco.frag")?;
+        shader.set_uniform(ctx, "u_overlay", overlay);
+
+        Ok(GameState {
+            texture: Texture::new(ctx, "./examples/resources/player.png")?,
+            shader,
+            status: Text::new(
+                "Try editing player.png or disco.frag in examples/resources!",
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 16.0)?,
+            ),
+        })
+    }
+}
+
+impl State for GameState {
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.392, 0.584, 0.929));
+
+        // Uniforms are reset when a shader is reloaded (other than textures),
+        // so we set them every frame.
+        self.shader.set_uniform(ctx, "u_red", 1.0);
+        self.shader.set_uniform(ctx, "u_green", 1.0);
+        self.shader.set_uniform(ctx, "u_blue", 1.0);
+
+        graphics::set_shader(ctx, &self.shader);
+
+        self.texture.draw(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(320.0, 240.0))
+                .origin(Vec2::new(8.0, 8.0))
+                .scale(Vec2::new(8.0, 8.0)),
+        );
+
+        graphics::reset_shader(ctx);
+
+        self.status.draw(ctx, Vec2::new(16.0, 16.0));
+
+        Ok(())
+    }
+
+    fn event(&mut self, _: &mut Context, event: Event) -> tetra::Result {
+        match event {
+            Event::AssetReloaded { path } => {
+                self.status
+                    .set_content(format!("Reloaded {}", path.display()));
+            }
+
+            // If the new version of an asset is broken (e.g. the shader doesn't compile),
+            // the old version will keep being used.
+            Event::AssetReloadFailed { path, error } => {
+                self.status
+                    .set_content(format!("Failed to reload {}:\n{}", path.display(), error));
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Hot Reloading", 640, 480)
+        .hot_reload(true)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
@@ -0,0 +1,103 @@
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams, Texture};
+use tetra::input::{self, Key};
+use tetra::math::Vec2;
+use tetra::{Context, ContextBuilder, State};
+
+const RECORDING_PATH: &str = "./input_replay.bin";
+
+struct GameState {
+    texture: Texture,
+    text: Text,
+    position: Vec2<f32>,
+    start_position: Vec2<f32>,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        Ok(GameState {
+            texture: Texture::new(ctx, "./examples/resources/player.png")?,
+            text: Text::new(
+                "",
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 16.0)?,
+            ),
+            position: Vec2::new(320.0, 240.0),
+            start_position: Vec2::new(320.0, 240.0),
+        })
+    }
+}
+
+impl State for GameState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        if input::is_key_pressed(ctx, Key::R) && !input::is_playing_back(ctx) {
+            if input::is_recording(ctx) {
+                if let Some(recording) = input::stop_recording(ctx) {
+                    recording.save(RECORDING_PATH)?;
+                }
+            } else {
+                // The game's state needs to be captured alongside the recording,
+                // so that playback starts from the same place.
+                self.start_position = self.position;
+                input::start_recording(ctx);
+            }
+        }
+
+        if input::is_key_pressed(ctx, Key::P) && !input::is_recording(ctx) {
+            let recording = input::InputRecording::load(RECORDING_PATH)?;
+
+            self.position = self.start_position;
+            input::start_playback(ctx, recording);
+        }
+
+        if input::is_key_down(ctx, Key::A) {
+            self.position.x -= 4.0;
+        }
+
+        if input::is_key_down(ctx, Key::D) {
+            self.position.x += 4.0;
+        }
+
+        if input::is_key_down(ctx, Key::W) {
+            self.position.y -= 4.0;
+        }
+
+        if input::is_key_down(ctx, Key::S) {
+            self.position.y += 4.0;
+        }
+
+        let status = if input::is_recording(ctx) {
+            "Recording - press R to stop"
+        } else if input::is_playing_back(ctx) {
+            "Playing back..."
+        } else {
+            "Press R to start recording, or P to play back the last recording"
+        };
+
+        self.text.set_content(format!("Move with WASD\n{}", status));
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.769, 0.812, 0.631));
+
+        self.texture.draw(
+            ctx,
+            DrawParams::new()
+                .position(self.position)
+                .origin(Vec2::new(8.0, 8.0))
+                .scale(Vec2::new(2.0, 2.0)),
+        );
+
+        self.text.draw(ctx, Vec2::new(16.0, 16.0));
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Input Recording and Playback", 640, 480)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
@@ -0,0 +1,112 @@
+use tetra::audio::Sound;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams, Rectangle, Texture};
+use tetra::loading::{AssetHandle, AssetLoader};
+use tetra::math::Vec2;
+use tetra::{Context, ContextBuilder, State};
+
+const BAR_WIDTH: f32 = 400.0;
+
+struct Assets {
+    backdrop: AssetHandle<Texture>,
+    player: AssetHandle<Texture>,
+    music: AssetHandle<Sound>,
+    font: AssetHandle<Font>,
+}
+
+struct GameState {
+    loader: AssetLoader,
+    assets: Assets,
+    bar: Mesh,
+    text: Option<Text>,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        let mut loader = AssetLoader::new(ctx)?;
+
+        // These calls return immediately - the files are loaded on other threads.
+        let assets = Assets {
+            backdrop: loader.texture("./examples/resources/backdrop.png"),
+            player: loader.texture("./examples/resources/player.png"),
+            music: loader.sound("./examples/resources/bgm.ogg"),
+            font: loader.vector_font("./examples/resources/DejaVuSansMono.ttf", 32.0),
+        };
+
+        Ok(GameState {
+            loader,
+            assets,
+            bar: Mesh::rectangle(
+                ctx,
+                ShapeStyle::Fill,
+                Rectangle::new(0.0, 0.0, BAR_WIDTH, 16.0),
+            )?,
+            text: None,
+        })
+    }
+}
+
+impl State for GameState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        if self.loader.is_finished() {
+            return Ok(());
+        }
+
+        self.loader.update(ctx)?;
+
+        if self.loader.is_finished() {
+            if let Some(music) = self.assets.music.get() {
+                music.repeat(ctx)?;
+            }
+
+            if let Some(font) = self.assets.font.get() {
+                self.text = Some(Text::new("Loaded!", font));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.094, 0.11, 0.16));
+
+        if !self.loader.is_finished() {
+            self.bar.draw(
+                ctx,
+                DrawParams::new()
+                    .position(Vec2::new(120.0, 232.0))
+                    .scale(Vec2::new(self.loader.progress(), 1.0)),
+            );
+
+            return Ok(());
+        }
+
+        if let Some(backdrop) = self.assets.backdrop.get() {
+            backdrop.draw(ctx, Vec2::zero());
+        }
+
+        if let Some(player) = self.assets.player.get() {
+            player.draw(
+                ctx,
+                DrawParams::new()
+                    .position(Vec2::new(320.0, 240.0))
+                    .origin(Vec2::new(8.0, 8.0))
+                    .scale(Vec2::new(4.0, 4.0)),
+            );
+        }
+
+        if let Some(text) = &mut self.text {
+            text.draw(ctx, Vec2::new(16.0, 16.0));
+        }
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Loading Screen", 640, 480)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
@@ -0,0 +1,143 @@
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams, Texture};
+use tetra::input::players::{InputDevice, PlayerEvent, PlayerSlots};
+use tetra::input::{self, GamepadStick, Key};
+use tetra::math::Vec2;
+use tetra::{Context, ContextBuilder, Event, State};
+
+const MAX_PLAYERS: usize = 4;
+const SPEED: f32 = 6.0;
+
+const COLORS: [Color; MAX_PLAYERS] = [
+    Color::rgb(1.0, 0.4, 0.4),
+    Color::rgb(0.4, 1.0, 0.4),
+    Color::rgb(0.4, 0.4, 1.0),
+    Color::rgb(1.0, 1.0, 0.4),
+];
+
+struct GameState {
+    texture: Texture,
+    status: Text,
+    players: PlayerSlots,
+    positions: [Vec2<f32>; MAX_PLAYERS],
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        Ok(GameState {
+            texture: Texture::new(ctx, "./examples/resources/player.png")?,
+            status: Text::new(
+                "Press Enter or Start to join",
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 16.0)?,
+            ),
+            players: PlayerSlots::new(MAX_PLAYERS),
+            positions: [
+                Vec2::new(160.0, 160.0),
+                Vec2::new(480.0, 160.0),
+                Vec2::new(160.0, 320.0),
+                Vec2::new(480.0, 320.0),
+            ],
+        })
+    }
+
+    fn show_event(&mut self, event: PlayerEvent) {
+        let message = match event {
+            PlayerEvent::Joined { player, device } => {
+                format!("Player {} joined with {:?}", player + 1, device)
+            }
+            PlayerEvent::Left { player, .. } => {
+                format!("Player {}'s gamepad was disconnected", player + 1)
+            }
+            PlayerEvent::Rejoined { player, .. } => {
+                format!("Player {}'s gamepad was reconnected", player + 1)
+            }
+        };
+
+        self.status.set_content(message);
+    }
+}
+
+impl State for GameState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        for event in self.players.update(ctx) {
+            self.show_event(event);
+        }
+
+        for player in 0..MAX_PLAYERS {
+            let movement = match self.players.get_device(player) {
+                Some(InputDevice::Keyboard) => {
+                    let mut movement = Vec2::zero();
+
+                    if input::is_key_down(ctx, Key::W) {
+                        movement.y -= 1.0;
+                    }
+
+                    if input::is_key_down(ctx, Key::S) {
+                        movement.y += 1.0;
+                    }
+
+                    if input::is_key_down(ctx, Key::A) {
+                        movement.x -= 1.0;
+                    }
+
+                    if input::is_key_down(ctx, Key::D) {
+                        movement.x += 1.0;
+                    }
+
+                    movement
+                }
+
+                Some(InputDevice::Gamepad(id)) => {
+                    input::get_gamepad_stick_position(ctx, id, GamepadStick::LeftStick)
+                }
+
+                None => Vec2::zero(),
+            };
+
+            self.positions[player] += movement * SPEED;
+        }
+
+        Ok(())
+    }
+
+    fn event(&mut self, ctx: &mut Context, event: Event) -> tetra::Result {
+        if let Some(event) = self.players.event(ctx, &event) {
+            self.show_event(event);
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.392, 0.584, 0.929));
+
+        for player in self.players.players() {
+            // Players whose gamepad is disconnected are drawn faded out.
+            let color = if self.players.get_device(player).is_some() {
+                COLORS[player]
+            } else {
+                COLORS[player].with_alpha(0.3)
+            };
+
+            self.texture.draw(
+                ctx,
+                DrawParams::new()
+                    .position(self.positions[player])
+                    .origin(Vec2::new(8.0, 8.0))
+                    .scale(Vec2::new(4.0, 4.0))
+                    .color(color),
+            );
+        }
+
+        self.status.draw(ctx, Vec2::new(16.0, 16.0));
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Local Multiplayer", 640, 480)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
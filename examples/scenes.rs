@@ -0,0 +1,119 @@
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color};
+use tetra::input::{self, Key};
+use tetra::math::Vec2;
+use tetra::scene::{Scene, SceneStack, Transition};
+use tetra::{Context, ContextBuilder};
+
+struct TitleScene {
+    text: Text,
+}
+
+impl TitleScene {
+    fn new(ctx: &mut Context) -> tetra::Result<TitleScene> {
+        Ok(TitleScene {
+            text: Text::new(
+                "Title Screen\n\nPress Space to start",
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 32.0)?,
+            ),
+        })
+    }
+}
+
+impl Scene for TitleScene {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result<Transition> {
+        if input::is_key_pressed(ctx, Key::Space) {
+            Ok(Transition::Replace(Box::new(GameScene::new(ctx)?)))
+        } else {
+            Ok(Transition::None)
+        }
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.094, 0.11, 0.16));
+        self.text.draw(ctx, Vec2::new(16.0, 16.0));
+
+        Ok(())
+    }
+}
+
+struct GameScene {
+    text: Text,
+    position: Vec2<f32>,
+}
+
+impl GameScene {
+    fn new(ctx: &mut Context) -> tetra::Result<GameScene> {
+        Ok(GameScene {
+            text: Text::new(
+                "Game\n\nPress Escape to pause",
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 32.0)?,
+            ),
+            position: Vec2::new(16.0, 16.0),
+        })
+    }
+}
+
+impl Scene for GameScene {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result<Transition> {
+        self.position.x = (self.position.x + 2.0) % 640.0;
+
+        if input::is_key_pressed(ctx, Key::Escape) {
+            Ok(Transition::Push(Box::new(PauseScene::new(ctx)?)))
+        } else {
+            Ok(Transition::None)
+        }
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.392, 0.584, 0.929));
+        self.text.draw(ctx, self.position);
+
+        Ok(())
+    }
+}
+
+struct PauseScene {
+    text: Text,
+}
+
+impl PauseScene {
+    fn new(ctx: &mut Context) -> tetra::Result<PauseScene> {
+        Ok(PauseScene {
+            text: Text::new(
+                "Paused\n\nPress Escape to resume\nPress Q to quit",
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 32.0)?,
+            ),
+        })
+    }
+}
+
+impl Scene for PauseScene {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result<Transition> {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            Ok(Transition::Pop)
+        } else if input::is_key_pressed(ctx, Key::Q) {
+            Ok(Transition::Quit)
+        } else {
+            Ok(Transition::None)
+        }
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        self.text.draw(ctx, Vec2::new(16.0, 240.0));
+
+        Ok(())
+    }
+
+    // The game should still be visible behind the pause menu, but it
+    // shouldn't keep updating.
+    fn draw_previous(&self) -> bool {
+        true
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Scenes", 640, 480)
+        .build()?
+        .run(|ctx| Ok(SceneStack::new(TitleScene::new(ctx)?)))
+}
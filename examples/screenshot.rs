@@ -0,0 +1,89 @@
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams, Texture};
+use tetra::input::{self, Key};
+use tetra::math::Vec2;
+use tetra::{Context, ContextBuilder, State};
+
+struct GameState {
+    texture: Texture,
+    status: Text,
+    rotation: f32,
+    screenshots: usize,
+    take_screenshot: bool,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        Ok(GameState {
+            texture: Texture::new(ctx, "./examples/resources/player.png")?,
+            status: Text::new(
+                "Press F12 to take a screenshot, or C to start capturing frames.",
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 16.0)?,
+            ),
+            rotation: 0.0,
+            screenshots: 0,
+            take_screenshot: false,
+        })
+    }
+}
+
+impl State for GameState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        self.rotation += 0.05;
+
+        if input::is_key_pressed(ctx, Key::F12) {
+            self.take_screenshot = true;
+        }
+
+        if input::is_key_pressed(ctx, Key::C) {
+            if graphics::is_capturing_frames(ctx) {
+                let frames = graphics::stop_frame_capture(ctx)?;
+                self.status
+                    .set_content(format!("Saved {} frames to ./capture", frames));
+            } else {
+                // Saving every other frame gives a 30 FPS recording.
+                graphics::start_frame_capture(ctx, "./capture", 2)?;
+                self.status
+                    .set_content("Capturing frames - press C again to stop.");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.392, 0.584, 0.929));
+
+        self.texture.draw(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(320.0, 240.0))
+                .origin(Vec2::new(8.0, 8.0))
+                .scale(Vec2::new(8.0, 8.0))
+                .rotation(self.rotation),
+        );
+
+        // The screenshot is taken before the status text is drawn, so that
+        // it doesn't show up in the image.
+        if self.take_screenshot {
+            self.take_screenshot = false;
+
+            let path = format!("./screenshot_{}.png", self.screenshots);
+            graphics::screenshot(ctx).save(&path)?;
+
+            self.screenshots += 1;
+            self.status.set_content(format!("Saved {}", path));
+        }
+
+        self.status.draw(ctx, Vec2::new(16.0, 16.0));
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Screenshots", 640, 480)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams, Texture};
+use tetra::input::{self, Key};
+use tetra::math::Vec2;
+use tetra::time;
+use tetra::{Context, ContextBuilder, Event, State};
+
+const HIT_STOP: Duration = Duration::from_millis(150);
+
+struct GameState {
+    texture: Texture,
+    help: Text,
+    position: Vec2<f32>,
+    velocity: Vec2<f32>,
+    hit_stop: Duration,
+    menu_open: bool,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        Ok(GameState {
+            texture: Texture::new(ctx, "./examples/resources/player.png")?,
+            help: Text::new(
+                "Hold Space for slow motion\nPress P to pause",
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 16.0)?,
+            ),
+            position: Vec2::new(320.0, 240.0),
+            velocity: Vec2::new(4.0, 3.0),
+            hit_stop: Duration::ZERO,
+            menu_open: false,
+        })
+    }
+}
+
+impl State for GameState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        if input::is_key_down(ctx, Key::Space) {
+            time::set_time_scale(ctx, 0.25);
+        } else {
+            time::set_time_scale(ctx, 1.0);
+        }
+
+        self.position += self.velocity;
+
+        let mut bounced = false;
+
+        if self.position.x < 16.0 || self.position.x > 624.0 {
+            self.velocity.x = -self.velocity.x;
+            bounced = true;
+        }
+
+        if self.position.y < 16.0 || self.position.y > 464.0 {
+            self.velocity.y = -self.velocity.y;
+            bounced = true;
+        }
+
+        // Freeze the action for a moment whenever the sprite hits a wall.
+        if bounced {
+            self.hit_stop = HIT_STOP;
+            time::set_paused(ctx, true);
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        // Updates don't run while the game is paused, so the hit-stop timer
+        // is counted down in real time here instead.
+        if !self.hit_stop.is_zero() {
+            self.hit_stop = self
+                .hit_stop
+                .saturating_sub(time::get_unscaled_delta_time(ctx));
+
+            if self.hit_stop.is_zero() && !self.menu_open {
+                time::set_paused(ctx, false);
+            }
+        }
+
+        graphics::clear(ctx, Color::rgb(0.392, 0.584, 0.929));
+
+        self.texture.draw(
+            ctx,
+            DrawParams::new()
+                .position(self.position)
+                .origin(Vec2::new(8.0, 8.0))
+                .scale(Vec2::new(2.0, 2.0)),
+        );
+
+        self.help.draw(ctx, Vec2::new(16.0, 16.0));
+
+        Ok(())
+    }
+
+    fn event(&mut self, ctx: &mut Context, event: Event) -> tetra::Result {
+        // Events are still delivered while the game is paused, so this is where
+        // the pause key is handled.
+        if let Event::KeyPressed { key: Key::P } = event {
+            self.menu_open = !self.menu_open;
+            time::set_paused(ctx, self.menu_open || !self.hit_stop.is_zero());
+
+            self.help.set_content(if self.menu_open {
+                "Paused - press P to resume"
+            } else {
+                "Hold Space for slow motion\nPress P to pause"
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Slow Motion", 640, 480)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use rand::Rng;
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams, Texture};
+use tetra::input::{self, Key};
+use tetra::math::Vec2;
+use tetra::time::{Scheduler, TimerId};
+use tetra::{Context, ContextBuilder, State};
+
+#[derive(Debug, Clone)]
+enum Action {
+    Spawn,
+    Despawn,
+    SetMessage(&'static str),
+}
+
+struct GameState {
+    texture: Texture,
+    message: Text,
+    scheduler: Scheduler<Action>,
+    spawner: Option<TimerId>,
+    positions: Vec<Vec2<f32>>,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        let mut scheduler = Scheduler::new();
+
+        let spawner = scheduler.every(Duration::from_millis(500), Action::Spawn);
+        scheduler.every(Duration::from_secs(2), Action::Despawn);
+
+        // Sequences can be used to script a series of events.
+        scheduler.sequence([
+            (Duration::from_secs(1), Action::SetMessage("Welcome!")),
+            (
+                Duration::from_secs(2),
+                Action::SetMessage("Things will keep spawning..."),
+            ),
+            (
+                Duration::from_secs(2),
+                Action::SetMessage("...until you press Space."),
+            ),
+        ]);
+
+        Ok(GameState {
+            texture: Texture::new(ctx, "./examples/resources/player.png")?,
+            message: Text::new(
+                "",
+                Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 16.0)?,
+            ),
+            scheduler,
+            spawner: Some(spawner),
+            positions: Vec::new(),
+        })
+    }
+}
+
+impl State for GameState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        if input::is_key_pressed(ctx, Key::Space) {
+            if let Some(spawner) = self.spawner.take() {
+                self.scheduler.cancel(spawner);
+                self.message.set_content("Stopped spawning.");
+            }
+        }
+
+        for action in self.scheduler.update(ctx) {
+            match action {
+                Action::Spawn => {
+                    let mut rng = rand::thread_rng();
+
+                    self.positions.push(Vec2::new(
+                        rng.gen_range(32.0..608.0),
+                        rng.gen_range(64.0..448.0),
+                    ));
+                }
+
+                Action::Despawn => {
+                    if !self.positions.is_empty() {
+                        self.positions.remove(0);
+                    }
+                }
+
+                Action::SetMessage(message) => self.message.set_content(message),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.392, 0.584, 0.929));
+
+        for position in &self.positions {
+            self.texture.draw(
+                ctx,
+                DrawParams::new()
+                    .position(*position)
+                    .origin(Vec2::new(8.0, 8.0))
+                    .scale(Vec2::new(2.0, 2.0)),
+            );
+        }
+
+        self.message.draw(ctx, Vec2::new(16.0, 16.0));
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Timers", 640, 480)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
@@ -0,0 +1,71 @@
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams};
+use tetra::input;
+use tetra::math::Vec2;
+use tetra::{Context, ContextBuilder, Event, State};
+
+struct GameState {
+    circle: Mesh,
+    label: Text,
+    last_event: Text,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        let font = Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 16.0)?;
+
+        Ok(GameState {
+            circle: Mesh::circle(ctx, ShapeStyle::Fill, Vec2::zero(), 32.0)?,
+            label: Text::new("", font.clone()),
+            last_event: Text::new("Touch the screen", font),
+        })
+    }
+}
+
+impl State for GameState {
+    fn event(&mut self, _: &mut Context, event: Event) -> tetra::Result {
+        if let Event::TouchStarted { .. } | Event::TouchEnded { .. } = event {
+            self.last_event.set_content(format!("{:?}", event));
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.392, 0.584, 0.929));
+
+        let primary = input::get_primary_touch(ctx).map(|t| t.id);
+
+        for touch in input::get_touches(ctx).to_vec() {
+            let color = if Some(touch.id) == primary {
+                Color::rgb(1.0, 0.4, 0.4)
+            } else {
+                Color::WHITE
+            };
+
+            self.circle.draw(
+                ctx,
+                DrawParams::new()
+                    .position(touch.position)
+                    // Pressing harder makes the circle bigger, on devices that support it.
+                    .scale(Vec2::broadcast(0.5 + touch.pressure))
+                    .color(color),
+            );
+
+            self.label.set_content(touch.id.to_string());
+            self.label.draw(ctx, touch.position + Vec2::new(40.0, -8.0));
+        }
+
+        self.last_event.draw(ctx, Vec2::new(16.0, 16.0));
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Touch Input", 640, 480)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
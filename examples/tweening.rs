@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{self, Color, DrawParams, Texture};
+use tetra::math::Vec2;
+use tetra::tween::{Easing, Parallel, Repeat, Sequence, Tween, Tweener};
+use tetra::{Context, ContextBuilder, State};
+
+const EASINGS: [Easing; 6] = [
+    Easing::Linear,
+    Easing::SineInOut,
+    Easing::CubicInOut,
+    Easing::BackOut,
+    Easing::ElasticOut,
+    Easing::BounceOut,
+];
+
+struct GameState {
+    texture: Texture,
+    labels: Vec<Text>,
+
+    // One tweener per easing curve, to compare them side by side.
+    sliders: Vec<Tweener<Tween<f32>>>,
+
+    // A sequence that moves the sprite around, while its color
+    // changes in parallel.
+    pulse: Tweener<Parallel<Sequence<DrawParams>, Tween<Color>>>,
+}
+
+impl GameState {
+    fn new(ctx: &mut Context) -> tetra::Result<GameState> {
+        let font = Font::vector(ctx, "./examples/resources/DejaVuSansMono.ttf", 16.0)?;
+
+        let labels = EASINGS
+            .iter()
+            .map(|easing| Text::new(format!("{:?}", easing), font.clone()))
+            .collect();
+
+        let sliders = EASINGS
+            .iter()
+            .map(|easing| {
+                Tweener::new(Tween::new(200.0, 600.0, Duration::from_secs(2), *easing))
+                    .repeat(Repeat::Forever)
+                    .yoyo(true)
+            })
+            .collect();
+
+        let start = DrawParams::new()
+            .position(Vec2::new(400.0, 480.0))
+            .origin(Vec2::new(8.0, 8.0))
+            .scale(Vec2::new(4.0, 4.0));
+
+        let grown = start.clone().scale(Vec2::new(8.0, 8.0)).rotation(0.5);
+
+        let movement = Sequence::new(Tween::new(
+            start.clone(),
+            grown.clone(),
+            Duration::from_millis(600),
+            Easing::BackOut,
+        ))
+        .then(Tween::hold(grown.clone(), Duration::from_millis(400)))
+        .then(Tween::new(
+            grown,
+            start,
+            Duration::from_millis(1000),
+            Easing::BounceOut,
+        ));
+
+        let color = Tween::new(
+            Color::WHITE,
+            Color::rgb(1.0, 0.4, 0.4),
+            Duration::from_millis(2000),
+            Easing::SineInOut,
+        );
+
+        Ok(GameState {
+            texture: Texture::new(ctx, "./examples/resources/player.png")?,
+            labels,
+            sliders,
+            pulse: Tweener::new(Parallel(movement, color)).repeat(Repeat::Forever),
+        })
+    }
+}
+
+impl State for GameState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        for slider in &mut self.sliders {
+            slider.update(ctx);
+        }
+
+        self.pulse.update(ctx);
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        graphics::clear(ctx, Color::rgb(0.094, 0.11, 0.16));
+
+        for (i, (label, slider)) in self.labels.iter_mut().zip(&self.sliders).enumerate() {
+            let y = 32.0 + i as f32 * 48.0;
+
+            label.draw(ctx, Vec2::new(16.0, y));
+
+            self.texture.draw(
+                ctx,
+                DrawParams::new()
+                    .position(Vec2::new(slider.value(), y + 8.0))
+                    .origin(Vec2::new(8.0, 8.0))
+                    .scale(Vec2::new(2.0, 2.0)),
+            );
+        }
+
+        let (params, color) = self.pulse.value();
+        self.texture.draw(ctx, params.color(color));
+
+        Ok(())
+    }
+}
+
+fn main() -> tetra::Result {
+    ContextBuilder::new("Tweening", 800, 600)
+        .quit_on_escape(true)
+        .build()?
+        .run(GameState::new)
+}
@@ -65,7 +65,7 @@ impl Sound {
         P: AsRef<Path>,
     {
         Ok(Sound {
-            data: fs::read(path)?.into(),
+            data: fs::read_asset(path)?.into(),
         })
     }
 
@@ -329,6 +329,7 @@ struct AudioStream {
 pub(crate) struct AudioDevice {
     stream: Option<AudioStream>,
     master_volume: Arc<AtomicU32>,
+    suspended: Arc<AtomicBool>,
 }
 
 impl AudioDevice {
@@ -343,6 +344,19 @@ impl AudioDevice {
         AudioDevice {
             stream,
             master_volume: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            suspended: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Creates a device without an output stream, for use in headless contexts.
+    ///
+    /// Attempting to play sounds via this device will return
+    /// [`TetraError::NoAudioDevice`].
+    pub(crate) fn null() -> AudioDevice {
+        AudioDevice {
+            stream: None,
+            master_volume: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            suspended: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -354,6 +368,12 @@ impl AudioDevice {
         self.master_volume.store(volume.to_bits(), Ordering::SeqCst);
     }
 
+    /// Pauses or resumes every sound that is playing, without changing the state
+    /// of their `SoundInstance`s.
+    pub(crate) fn set_suspended(&self, suspended: bool) {
+        self.suspended.store(suspended, Ordering::SeqCst);
+    }
+
     fn play_sound(
         &self,
         data: Arc<[u8]>,
@@ -381,6 +401,7 @@ impl AudioDevice {
             data,
 
             remote_master_volume: Arc::clone(&self.master_volume),
+            remote_suspended: Arc::clone(&self.suspended),
             remote_controls: Arc::clone(&controls),
             time_till_update: 220,
 
@@ -388,6 +409,7 @@ impl AudioDevice {
             playing,
             repeating,
             rewind: false,
+            suspended: self.suspended.load(Ordering::SeqCst),
             master_volume,
             volume,
             speed,
@@ -414,6 +436,7 @@ struct TetraSource {
     repeat_source: TetraSourceData,
 
     remote_master_volume: Arc<AtomicU32>,
+    remote_suspended: Arc<AtomicBool>,
     remote_controls: Arc<AudioControls>,
     time_till_update: u32,
 
@@ -421,6 +444,7 @@ struct TetraSource {
     playing: bool,
     repeating: bool,
     rewind: bool,
+    suspended: bool,
     master_volume: f32,
     volume: f32,
     speed: f32,
@@ -439,6 +463,7 @@ impl Iterator for TetraSource {
 
         if self.time_till_update == 0 {
             self.master_volume = f32::from_bits(self.remote_master_volume.load(Ordering::SeqCst));
+            self.suspended = self.remote_suspended.load(Ordering::SeqCst);
             self.playing = self.remote_controls.playing.load(Ordering::SeqCst);
 
             // If we're not playing, we don't really care about updating the rest of the state.
@@ -462,6 +487,12 @@ impl Iterator for TetraSource {
             return if self.detached { None } else { Some(0) };
         }
 
+        // Suspended sounds output silence until they are resumed, even if they
+        // have been detached.
+        if self.suspended {
+            return Some(0);
+        }
+
         if self.rewind {
             self.data = self.repeat_source.clone();
             self.rewind = false;
@@ -1,11 +1,13 @@
 use std::result;
+use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
-use crate::graphics::{self, GraphicsContext};
+use crate::fs::Vfs;
+use crate::graphics::{self, hot_reload, GraphicsContext};
 use crate::input::{self, InputContext};
 use crate::platform::{self, GraphicsDevice, Window};
-use crate::time::{self, TimeContext, Timestep};
+use crate::time::{self, FrameTimings, TimeContext, Timestep};
 use crate::{Result, State, TetraError};
 
 #[cfg(feature = "audio")]
@@ -20,20 +22,44 @@ pub struct Context {
     pub(crate) graphics: GraphicsContext,
     pub(crate) input: InputContext,
     pub(crate) time: TimeContext,
+    pub(crate) fs: Arc<Vfs>,
 
     pub(crate) running: bool,
     pub(crate) quit_on_escape: bool,
     pub(crate) fps_limit: bool,
+    pub(crate) pause_on_focus_lost: bool,
+    pub(crate) suspended: bool,
 }
 
 impl Context {
     pub(crate) fn new(settings: &ContextBuilder) -> Result<Context> {
         // This needs to be initialized ASAP to avoid https://github.com/tomaka/rodio/issues/214
         #[cfg(feature = "audio")]
-        let audio = AudioDevice::new();
+        let audio = if settings.headless {
+            AudioDevice::null()
+        } else {
+            AudioDevice::new()
+        };
 
-        let (window, gl_context, window_width, window_height) = Window::new(settings)?;
-        let mut device = GraphicsDevice::new(gl_context)?;
+        let (window, mut device, window_width, window_height) = if settings.headless {
+            let (window, window_width, window_height) = Window::new_null(settings);
+
+            (
+                window,
+                GraphicsDevice::new_null(),
+                window_width,
+                window_height,
+            )
+        } else {
+            let (window, gl_context, window_width, window_height) = Window::new(settings)?;
+
+            (
+                window,
+                GraphicsDevice::new(gl_context)?,
+                window_width,
+                window_height,
+            )
+        };
 
         if settings.debug_info {
             let device_info = device.get_info();
@@ -44,9 +70,17 @@ impl Context {
             println!("GLSL Version: {}", device_info.glsl_version);
         }
 
-        let graphics = GraphicsContext::new(&mut device, window_width, window_height)?;
-        let input = InputContext::new();
-        let time = TimeContext::new(settings.timestep);
+        let graphics = GraphicsContext::new(
+            &mut device,
+            window_width,
+            window_height,
+            settings.hot_reload,
+        )?;
+        let input = InputContext::new(settings);
+        let time = TimeContext::new(settings);
+
+        let fs = Arc::new(Vfs::new());
+        fs.activate();
 
         Ok(Context {
             window,
@@ -57,11 +91,14 @@ impl Context {
             graphics,
             input,
             time,
+            fs,
 
-            running: false,
+            running: true,
             quit_on_escape: settings.quit_on_escape,
 
-            fps_limit: settings.fps_limit
+            fps_limit: settings.fps_limit,
+            pause_on_focus_lost: settings.pause_on_focus_lost,
+            suspended: false,
         })
     }
 
@@ -136,53 +173,222 @@ impl Context {
         S: State<E>,
         E: From<TetraError>,
     {
-        let mut last_time = Instant::now();
-
         while self.running {
-            let curr_time = Instant::now();
-            let diff_time = curr_time - last_time;
-            last_time = curr_time;
+            let frame_start = Instant::now();
 
-            self.time.fps_tracker.push(diff_time);
+            self.step(state)?;
 
-            platform::handle_events(self, state)?;
+            match time::get_frame_target(self) {
+                Some(frame_time) => time::wait_until(frame_start + frame_time),
 
-            match self.time.tick_rate {
-                Some(tick_rate) => {
-                    self.time.delta_time = tick_rate;
-                    self.time.accumulator = (self.time.accumulator + diff_time).min(tick_rate * 8);
+                // This provides a sensible FPS limit when running without vsync, and
+                // avoids CPU usage skyrocketing on some systems.
+                None if self.fps_limit => thread::sleep(Duration::from_millis(1)),
 
-                    while self.time.accumulator >= tick_rate {
-                        state.update(self)?;
-                        input::clear(self);
+                None => {}
+            }
+        }
 
-                        self.time.accumulator -= tick_rate;
-                    }
+        Ok(())
+    }
 
-                    self.time.delta_time = diff_time;
-                }
+    /// Runs a single iteration of the game loop, measuring the time that has passed
+    /// since the last iteration using the system clock.
+    ///
+    /// This allows you to drive the game loop yourself, rather than handing control
+    /// over to [`run`](Context::run) - for example, if you are embedding Tetra in an
+    /// editor or another application's main loop. If you need to control how much
+    /// time passes between iterations (e.g. in automated tests), use
+    /// [`tick`](Context::tick) instead.
+    ///
+    /// The first call to this method will be treated as if no time has passed.
+    ///
+    /// Unlike [`run`](Context::run), this method does not show the window, sleep to
+    /// limit the frame rate, or stop when the game quits - you can use
+    /// [`is_running`](Context::is_running) to check whether the game has been
+    /// asked to close.
+    ///
+    /// # Errors
+    ///
+    /// If the [`State`] returns an error from [`update`](State::update), [`draw`](State::draw)
+    /// or [`event`](State::event), this method will return the error.
+    pub fn step<S, E>(&mut self, state: &mut S) -> result::Result<(), E>
+    where
+        S: State<E>,
+        E: From<TetraError>,
+    {
+        let curr_time = Instant::now();
+
+        let diff_time = match self.time.last_time {
+            Some(last_time) => curr_time - last_time,
+            None => Duration::from_secs(0),
+        };
+
+        self.time.last_time = Some(curr_time);
+
+        self.tick(state, diff_time)
+    }
+
+    /// Runs a single iteration of the game loop, as if `elapsed` time had passed since
+    /// the last iteration.
+    ///
+    /// Each iteration processes any pending events, runs as many updates as the
+    /// elapsed time allows (or a single update, if a variable timestep is being used),
+    /// draws the game and then presents the frame. This makes it possible to advance
+    /// the game deterministically - for example, in a fixed timestep game, calling
+    /// this method with the tick rate as the elapsed time will run exactly one update
+    /// per call.
+    ///
+    /// This method does not affect the clock used by [`step`](Context::step).
+    ///
+    /// While input is being played back via [`input::start_playback`], `elapsed` will
+    /// be replaced by the time that was recorded for the current frame.
+    ///
+    /// # Errors
+    ///
+    /// If the [`State`] returns an error from [`update`](State::update), [`draw`](State::draw)
+    /// or [`event`](State::event), this method will return the error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use tetra::{ContextBuilder, State};
+    /// # struct GameState;
+    /// # impl State for GameState {}
+    /// # fn main() -> tetra::Result {
+    /// let mut ctx = ContextBuilder::new("Hello, world!", 1280, 720)
+    ///     .headless(true)
+    ///     .build()?;
+    ///
+    /// let mut state = GameState;
+    ///
+    /// // Advance the game by exactly ten frames:
+    /// for _ in 0..10 {
+    ///     ctx.tick(&mut state, Duration::from_secs_f64(1.0 / 60.0))?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tick<S, E>(&mut self, state: &mut S, elapsed: Duration) -> result::Result<(), E>
+    where
+        S: State<E>,
+        E: From<TetraError>,
+    {
+        // If input is being played back, the frame should take as long as it
+        // did when it was recorded.
+        let elapsed = input::begin_frame(self, elapsed);
+
+        self.time.fps_tracker.push(elapsed);
+
+        let mut timings = FrameTimings::default();
+        let mut phase_start = Instant::now();
+
+        platform::handle_events(self, state)?;
+        input::play_back_events(self, state)?;
+        hot_reload::poll(self, state, elapsed)?;
+
+        timings.events = phase_start.elapsed();
+        phase_start = Instant::now();
+
+        self.update_suspended();
+
+        self.time.unscaled_delta_time = elapsed;
+        self.time.unscaled_time += elapsed;
+
+        let paused = self.time.paused || self.suspended;
+
+        // While paused, game time stands still - this also stops the accumulator
+        // from building up a burst of updates to run when the game is unpaused.
+        let elapsed = if paused {
+            Duration::ZERO
+        } else {
+            elapsed.mul_f64(self.time.time_scale)
+        };
+
+        match self.time.tick_rate {
+            Some(tick_rate) => {
+                let accumulator = self.time.accumulator + elapsed;
+
+                self.time.delta_time = tick_rate;
+                self.time.accumulator = accumulator.min(tick_rate * 8);
 
-                None => {
-                    self.time.delta_time = diff_time;
+                timings.dropped = accumulator - self.time.accumulator;
 
+                while self.time.accumulator >= tick_rate {
                     state.update(self)?;
                     input::clear(self);
+
+                    self.time.accumulator -= tick_rate;
+                    timings.updates += 1;
                 }
+
+                self.time.delta_time = elapsed;
             }
 
-            state.draw(self)?;
+            None => {
+                self.time.delta_time = elapsed;
 
-            graphics::present(self);
+                if !paused {
+                    state.update(self)?;
+                    input::clear(self);
 
-            // This provides a sensible FPS limit when running without vsync, and
-            // avoids CPU usage skyrocketing on some systems.
-            if self.fps_limit {
-                thread::sleep(Duration::from_millis(1));
+                    timings.updates += 1;
+                }
             }
         }
 
+        timings.update = phase_start.elapsed();
+        phase_start = Instant::now();
+
+        state.draw(self)?;
+
+        // Input that arrives while paused would otherwise be seen by the first
+        // update after unpausing.
+        if paused {
+            input::clear(self);
+        }
+
+        timings.draw = phase_start.elapsed();
+        phase_start = Instant::now();
+
+        graphics::present(self);
+
+        timings.present = phase_start.elapsed();
+        self.time.last_frame = timings;
+
         Ok(())
     }
+
+    /// Suspends or resumes the game if the window has lost or regained focus, when
+    /// `pause_on_focus_lost` is enabled.
+    fn update_suspended(&mut self) {
+        let suspended =
+            self.pause_on_focus_lost && (!self.window.is_focused() || self.window.is_minimized());
+
+        if suspended != self.suspended {
+            self.suspended = suspended;
+
+            #[cfg(feature = "audio")]
+            self.audio.set_suspended(suspended);
+        }
+    }
+
+    /// Returns whether or not the game is still running.
+    ///
+    /// This will return `false` once the game has been asked to close (e.g. via
+    /// [`window::quit`](crate::window::quit), or by the user closing the window).
+    /// This is mainly useful when driving the game loop manually via
+    /// [`step`](Context::step) or [`tick`](Context::tick).
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        self.fs.deactivate();
+    }
 }
 
 /// Settings that can be configured when starting up a game.
@@ -221,9 +427,15 @@ pub struct ContextBuilder {
     pub(crate) show_mouse: bool,
     pub(crate) grab_mouse: bool,
     pub(crate) relative_mouse_mode: bool,
+    pub(crate) touch_mouse_emulation: bool,
     pub(crate) quit_on_escape: bool,
     pub(crate) fps_limit: bool,
+    pub(crate) target_fps: Option<f64>,
+    pub(crate) background_fps: Option<f64>,
+    pub(crate) pause_on_focus_lost: bool,
     pub(crate) debug_info: bool,
+    pub(crate) hot_reload: bool,
+    pub(crate) headless: bool,
 }
 
 impl ContextBuilder {
@@ -280,12 +492,61 @@ impl ContextBuilder {
     /// to provide a sensible FPS limit when running without vsync, and to avoid CPU usage
     /// skyrocketing on some systems.
     ///
+    /// This has no effect if a [`target_fps`](ContextBuilder::target_fps) is set.
+    ///
     /// Defaults to `true`.
     pub fn fps_limit(&mut self, fps_limit: bool) -> &mut ContextBuilder {
         self.fps_limit = fps_limit;
         self
     }
 
+    /// Sets the maximum number of frames that will be run per second.
+    ///
+    /// Unlike [`fps_limit`](ContextBuilder::fps_limit), this paces each frame precisely,
+    /// by sleeping for most of the remaining time and then spin-waiting for the rest.
+    /// This can be used to cap the frame rate when vsync is disabled or unavailable.
+    ///
+    /// This can be changed at runtime via [`time::set_target_fps`].
+    ///
+    /// Defaults to `None`.
+    pub fn target_fps(&mut self, target_fps: Option<f64>) -> &mut ContextBuilder {
+        self.target_fps = target_fps;
+        self
+    }
+
+    /// Sets the maximum number of frames that will be run per second while the window
+    /// is unfocused or minimized.
+    ///
+    /// A low value (e.g. `10.0`) will stop the game from using a lot of CPU and battery
+    /// while it is in the background. If this is `None`, the
+    /// [`target_fps`](ContextBuilder::target_fps) is used instead.
+    ///
+    /// This can be changed at runtime via [`time::set_background_fps`].
+    ///
+    /// Defaults to `None`.
+    pub fn background_fps(&mut self, background_fps: Option<f64>) -> &mut ContextBuilder {
+        self.background_fps = background_fps;
+        self
+    }
+
+    /// Sets whether or not the game should pause itself while the window is unfocused
+    /// or minimized.
+    ///
+    /// While paused, [`State::update`] will not be called and all sounds will be paused,
+    /// but the game will still be drawn and receive events. Once the window regains
+    /// focus, everything resumes from where it left off - the time that passed in
+    /// the background is discarded, rather than being caught up on.
+    ///
+    /// This does not affect [`time::is_paused`]. To reduce how often the game is
+    /// drawn while it is in the background, use this alongside
+    /// [`background_fps`](ContextBuilder::background_fps).
+    ///
+    /// Defaults to `false`.
+    pub fn pause_on_focus_lost(&mut self, pause_on_focus_lost: bool) -> &mut ContextBuilder {
+        self.pause_on_focus_lost = pause_on_focus_lost;
+        self
+    }
+
     /// Sets the game's timestep.
     ///
     /// Defaults to `Timestep::Fixed(60.0)`.
@@ -441,6 +702,17 @@ impl ContextBuilder {
         self
     }
 
+    /// Sets whether or not mouse events should be generated from the primary touch,
+    /// so that games which only handle mouse input can be played on a touchscreen.
+    ///
+    /// This can be changed at runtime via [`input::set_touch_mouse_emulation`](crate::input::set_touch_mouse_emulation).
+    ///
+    /// Defaults to `true`.
+    pub fn touch_mouse_emulation(&mut self, touch_mouse_emulation: bool) -> &mut ContextBuilder {
+        self.touch_mouse_emulation = touch_mouse_emulation;
+        self
+    }
+
     /// Sets whether or not the game should close when the Escape key is pressed.
     ///
     /// Defaults to `false`.
@@ -456,6 +728,50 @@ impl ContextBuilder {
         self
     }
 
+    /// Sets whether or not assets should be reloaded when the files that they were
+    /// loaded from change.
+    ///
+    /// While this is enabled, the files backing any [`Texture`](crate::graphics::Texture),
+    /// [`Shader`](crate::graphics::Shader) or [`Font`](crate::graphics::text::Font) that
+    /// was loaded from a path will be checked for changes a few times per second. When
+    /// a file changes, the asset is reloaded in place, so every existing clone of it
+    /// will see the new data.
+    ///
+    /// An [`Event::AssetReloaded`](crate::Event::AssetReloaded) will be fired after each
+    /// reload. If a reload fails (for example, because a shader did not compile), an
+    /// [`Event::AssetReloadFailed`](crate::Event::AssetReloadFailed) will be fired instead,
+    /// and the asset will keep its old data.
+    ///
+    /// Reloading a shader resets any uniforms that were set via
+    /// [`Shader::set_uniform`](crate::graphics::Shader::set_uniform), other than textures.
+    ///
+    /// This is intended for use during development - you will probably want to
+    /// disable it in release builds.
+    ///
+    /// Defaults to `false`.
+    pub fn hot_reload(&mut self, hot_reload: bool) -> &mut ContextBuilder {
+        self.hot_reload = hot_reload;
+        self
+    }
+
+    /// Sets whether or not the context should be created without a window or
+    /// a graphics device.
+    ///
+    /// In headless mode, no window is opened and nothing is sent to the GPU - instead,
+    /// the window state is simulated, and draw calls are recorded so that they can be
+    /// inspected via [`graphics::take_draw_calls`](crate::graphics::take_draw_calls).
+    /// Audio output is also disabled. This is primarily useful for running automated
+    /// tests on machines that do not have a display.
+    ///
+    /// Shaders are not compiled in headless mode, so invalid shader code will not
+    /// cause an error.
+    ///
+    /// Defaults to `false`.
+    pub fn headless(&mut self, headless: bool) -> &mut ContextBuilder {
+        self.headless = headless;
+        self
+    }
+
     /// Builds the context.
     ///
     /// # Errors
@@ -487,9 +803,324 @@ impl Default for ContextBuilder {
             show_mouse: false,
             grab_mouse: false,
             relative_mouse_mode: false,
+            touch_mouse_emulation: true,
             quit_on_escape: false,
             fps_limit: true,
+            target_fps: None,
+            background_fps: None,
+            pause_on_focus_lost: false,
             debug_info: false,
+            hot_reload: false,
+            headless: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::{self, Canvas, Color, Texture, TextureFormat};
+    use crate::math::Vec2;
+    use crate::window;
+    use crate::Event;
+
+    #[derive(Default)]
+    struct CountingState {
+        updates: usize,
+        draws: usize,
+    }
+
+    impl State for CountingState {
+        fn update(&mut self, _: &mut Context) -> Result {
+            self.updates += 1;
+            Ok(())
+        }
+
+        fn draw(&mut self, _: &mut Context) -> Result {
+            self.draws += 1;
+            Ok(())
+        }
+    }
+
+    fn headless_context() -> Context {
+        ContextBuilder::new("Headless", 640, 480)
+            .headless(true)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn headless_records_draw_calls() {
+        let mut ctx = headless_context();
+
+        let texture = Texture::from_data(
+            &mut ctx,
+            2,
+            1,
+            TextureFormat::Rgba8,
+            &[255, 0, 0, 255, 0, 255, 0, 255],
+        )
+        .unwrap();
+
+        texture.draw(&mut ctx, Vec2::new(16.0, 32.0));
+        graphics::flush(&mut ctx);
+
+        let draw_calls = graphics::take_draw_calls(&mut ctx);
+
+        assert_eq!(draw_calls.len(), 1);
+        assert_eq!(draw_calls[0].vertices.len(), 6);
+        assert_eq!(draw_calls[0].texture_size, (2, 1));
+        assert!(!draw_calls[0].to_canvas);
+        assert_eq!(draw_calls[0].vertices[0].position, Vec2::new(16.0, 32.0));
+
+        assert!(graphics::take_draw_calls(&mut ctx).is_empty());
+    }
+
+    #[test]
+    fn headless_texture_data_round_trip() {
+        let mut ctx = headless_context();
+
+        let texture = Texture::from_data(&mut ctx, 2, 2, TextureFormat::Rgba8, &[0; 16]).unwrap();
+
+        texture
+            .set_data(&mut ctx, 1, 1, 1, 1, &[1, 2, 3, 4])
+            .unwrap();
+
+        let data = texture.get_data(&mut ctx);
+
+        assert_eq!(
+            data.get_pixel_color(Vec2::new(1, 1)),
+            Color::rgba8(1, 2, 3, 4)
+        );
+        assert_eq!(
+            data.get_pixel_color(Vec2::new(0, 0)),
+            Color::rgba8(0, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn headless_canvas_draws_are_marked() {
+        let mut ctx = headless_context();
+
+        let canvas = Canvas::new(&mut ctx, 32, 32).unwrap();
+
+        graphics::set_canvas(&mut ctx, &canvas);
+        graphics::clear(&mut ctx, Color::BLACK);
+        canvas.draw(&mut ctx, Vec2::zero());
+        graphics::reset_canvas(&mut ctx);
+
+        let draw_calls = graphics::take_draw_calls(&mut ctx);
+
+        assert_eq!(draw_calls.len(), 1);
+        assert!(draw_calls[0].to_canvas);
+        assert_eq!(draw_calls[0].texture_size, (32, 32));
+    }
+
+    #[test]
+    fn headless_window_state() {
+        let mut ctx = headless_context();
+
+        assert_eq!(window::get_size(&ctx), (640, 480));
+
+        window::set_size(&mut ctx, 800, 600).unwrap();
+
+        assert_eq!(window::get_size(&ctx), (800, 600));
+        assert_eq!(window::get_monitor_size(&ctx, 0).unwrap(), (800, 600));
+    }
+
+    #[test]
+    fn tick_runs_fixed_updates() {
+        let mut ctx = headless_context();
+        let mut state = CountingState::default();
+
+        let tick_rate = Duration::from_secs_f64(1.0 / 60.0);
+
+        for _ in 0..10 {
+            ctx.tick(&mut state, tick_rate).unwrap();
+        }
+
+        assert_eq!(state.updates, 10);
+        assert_eq!(state.draws, 10);
+
+        ctx.tick(&mut state, tick_rate / 2).unwrap();
+
+        assert_eq!(state.updates, 10);
+        assert_eq!(state.draws, 11);
+        assert_eq!(time::get_accumulator(&ctx), tick_rate / 2);
+    }
+
+    #[test]
+    fn tick_clamps_accumulator() {
+        let mut ctx = headless_context();
+        let mut state = CountingState::default();
+
+        ctx.tick(&mut state, Duration::from_secs(10)).unwrap();
+
+        assert_eq!(state.updates, 8);
+        assert_eq!(state.draws, 1);
+    }
+
+    #[test]
+    fn tick_variable_timestep() {
+        let mut ctx = headless_context();
+        let mut state = CountingState::default();
+
+        time::set_timestep(&mut ctx, Timestep::Variable);
+
+        ctx.tick(&mut state, Duration::from_millis(250)).unwrap();
+
+        assert_eq!(state.updates, 1);
+        assert_eq!(time::get_delta_time(&ctx), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn tick_applies_time_scale() {
+        let mut ctx = headless_context();
+        let mut state = CountingState::default();
+
+        let tick_rate = Duration::from_secs_f64(1.0 / 60.0);
+        time::set_time_scale(&mut ctx, 0.5);
+
+        // At half speed, two ticks' worth of time should only run one update.
+        for _ in 0..10 {
+            ctx.tick(&mut state, tick_rate * 2).unwrap();
+        }
+
+        assert_eq!(state.updates, 10);
+        assert_eq!(state.draws, 10);
+        assert_eq!(time::get_delta_time(&ctx), tick_rate);
+        assert_eq!(time::get_unscaled_delta_time(&ctx), tick_rate * 2);
+    }
+
+    #[test]
+    fn tick_skips_updates_while_paused() {
+        let mut ctx = headless_context();
+        let mut state = CountingState::default();
+
+        time::set_paused(&mut ctx, true);
+        ctx.tick(&mut state, Duration::from_secs(1)).unwrap();
+
+        assert_eq!(state.updates, 0);
+        assert_eq!(state.draws, 1);
+        assert_eq!(time::get_delta_time(&ctx), Duration::ZERO);
+
+        // Unpausing should not run the updates that were skipped.
+        time::set_paused(&mut ctx, false);
+        ctx.tick(&mut state, Duration::from_secs_f64(1.0 / 60.0))
+            .unwrap();
+
+        assert_eq!(state.updates, 1);
+
+        time::set_timestep(&mut ctx, Timestep::Variable);
+        time::set_paused(&mut ctx, true);
+        ctx.tick(&mut state, Duration::from_secs(1)).unwrap();
+
+        assert_eq!(state.updates, 1);
+        assert_eq!(state.draws, 3);
+    }
+
+    #[test]
+    fn pause_on_focus_lost() {
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .pause_on_focus_lost(true)
+            .build()
+            .unwrap();
+
+        let mut state = CountingState::default();
+        let tick_rate = Duration::from_secs_f64(1.0 / 60.0);
+
+        ctx.tick(&mut state, tick_rate).unwrap();
+        assert_eq!(state.updates, 1);
+
+        window::minimize(&mut ctx);
+
+        for _ in 0..10 {
+            ctx.tick(&mut state, tick_rate).unwrap();
+        }
+
+        assert_eq!(state.updates, 1);
+        assert_eq!(state.draws, 11);
+        assert!(!time::is_paused(&ctx));
+
+        // The time spent in the background should not be caught up on.
+        window::restore(&mut ctx);
+        ctx.tick(&mut state, tick_rate).unwrap();
+
+        assert_eq!(state.updates, 2);
+    }
+
+    #[test]
+    fn tick_records_frame_stats() {
+        let mut ctx = headless_context();
+        let mut state = CountingState::default();
+
+        let tick_rate = Duration::from_secs_f64(1.0 / 60.0);
+
+        for ms in 1..=100 {
+            ctx.tick(&mut state, Duration::from_millis(ms)).unwrap();
+        }
+
+        let stats = time::get_frame_stats(&ctx);
+
+        assert_eq!(stats.min_frame_time, Duration::from_millis(1));
+        assert_eq!(stats.max_frame_time, Duration::from_millis(100));
+        assert_eq!(stats.median_frame_time, Duration::from_millis(50));
+        assert_eq!(stats.p95_frame_time, Duration::from_millis(95));
+        assert_eq!(stats.p99_frame_time, Duration::from_millis(99));
+
+        // The accumulator can hold at most eight ticks, so the rest of a
+        // long frame should be reported as dropped.
+        ctx.tick(&mut state, Duration::from_secs(1)).unwrap();
+
+        let stats = time::get_frame_stats(&ctx);
+
+        assert_eq!(stats.updates, 8);
+        assert!(stats.dropped_time >= Duration::from_secs(1) - tick_rate * 8);
+        assert_eq!(stats.max_frame_time, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn step_stops_running_on_quit() {
+        let mut ctx = headless_context();
+        let mut state = CountingState::default();
+
+        assert!(ctx.is_running());
+
+        ctx.step(&mut state).unwrap();
+        window::quit(&mut ctx);
+
+        assert!(!ctx.is_running());
+        assert_eq!(state.draws, 1);
+    }
+
+    #[test]
+    fn quit_requests_can_be_cancelled() {
+        struct ConfirmQuit {
+            confirmed: bool,
+        }
+
+        impl State for ConfirmQuit {
+            fn event(&mut self, ctx: &mut Context, event: Event) -> Result {
+                if let Event::QuitRequested = event {
+                    if !self.confirmed {
+                        window::cancel_quit(ctx);
+                    }
+                }
+
+                Ok(())
+            }
+        }
+
+        let mut ctx = headless_context();
+        let mut state = ConfirmQuit { confirmed: false };
+
+        window::request_quit(&mut ctx, &mut state).unwrap();
+        assert!(ctx.is_running());
+
+        state.confirmed = true;
+
+        window::request_quit(&mut ctx, &mut state).unwrap();
+        assert!(!ctx.is_running());
+    }
+}
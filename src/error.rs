@@ -37,6 +37,16 @@ pub enum TetraError {
         path: PathBuf,
     },
 
+    /// Returned when your game fails to save a file. This is usually caused by an
+    /// incorrect file path, or some form of permission issues.
+    FailedToSaveFile {
+        /// The underlying reason for the error.
+        reason: io::Error,
+
+        /// The path to the file that failed to save.
+        path: PathBuf,
+    },
+
     /// Returned when a color is invalid.
     InvalidColor,
 
@@ -77,6 +87,12 @@ pub enum TetraError {
 
     /// Returned when a shape cannot be tessellated.
     TessellationError(TessellationError),
+
+    /// Returned when an input recording could not be decoded.
+    InvalidInputRecording,
+
+    /// Returned when an archive could not be mounted into the virtual filesystem.
+    InvalidArchive(String),
 }
 
 impl Display for TetraError {
@@ -88,6 +104,9 @@ impl Display for TetraError {
             TetraError::FailedToLoadAsset { path, .. } => {
                 write!(f, "Failed to load asset from {}", path.to_string_lossy())
             }
+            TetraError::FailedToSaveFile { path, .. } => {
+                write!(f, "Failed to save file to {}", path.to_string_lossy())
+            }
             TetraError::InvalidColor => write!(f, "Invalid color"),
             TetraError::InvalidTexture(_) => write!(f, "Invalid texture data"),
             TetraError::InvalidShader(msg) => write!(f, "Invalid shader source: {}", msg),
@@ -109,6 +128,8 @@ impl Display for TetraError {
             TetraError::TessellationError(_) => {
                 write!(f, "An error occurred while tessellating a shape")
             }
+            TetraError::InvalidInputRecording => write!(f, "Invalid input recording data"),
+            TetraError::InvalidArchive(msg) => write!(f, "Invalid archive: {}", msg),
         }
     }
 }
@@ -118,6 +139,7 @@ impl Error for TetraError {
         match self {
             TetraError::PlatformError(_) => None,
             TetraError::FailedToLoadAsset { reason, .. } => Some(reason),
+            TetraError::FailedToSaveFile { reason, .. } => Some(reason),
             TetraError::InvalidColor => None,
             TetraError::InvalidTexture(reason) => Some(reason),
             TetraError::InvalidShader(_) => None,
@@ -129,6 +151,8 @@ impl Error for TetraError {
             TetraError::FailedToGetRefreshRate(_) => None,
             TetraError::FailedToChangeDisplayMode(_) => None,
             TetraError::TessellationError(reason) => Some(reason),
+            TetraError::InvalidInputRecording => None,
+            TetraError::InvalidArchive(_) => None,
         }
     }
 }
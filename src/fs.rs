@@ -1,34 +1,567 @@
-//! Internal utilities for reading files while retaining context about file paths.
+//! Functions and types relating to the virtual filesystem.
+//!
+//! By default, Tetra loads assets straight from the OS filesystem, with relative paths
+//! being resolved against the current working directory. If you want to load assets
+//! from elsewhere, you can mount directories (and, with the `archive_zip` feature enabled,
+//! ZIP archives) into the virtual filesystem via [`mount`].
+//!
+//! Once something has been mounted, all of Tetra's asset loading functions (e.g.
+//! [`Texture::new`](crate::graphics::Texture::new), [`Sound::new`](crate::audio::Sound::new),
+//! [`Shader::new`](crate::graphics::Shader::new) and [`Font::vector`](crate::graphics::text::Font::vector))
+//! will look for relative paths inside of the mounts first. If a file cannot be found in any
+//! of the mounts, it will be loaded from the OS filesystem as before. Absolute paths always
+//! bypass the mounts.
+//!
+//! # Embedded Assets
+//!
+//! If the `embedded_assets` feature is enabled, a whole directory of assets can be embedded
+//! into your game's executable via the [`include_bundle!`](crate::include_bundle) macro, and
+//! then mounted via [`mount_bundle`]. This makes it possible to distribute your game as a single
+//! file, without having to change any of your loading code.
+//!
+//! # Priority
+//!
+//! Mounts are searched in order of priority, highest first. If multiple mounts have the
+//! same priority, the most recently mounted one is searched first. This can be used to
+//! let mods override the base game's assets - for example, you could mount your game's
+//! assets with a priority of `0`, and each mod folder with a priority of `1`.
+//!
+//! # Functions without a `Context`
+//!
+//! Some loading functions (such as [`Sound::new`](crate::audio::Sound::new) and
+//! [`ImageData::new`](crate::graphics::ImageData::new)) do not take a [`Context`]. These
+//! will use the mounts of the most recently created `Context` on the current thread, or
+//! the OS filesystem if there isn't one.
 
 // To avoid warnings in the rare case where all features are disabled at the same time:
 #![allow(unused)]
 
+use std::cell::RefCell;
 use std::fs;
-use std::path::Path;
+use std::io::{self, ErrorKind};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
 
-use image::{self, DynamicImage, ImageError};
+#[cfg(feature = "archive_zip")]
+use std::{fs::File, io::BufReader, io::Read, sync::Mutex};
+
+use image::{self, DynamicImage, ImageError, ImageFormat};
+
+#[cfg(feature = "embedded_assets")]
+use include_dir::Dir;
+
+#[cfg(feature = "archive_zip")]
+use zip::{result::ZipError, ZipArchive};
 
 use crate::error::{Result, TetraError};
+use crate::Context;
+
+thread_local! {
+    static ACTIVE_VFS: RefCell<Option<Arc<Vfs>>> = const { RefCell::new(None) };
+}
+
+/// The virtual filesystem that belongs to a `Context`.
+pub(crate) struct Vfs {
+    mounts: RwLock<Vec<Mount>>,
+}
+
+struct Mount {
+    path: PathBuf,
+    priority: i32,
+    source: MountSource,
+}
+
+enum MountSource {
+    Directory,
+
+    #[cfg(feature = "archive_zip")]
+    Archive(Mutex<ZipArchive<BufReader<File>>>),
+
+    #[cfg(feature = "embedded_assets")]
+    Bundle(Bundle),
+}
+
+/// A directory of assets that has been embedded into the executable.
+///
+/// Bundles are created via the [`include_bundle!`](crate::include_bundle) macro, and can
+/// be mounted into the virtual filesystem via [`mount_bundle`].
+///
+/// In debug builds, files will be loaded from the original directory on disk if they
+/// are present there, so that changes to your assets can be seen without recompiling.
+/// The embedded copies will only be used if the file cannot be found on disk.
+/// In release builds, only the embedded copies will be used.
+#[cfg(feature = "embedded_assets")]
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    dir: Dir<'static>,
+    source_dir: PathBuf,
+    load_from_disk: bool,
+}
+
+#[cfg(feature = "embedded_assets")]
+impl Bundle {
+    #[doc(hidden)]
+    pub fn __new(
+        dir: Dir<'static>,
+        path: &str,
+        manifest_dir: &str,
+        load_from_disk: bool,
+    ) -> Bundle {
+        Bundle {
+            dir,
+            source_dir: PathBuf::from(path.replace("$CARGO_MANIFEST_DIR", manifest_dir)),
+            load_from_disk,
+        }
+    }
+
+    /// Returns the contents of an embedded file, if it exists in the bundle.
+    ///
+    /// Unlike loading via the virtual filesystem, this will never read from disk, even
+    /// in debug builds. This can be useful in combination with functions that load from
+    /// raw data, such as [`Texture::from_encoded`](crate::graphics::Texture::from_encoded).
+    pub fn get<P>(&self, path: P) -> Option<&'static [u8]>
+    where
+        P: AsRef<Path>,
+    {
+        let name = archive_name(&normalize(path.as_ref())?).ok()?;
+
+        self.dir.get_file(name).map(|file| file.contents())
+    }
+
+    /// Returns the directory that the bundle was embedded from.
+    pub fn source_dir(&self) -> &Path {
+        &self.source_dir
+    }
+
+    fn read(&self, relative: &Path) -> io::Result<Vec<u8>> {
+        if self.load_from_disk {
+            match fs::read(self.source_dir.join(relative)) {
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                result => return result,
+            }
+        }
+
+        self.get(relative)
+            .map(|data| data.to_vec())
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "file not found in bundle"))
+    }
+
+    fn exists(&self, relative: &Path) -> bool {
+        (self.load_from_disk && self.source_dir.join(relative).is_file())
+            || self.get(relative).is_some()
+    }
+
+    fn disk_path(&self, relative: &Path) -> Option<PathBuf> {
+        let path = self.source_dir.join(relative);
+
+        (self.load_from_disk && path.is_file()).then_some(path)
+    }
+}
+
+/// Embeds a directory of assets into the executable, returning a [`Bundle`](crate::fs::Bundle).
+///
+/// The path may contain environment variables, prefixed with a `$` - you will usually want to use
+/// `$CARGO_MANIFEST_DIR`, which expands to the root of your crate.
+///
+/// This macro requires the `embedded_assets` feature to be enabled.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use tetra::{Context, fs, graphics::Texture};
+/// # fn load(ctx: &mut Context) -> tetra::Result {
+/// let bundle = tetra::include_bundle!("$CARGO_MANIFEST_DIR/examples/resources");
+/// fs::mount_bundle(ctx, bundle, 0);
+///
+/// // This will load `examples/resources/player.png` from the bundle:
+/// let texture = Texture::new(ctx, "player.png")?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "embedded_assets")]
+#[macro_export]
+macro_rules! include_bundle {
+    ($path:tt) => {
+        $crate::fs::Bundle::__new(
+            {
+                use $crate::fs::__private::include_dir;
+                include_dir::include_dir!($path)
+            },
+            $path,
+            env!("CARGO_MANIFEST_DIR"),
+            cfg!(debug_assertions),
+        )
+    };
+}
+
+#[cfg(feature = "embedded_assets")]
+#[doc(hidden)]
+pub mod __private {
+    pub use include_dir;
+}
+
+impl Vfs {
+    pub(crate) fn new() -> Vfs {
+        Vfs {
+            mounts: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Makes this the filesystem that is used by loading functions which do not take
+    /// a `Context`.
+    pub(crate) fn activate(self: &Arc<Vfs>) {
+        ACTIVE_VFS.with(|active| *active.borrow_mut() = Some(Arc::clone(self)));
+    }
+
+    /// Stops this filesystem being used by loading functions which do not take a `Context`,
+    /// if it is currently active.
+    pub(crate) fn deactivate(self: &Arc<Vfs>) {
+        ACTIVE_VFS.with(|active| {
+            let mut active = active.borrow_mut();
+
+            if matches!(&*active, Some(vfs) if Arc::ptr_eq(vfs, self)) {
+                *active = None;
+            }
+        });
+    }
+
+    /// Returns the filesystem that is currently active on this thread, if there is one.
+    pub(crate) fn active() -> Option<Arc<Vfs>> {
+        ACTIVE_VFS.with(|active| active.borrow().clone())
+    }
+
+    fn mount(&self, path: &Path, priority: i32) -> Result {
+        let metadata = fs::metadata(path).map_err(|e| TetraError::FailedToLoadAsset {
+            reason: e,
+            path: path.to_owned(),
+        })?;
+
+        let source = if metadata.is_dir() {
+            MountSource::Directory
+        } else {
+            open_archive(path)?
+        };
+
+        self.insert(Mount {
+            path: path.to_owned(),
+            priority,
+            source,
+        });
+
+        Ok(())
+    }
+
+    fn insert(&self, mount: Mount) {
+        let mut mounts = self.mounts.write().unwrap();
+
+        // Mounts are kept sorted so that they can be searched from front to back.
+        let index = mounts
+            .iter()
+            .position(|m| m.priority <= mount.priority)
+            .unwrap_or(mounts.len());
+
+        mounts.insert(index, mount);
+    }
+
+    fn unmount(&self, path: &Path) -> bool {
+        let mut mounts = self.mounts.write().unwrap();
+        let count = mounts.len();
+
+        mounts.retain(|m| m.path != path);
+
+        mounts.len() != count
+    }
+
+    pub(crate) fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        if let Some(relative) = normalize(path) {
+            for mount in self.mounts.read().unwrap().iter() {
+                match mount.read(&relative) {
+                    Err(e) if e.kind() == ErrorKind::NotFound => {}
+                    result => return result,
+                }
+            }
+        }
+
+        fs::read(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        if let Some(relative) = normalize(path) {
+            for mount in self.mounts.read().unwrap().iter() {
+                if mount.exists(&relative) {
+                    return true;
+                }
+            }
+        }
+
+        path.is_file()
+    }
+
+    /// Returns the location on disk that a file would be read from, or `None` if it
+    /// would be read from an archive or a bundle's embedded data.
+    pub(crate) fn disk_path(&self, path: &Path) -> Option<PathBuf> {
+        if let Some(relative) = normalize(path) {
+            for mount in self.mounts.read().unwrap().iter() {
+                if mount.exists(&relative) {
+                    return mount.disk_path(&relative);
+                }
+            }
+        }
+
+        path.is_file().then(|| path.to_owned())
+    }
+}
+
+impl Mount {
+    fn read(&self, relative: &Path) -> io::Result<Vec<u8>> {
+        match &self.source {
+            MountSource::Directory => fs::read(self.path.join(relative)),
+
+            #[cfg(feature = "archive_zip")]
+            MountSource::Archive(archive) => {
+                let name = archive_name(relative)?;
+                let mut archive = archive.lock().unwrap();
+
+                let mut file = archive.by_name(&name).map_err(|e| match e {
+                    ZipError::Io(e) => e,
+                    ZipError::FileNotFound => io::Error::new(ErrorKind::NotFound, e),
+                    _ => io::Error::new(ErrorKind::InvalidData, e),
+                })?;
+
+                let mut data = Vec::with_capacity(file.size() as usize);
+                file.read_to_end(&mut data)?;
+
+                Ok(data)
+            }
+
+            #[cfg(feature = "embedded_assets")]
+            MountSource::Bundle(bundle) => bundle.read(relative),
+        }
+    }
+
+    fn exists(&self, relative: &Path) -> bool {
+        match &self.source {
+            MountSource::Directory => self.path.join(relative).is_file(),
+
+            #[cfg(feature = "archive_zip")]
+            MountSource::Archive(archive) => match archive_name(relative) {
+                Ok(name) => archive.lock().unwrap().by_name(&name).is_ok(),
+                Err(_) => false,
+            },
+
+            #[cfg(feature = "embedded_assets")]
+            MountSource::Bundle(bundle) => bundle.exists(relative),
+        }
+    }
+
+    fn disk_path(&self, relative: &Path) -> Option<PathBuf> {
+        match &self.source {
+            MountSource::Directory => Some(self.path.join(relative)),
+
+            #[cfg(feature = "archive_zip")]
+            MountSource::Archive(_) => None,
+
+            #[cfg(feature = "embedded_assets")]
+            MountSource::Bundle(bundle) => bundle.disk_path(relative),
+        }
+    }
+}
+
+#[cfg(feature = "archive_zip")]
+fn open_archive(path: &Path) -> Result<MountSource> {
+    let file = File::open(path).map_err(|e| TetraError::FailedToLoadAsset {
+        reason: e,
+        path: path.to_owned(),
+    })?;
+
+    let archive = ZipArchive::new(BufReader::new(file)).map_err(|e| match e {
+        ZipError::Io(e) => TetraError::FailedToLoadAsset {
+            reason: e,
+            path: path.to_owned(),
+        },
+        _ => TetraError::InvalidArchive(e.to_string()),
+    })?;
+
+    Ok(MountSource::Archive(Mutex::new(archive)))
+}
+
+#[cfg(not(feature = "archive_zip"))]
+fn open_archive(_path: &Path) -> Result<MountSource> {
+    Err(TetraError::InvalidArchive(String::from(
+        "mounting archives requires the `archive_zip` feature",
+    )))
+}
+
+/// Converts a relative path into the form used for archive entries.
+fn archive_name(relative: &Path) -> io::Result<String> {
+    let mut name = String::new();
+
+    for component in relative.components() {
+        let component = component
+            .as_os_str()
+            .to_str()
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "path is not valid UTF-8"))?;
+
+        if !name.is_empty() {
+            name.push('/');
+        }
+
+        name.push_str(component);
+    }
+
+    Ok(name)
+}
+
+/// Removes any `.` and `..` components from a relative path, so that it can be looked
+/// up inside of a mount.
+///
+/// Returns `None` if the path is absolute, or if it would escape the root of the mount.
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+
+    Some(normalized)
+}
+
+/// Mounts a directory or archive into the virtual filesystem, with the given priority.
+///
+/// Mounts with a higher priority will be searched first. If multiple mounts have the
+/// same priority, the most recently mounted one will be searched first.
+///
+/// # Errors
+///
+/// * [`TetraError::FailedToLoadAsset`](crate::TetraError::FailedToLoadAsset) will be returned
+/// if the path could not be opened.
+/// * [`TetraError::InvalidArchive`](crate::TetraError::InvalidArchive) will be returned if
+/// the path was a file, but it was not a valid archive (or the `archive_zip` feature is not
+/// enabled).
+pub fn mount<P>(ctx: &mut Context, path: P, priority: i32) -> Result
+where
+    P: AsRef<Path>,
+{
+    ctx.fs.mount(path.as_ref(), priority)
+}
+
+/// Mounts a bundle of embedded assets into the virtual filesystem, with the given priority.
+///
+/// Mounts with a higher priority will be searched first. If multiple mounts have the
+/// same priority, the most recently mounted one will be searched first.
+///
+/// The bundle can be unmounted by passing its [`source_dir`](Bundle::source_dir) to [`unmount`].
+#[cfg(feature = "embedded_assets")]
+pub fn mount_bundle(ctx: &mut Context, bundle: Bundle, priority: i32) {
+    ctx.fs.insert(Mount {
+        path: bundle.source_dir.clone(),
+        priority,
+        source: MountSource::Bundle(bundle),
+    });
+}
+
+/// Removes a directory, archive or bundle from the virtual filesystem.
+///
+/// Returns `false` if the path was not mounted.
+pub fn unmount<P>(ctx: &mut Context, path: P) -> bool
+where
+    P: AsRef<Path>,
+{
+    ctx.fs.unmount(path.as_ref())
+}
+
+/// Returns true if a file exists at the given path, either in one of the mounts
+/// or on the OS filesystem.
+pub fn exists<P>(ctx: &Context, path: P) -> bool
+where
+    P: AsRef<Path>,
+{
+    ctx.fs.exists(path.as_ref())
+}
+
+/// Reads the contents of a file via the virtual filesystem.
+///
+/// # Errors
+///
+/// * [`TetraError::FailedToLoadAsset`](crate::TetraError::FailedToLoadAsset) will be returned
+/// if the file could not be loaded.
+pub fn read<P>(ctx: &Context, path: P) -> Result<Vec<u8>>
+where
+    P: AsRef<Path>,
+{
+    let path_ref = path.as_ref();
 
-pub(crate) fn read<P>(path: P) -> Result<Vec<u8>>
+    ctx.fs
+        .read(path_ref)
+        .map_err(|e| TetraError::FailedToLoadAsset {
+            reason: e,
+            path: path_ref.to_owned(),
+        })
+}
+
+/// Reads the contents of a file via the virtual filesystem, as a string.
+///
+/// # Errors
+///
+/// * [`TetraError::FailedToLoadAsset`](crate::TetraError::FailedToLoadAsset) will be returned
+/// if the file could not be loaded, or if it was not valid UTF-8.
+pub fn read_to_string<P>(ctx: &Context, path: P) -> Result<String>
 where
     P: AsRef<Path>,
 {
     let path_ref = path.as_ref();
 
-    fs::read(path_ref).map_err(|e| TetraError::FailedToLoadAsset {
+    into_string(read(ctx, path_ref)?, path_ref)
+}
+
+fn into_string(data: Vec<u8>, path: &Path) -> Result<String> {
+    String::from_utf8(data).map_err(|e| TetraError::FailedToLoadAsset {
+        reason: io::Error::new(ErrorKind::InvalidData, e),
+        path: path.to_owned(),
+    })
+}
+
+/// Reads an asset via the active virtual filesystem (or the OS filesystem, if there
+/// isn't one).
+pub(crate) fn read_asset<P>(path: P) -> Result<Vec<u8>>
+where
+    P: AsRef<Path>,
+{
+    let path_ref = path.as_ref();
+
+    let result = match Vfs::active() {
+        Some(vfs) => vfs.read(path_ref),
+        None => fs::read(path_ref),
+    };
+
+    result.map_err(|e| TetraError::FailedToLoadAsset {
         reason: e,
         path: path_ref.to_owned(),
     })
 }
 
-pub(crate) fn read_to_image<P>(path: P) -> Result<DynamicImage>
+pub(crate) fn read_asset_to_image<P>(path: P) -> Result<DynamicImage>
 where
     P: AsRef<Path>,
 {
     let path_ref = path.as_ref();
+    let data = read_asset(path_ref)?;
 
-    image::open(path_ref).map_err(|e| match e {
+    let image = match ImageFormat::from_path(path_ref) {
+        Ok(format) => image::load_from_memory_with_format(&data, format),
+        Err(_) => image::load_from_memory(&data),
+    };
+
+    image.map_err(|e| match e {
         ImageError::IoError(inner) => TetraError::FailedToLoadAsset {
             reason: inner,
             path: path_ref.to_owned(),
@@ -37,14 +570,191 @@ where
     })
 }
 
-pub(crate) fn read_to_string<P>(path: P) -> Result<String>
+pub(crate) fn read_asset_to_string<P>(path: P) -> Result<String>
 where
     P: AsRef<Path>,
 {
     let path_ref = path.as_ref();
 
-    fs::read_to_string(path_ref).map_err(|e| TetraError::FailedToLoadAsset {
+    into_string(read_asset(path_ref)?, path_ref)
+}
+
+pub(crate) fn write<P, C>(path: P, contents: C) -> Result
+where
+    P: AsRef<Path>,
+    C: AsRef<[u8]>,
+{
+    let path_ref = path.as_ref();
+
+    fs::write(path_ref, contents).map_err(|e| TetraError::FailedToSaveFile {
         reason: e,
         path: path_ref.to_owned(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use super::*;
+    use crate::ContextBuilder;
+
+    fn headless_context() -> Context {
+        ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap()
+    }
+
+    /// Creates a fresh directory to run a test in.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tetra-fs-{}-{}", name, process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn mounts_are_searched_by_priority() {
+        let dir = test_dir("priority");
+
+        for (mount, contents) in &[("base", "base"), ("mod_a", "mod a"), ("mod_b", "mod b")] {
+            fs::create_dir_all(dir.join(mount).join("data")).unwrap();
+            fs::write(dir.join(mount).join("data/file.txt"), contents).unwrap();
+        }
+
+        fs::write(dir.join("base/data/base_only.txt"), "base only").unwrap();
+
+        let mut ctx = headless_context();
+
+        assert!(!exists(&ctx, "data/file.txt"));
+
+        mount(&mut ctx, dir.join("mod_a"), 1).unwrap();
+        mount(&mut ctx, dir.join("base"), 0).unwrap();
+        mount(&mut ctx, dir.join("mod_b"), 1).unwrap();
+
+        assert!(exists(&ctx, "data/file.txt"));
+        assert_eq!(read_to_string(&ctx, "data/file.txt").unwrap(), "mod b");
+        assert_eq!(
+            read_to_string(&ctx, "./data/../data/file.txt").unwrap(),
+            "mod b"
+        );
+        assert_eq!(
+            read_to_string(&ctx, "data/base_only.txt").unwrap(),
+            "base only"
+        );
+
+        assert!(unmount(&mut ctx, dir.join("mod_b")));
+        assert!(!unmount(&mut ctx, dir.join("mod_b")));
+        assert_eq!(read_to_string(&ctx, "data/file.txt").unwrap(), "mod a");
+
+        // Loading functions that don't take a context should use the same mounts:
+        assert_eq!(read_asset("data/file.txt").unwrap(), b"mod a");
+
+        // Paths that aren't in any of the mounts should be loaded from the OS filesystem:
+        let absolute = dir.join("base/data/file.txt");
+        assert_eq!(read_to_string(&ctx, &absolute).unwrap(), "base");
+
+        assert!(matches!(
+            read(&ctx, "data/missing.txt"),
+            Err(TetraError::FailedToLoadAsset { .. })
+        ));
+
+        drop(ctx);
+        assert!(read_asset("data/file.txt").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn paths_cannot_escape_mounts() {
+        assert_eq!(
+            normalize(Path::new("./a/b/../c")),
+            Some(PathBuf::from("a/c"))
+        );
+        assert_eq!(normalize(Path::new("a/../../b")), None);
+        assert_eq!(normalize(&std::env::temp_dir()), None);
+    }
+
+    #[cfg(feature = "archive_zip")]
+    #[test]
+    fn archives_can_be_mounted() {
+        use std::io::Write;
+
+        use zip::write::FileOptions;
+        use zip::{CompressionMethod, ZipWriter};
+
+        let dir = test_dir("archive");
+        let archive_path = dir.join("assets.zip");
+
+        let mut writer = ZipWriter::new(File::create(&archive_path).unwrap());
+
+        writer
+            .start_file(
+                "textures/readme.txt",
+                FileOptions::default().compression_method(CompressionMethod::Deflated),
+            )
+            .unwrap();
+
+        writer.write_all(b"hello from the archive").unwrap();
+        writer.finish().unwrap();
+
+        let mut ctx = headless_context();
+        mount(&mut ctx, &archive_path, 0).unwrap();
+
+        assert!(exists(&ctx, "textures/readme.txt"));
+        assert!(!exists(&ctx, "textures/missing.txt"));
+        assert_eq!(
+            read_to_string(&ctx, "textures/readme.txt").unwrap(),
+            "hello from the archive"
+        );
+
+        fs::write(dir.join("not_a_zip.zip"), b"nope").unwrap();
+
+        assert!(matches!(
+            mount(&mut ctx, dir.join("not_a_zip.zip"), 0),
+            Err(TetraError::InvalidArchive(_))
+        ));
+
+        drop(ctx);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "embedded_assets")]
+    #[test]
+    fn bundles_can_be_mounted() {
+        let mut ctx = headless_context();
+
+        let bundle = crate::include_bundle!("$CARGO_MANIFEST_DIR/src/resources");
+        assert!(bundle.get("missing.vert").is_none());
+        assert_eq!(
+            bundle.get("./shader.vert"),
+            Some(include_bytes!("resources/shader.vert").as_ref())
+        );
+
+        mount_bundle(&mut ctx, bundle.clone(), 0);
+
+        assert!(exists(&ctx, "shader.frag"));
+        assert_eq!(
+            read_to_string(&ctx, "shader.frag").unwrap(),
+            include_str!("resources/shader.frag")
+        );
+
+        assert!(unmount(&mut ctx, bundle.source_dir()));
+        assert!(!exists(&ctx, "shader.frag"));
+
+        // Bundles should still work if the original directory no longer exists:
+        let dir = test_dir("bundle");
+        let bundle = Bundle::__new(bundle.dir, dir.to_str().unwrap(), "", true);
+
+        mount_bundle(&mut ctx, bundle, 0);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            read_to_string(&ctx, "shader.vert").unwrap(),
+            include_str!("resources/shader.vert")
+        );
+    }
+}
@@ -8,8 +8,10 @@
 pub mod animation;
 mod camera;
 mod canvas;
+mod capture;
 mod color;
 mod drawparams;
+pub(crate) mod hot_reload;
 mod image_data;
 pub mod mesh;
 mod rectangle;
@@ -20,6 +22,7 @@ mod texture;
 
 pub use camera::*;
 pub use canvas::*;
+pub use capture::*;
 pub use color::*;
 pub use drawparams::*;
 pub use image_data::*;
@@ -33,6 +36,9 @@ use crate::platform::{GraphicsDevice, RawIndexBuffer, RawVertexBuffer};
 use crate::window;
 use crate::Context;
 
+#[cfg(feature = "texture_png")]
+use self::capture::FrameCapture;
+use self::hot_reload::HotReloader;
 use self::mesh::{BufferUsage, Vertex, VertexWinding};
 
 const MAX_SPRITES: usize = 2048;
@@ -60,6 +66,11 @@ pub(crate) struct GraphicsContext {
     element_count: usize,
 
     blend_state: BlendState,
+
+    pub(crate) hot_reload: Option<HotReloader>,
+
+    #[cfg(feature = "texture_png")]
+    frame_capture: Option<FrameCapture>,
 }
 
 impl GraphicsContext {
@@ -67,6 +78,7 @@ impl GraphicsContext {
         device: &mut GraphicsDevice,
         window_width: i32,
         window_height: i32,
+        hot_reload: bool,
     ) -> Result<GraphicsContext> {
         let vertex_buffer = device.new_vertex_buffer(MAX_VERTICES, BufferUsage::Dynamic)?;
         let index_buffer = device.new_index_buffer(MAX_INDICES, BufferUsage::Static)?;
@@ -118,6 +130,11 @@ impl GraphicsContext {
             element_count: 0,
 
             blend_state: BlendState::default(),
+
+            hot_reload: hot_reload.then(HotReloader::new),
+
+            #[cfg(feature = "texture_png")]
+            frame_capture: None,
         })
     }
 }
@@ -369,6 +386,9 @@ pub fn flush(ctx: &mut Context) {
 pub fn present(ctx: &mut Context) {
     flush(ctx);
 
+    #[cfg(feature = "texture_png")]
+    capture::capture_frame(ctx);
+
     ctx.window.swap_buffers();
 }
 
@@ -406,6 +426,42 @@ pub fn get_device_info(ctx: &Context) -> GraphicsDeviceInfo {
     ctx.device.get_info()
 }
 
+/// A draw call that was recorded by a headless context.
+///
+/// Rather than sending draw calls to the GPU, headless contexts record them, so that
+/// the output of rendering code can be inspected in automated tests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawCall {
+    /// The vertices that were drawn, in the order that they were submitted.
+    ///
+    /// If an index buffer was used, it will already have been applied - every three
+    /// vertices make up one triangle.
+    pub vertices: Vec<Vertex>,
+
+    /// The number of instances that were drawn.
+    pub instances: usize,
+
+    /// The size of the texture that was bound when the draw call was made.
+    pub texture_size: (i32, i32),
+
+    /// Whether the draw call was made to a canvas, rather than to the backbuffer.
+    pub to_canvas: bool,
+}
+
+/// Returns the draw calls that have been recorded since the last time this
+/// function was called, emptying the list.
+///
+/// Draw calls are only recorded when the context is running in
+/// [`headless`](crate::ContextBuilder::headless) mode - otherwise, this function
+/// will always return an empty list.
+///
+/// Note that sprites are batched and only submitted when the batch is flushed
+/// (e.g. when the texture changes, or when [`present`] is called). If you want
+/// to make sure all pending draws have been recorded, call [`flush`] first.
+pub fn take_draw_calls(ctx: &mut Context) -> Vec<DrawCall> {
+    ctx.device.take_draw_calls()
+}
+
 /// Returns the current transform matrix.
 pub fn get_transform_matrix(ctx: &Context) -> Mat4<f32> {
     ctx.graphics.transform_matrix
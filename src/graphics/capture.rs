@@ -0,0 +1,235 @@
+//! Functions and types relating to capturing the contents of the screen.
+
+#[cfg(feature = "texture_png")]
+use std::{
+    fs,
+    path::PathBuf,
+    sync::mpsc::{self, SyncSender},
+    thread::{self, JoinHandle},
+};
+
+#[cfg(feature = "texture_png")]
+use crate::error::{Result, TetraError};
+use crate::graphics::{self, ImageData, TextureFormat};
+use crate::window;
+use crate::Context;
+
+/// The maximum number of captured frames that can be waiting to be written. Each
+/// frame is stored uncompressed (about 8 MB at 1080p), so this bounds the memory
+/// that a capture can use if encoding falls behind.
+#[cfg(feature = "texture_png")]
+const MAX_QUEUED_FRAMES: usize = 4;
+
+/// An in-progress frame capture.
+#[cfg(feature = "texture_png")]
+pub(crate) struct FrameCapture {
+    interval: usize,
+    frame: usize,
+    saved: usize,
+    dir: PathBuf,
+
+    // Encoding a PNG can take longer than a frame, so the files are
+    // written on a separate thread.
+    frames: SyncSender<(PathBuf, ImageData)>,
+    writer: JoinHandle<Result>,
+}
+
+/// Captures the current contents of the screen.
+///
+/// This will [`flush`](graphics::flush) any pending draw calls first, but you should
+/// still usually call it at the end of your [`draw`](crate::State::draw) method - the
+/// contents of the screen are undefined until the current frame has been drawn. Any
+/// active canvas is ignored.
+///
+/// The returned image will be in [`TextureFormat::Rgba8`], and its size will match the
+/// window's [physical size](window::get_physical_size).
+///
+/// In [headless](crate::ContextBuilder::headless) mode, nothing is rendered, so the
+/// returned image will always be blank.
+pub fn screenshot(ctx: &mut Context) -> ImageData {
+    graphics::flush(ctx);
+
+    let (width, height) = window::get_physical_size(ctx);
+    let data = ctx.device.get_screen_data(width, height);
+
+    ImageData::from_data(width, height, TextureFormat::Rgba8, data)
+        .expect("screen data should fill the image")
+}
+
+/// Starts saving every `interval`th presented frame to the given directory, as a
+/// sequence of PNG files (`frame_00000.png`, `frame_00001.png`, and so on). An
+/// interval of `1` will save every frame.
+///
+/// This is useful for recording trailers or GIFs of your game. The files are encoded
+/// and written on a background thread, but capturing each frame still requires reading
+/// it back from the GPU, which will slow your game down.
+///
+/// Only a few frames can be waiting to be written at once. If the background thread
+/// falls behind, presenting a frame that is due to be captured will block until there is
+/// room, rather than dropping it - this keeps the memory usage bounded, and ensures that
+/// no frames are missing from the sequence.
+///
+/// If a capture is already in progress, it will be stopped first.
+///
+/// This function requires the `texture_png` feature to be enabled.
+///
+/// # Errors
+///
+/// * [`TetraError::FailedToSaveFile`] will be returned if the directory could not
+/// be created.
+/// * [`TetraError::PlatformError`] will be returned if the background thread could
+/// not be spawned.
+#[cfg(feature = "texture_png")]
+pub fn start_frame_capture<P>(ctx: &mut Context, dir: P, interval: usize) -> Result
+where
+    P: Into<PathBuf>,
+{
+    let dir = dir.into();
+
+    // Any errors from the previous capture are discarded, as there's
+    // nowhere to return them to.
+    let _ = stop_frame_capture(ctx);
+
+    fs::create_dir_all(&dir).map_err(|e| TetraError::FailedToSaveFile {
+        reason: e,
+        path: dir.clone(),
+    })?;
+
+    let (sender, receiver) = mpsc::sync_channel::<(PathBuf, ImageData)>(MAX_QUEUED_FRAMES);
+
+    let writer = thread::Builder::new()
+        .name("tetra-frame-capture".into())
+        .spawn(move || {
+            for (path, image) in receiver {
+                image.save(path)?;
+            }
+
+            Ok(())
+        })
+        .map_err(|e| TetraError::PlatformError(e.to_string()))?;
+
+    ctx.graphics.frame_capture = Some(FrameCapture {
+        interval: interval.max(1),
+        frame: 0,
+        saved: 0,
+        dir,
+
+        frames: sender,
+        writer,
+    });
+
+    Ok(())
+}
+
+/// Stops the current frame capture, waiting for any remaining frames to be written
+/// to disk.
+///
+/// Returns the number of frames that were captured, or `0` if no capture was
+/// in progress.
+///
+/// # Errors
+///
+/// * [`TetraError::FailedToSaveFile`] will be returned if any of the frames could
+/// not be written. No further frames will have been saved after the failed one.
+/// * [`TetraError::PlatformError`] will be returned if the background thread panicked.
+#[cfg(feature = "texture_png")]
+pub fn stop_frame_capture(ctx: &mut Context) -> Result<usize> {
+    match ctx.graphics.frame_capture.take() {
+        Some(capture) => {
+            drop(capture.frames);
+
+            capture
+                .writer
+                .join()
+                .map_err(|_| TetraError::PlatformError("frame capture thread panicked".into()))??;
+
+            Ok(capture.saved)
+        }
+
+        None => Ok(0),
+    }
+}
+
+/// Returns `true` if a frame capture is in progress.
+#[cfg(feature = "texture_png")]
+pub fn is_capturing_frames(ctx: &Context) -> bool {
+    ctx.graphics.frame_capture.is_some()
+}
+
+/// Captures the current frame, if a frame capture is in progress and the frame
+/// is due to be saved.
+#[cfg(feature = "texture_png")]
+pub(crate) fn capture_frame(ctx: &mut Context) {
+    let is_due = match &mut ctx.graphics.frame_capture {
+        Some(capture) => {
+            let is_due = capture.frame % capture.interval == 0;
+            capture.frame += 1;
+            is_due
+        }
+
+        None => false,
+    };
+
+    if is_due {
+        let image = screenshot(ctx);
+
+        if let Some(capture) = &mut ctx.graphics.frame_capture {
+            let path = capture.dir.join(format!("frame_{:05}.png", capture.saved));
+
+            // If this fails, the writer has stopped due to an error, which will be
+            // reported by `stop_frame_capture`.
+            if capture.frames.send((path, image)).is_ok() {
+                capture.saved += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ContextBuilder;
+
+    #[test]
+    fn screenshot_matches_window_size() {
+        let mut ctx = ContextBuilder::new("Test", 64, 32)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        let screenshot = screenshot(&mut ctx);
+
+        assert_eq!(screenshot.size(), (64, 32));
+        assert_eq!(screenshot.format(), TextureFormat::Rgba8);
+    }
+
+    #[test]
+    #[cfg(feature = "texture_png")]
+    fn frame_capture_saves_every_nth_frame() {
+        struct Empty;
+
+        impl crate::State for Empty {}
+
+        let dir = std::env::temp_dir().join(format!("tetra-capture-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut ctx = ContextBuilder::new("Test", 16, 16)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        start_frame_capture(&mut ctx, &dir, 2).unwrap();
+        assert!(is_capturing_frames(&ctx));
+
+        for _ in 0..5 {
+            ctx.tick(&mut Empty, std::time::Duration::ZERO).unwrap();
+        }
+
+        assert_eq!(stop_frame_capture(&mut ctx).unwrap(), 3);
+        assert!(!is_capturing_frames(&ctx));
+
+        let saved = ImageData::new(dir.join("frame_00002.png")).unwrap();
+        assert_eq!(saved.size(), (16, 16));
+        assert!(!dir.join("frame_00003.png").exists());
+    }
+}
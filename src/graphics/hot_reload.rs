@@ -0,0 +1,342 @@
+//! Reloading of assets when the files they were loaded from change on disk.
+
+use std::any::Any;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+use std::result;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use crate::error::{Result, TetraError};
+use crate::fs::Vfs;
+use crate::{Context, Event, State};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+type ReloadFn = Rc<dyn Fn(&mut Context) -> Result>;
+
+/// Keeps track of the files that assets were loaded from.
+pub(crate) struct HotReloader {
+    watches: Vec<Watch>,
+    time_since_poll: Duration,
+}
+
+struct Watch {
+    asset: Weak<dyn Any>,
+    files: Vec<WatchedFile>,
+    reload: ReloadFn,
+}
+
+struct WatchedFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl HotReloader {
+    pub(crate) fn new() -> HotReloader {
+        HotReloader {
+            watches: Vec::new(),
+            time_since_poll: Duration::ZERO,
+        }
+    }
+}
+
+/// Starts watching the files that an asset was loaded from, if hot reloading is enabled.
+///
+/// When any of the files change, `reload` will be called with the asset's shared data.
+/// The watch is discarded once every handle to the asset has been dropped.
+pub(crate) fn watch<T, F>(ctx: &mut Context, asset: &Rc<T>, paths: Vec<PathBuf>, reload: F)
+where
+    T: 'static,
+    F: Fn(&mut Context, &T) -> Result + 'static,
+{
+    let reloader = match &mut ctx.graphics.hot_reload {
+        Some(reloader) => reloader,
+        None => return,
+    };
+
+    let files = paths
+        .into_iter()
+        .map(|path| WatchedFile {
+            modified: modified(&ctx.fs, &path),
+            path,
+        })
+        .collect();
+
+    let weak = Rc::downgrade(asset);
+
+    reloader.watches.push(Watch {
+        asset: weak.clone(),
+        files,
+        reload: Rc::new(move |ctx| match weak.upgrade() {
+            Some(asset) => reload(ctx, &asset),
+            None => Ok(()),
+        }),
+    });
+}
+
+/// Reloads any assets whose files have changed since the last check, firing an
+/// event for each one.
+pub(crate) fn poll<S, E>(
+    ctx: &mut Context,
+    state: &mut S,
+    elapsed: Duration,
+) -> result::Result<(), E>
+where
+    S: State<E>,
+    E: From<TetraError>,
+{
+    let mut changed = Vec::new();
+
+    if let Some(reloader) = &mut ctx.graphics.hot_reload {
+        reloader.time_since_poll += elapsed;
+
+        if reloader.time_since_poll < POLL_INTERVAL {
+            return Ok(());
+        }
+
+        reloader.time_since_poll = Duration::ZERO;
+        reloader
+            .watches
+            .retain(|watch| watch.asset.strong_count() > 0);
+
+        for watch in &mut reloader.watches {
+            let mut changed_path = None;
+
+            for file in &mut watch.files {
+                let modified = modified(&ctx.fs, &file.path);
+
+                // Some editors delete the file before writing the new version, so
+                // we wait for it to reappear rather than treating that as a change.
+                if modified.is_some() && modified != file.modified {
+                    file.modified = modified;
+                    changed_path.get_or_insert_with(|| file.path.clone());
+                }
+            }
+
+            if let Some(path) = changed_path {
+                changed.push((path, Rc::clone(&watch.reload)));
+            }
+        }
+    }
+
+    for (path, reload) in changed {
+        let event = match reload(ctx) {
+            Ok(()) => Event::AssetReloaded { path },
+            Err(e) => Event::AssetReloadFailed {
+                path,
+                error: Arc::new(e),
+            },
+        };
+
+        state.event(ctx, event)?;
+    }
+
+    Ok(())
+}
+
+fn modified(vfs: &Vfs, path: &Path) -> Option<SystemTime> {
+    vfs.disk_path(path)?.metadata().ok()?.modified().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::process;
+
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+    use crate::graphics::shader::UniformData;
+    use crate::graphics::{Shader, Texture};
+    use crate::math::Vec2;
+    use crate::ContextBuilder;
+
+    #[derive(Default)]
+    struct EventLog {
+        events: Vec<Event>,
+    }
+
+    impl State for EventLog {
+        fn event(&mut self, _: &mut Context, event: Event) -> Result {
+            self.events.push(event);
+            Ok(())
+        }
+    }
+
+    fn hot_reload_context() -> Context {
+        ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .hot_reload(true)
+            .build()
+            .unwrap()
+    }
+
+    /// Creates a fresh directory to run a test in.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tetra-reload-{}-{}", name, process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    /// Writes a file, making sure that its modification time changes even on
+    /// filesystems with coarse timestamps.
+    fn write_changed(path: &Path, write: impl FnOnce(&Path)) {
+        let previous = fs::metadata(path)
+            .and_then(|m| m.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        write(path);
+
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(previous + Duration::from_secs(1))
+            .unwrap();
+    }
+
+    fn tick(ctx: &mut Context, state: &mut EventLog) {
+        ctx.tick(state, POLL_INTERVAL).unwrap();
+    }
+
+    #[test]
+    fn textures_are_reloaded_in_place() {
+        let dir = test_dir("texture");
+        let path = dir.join("texture.png");
+
+        RgbaImage::from_pixel(1, 1, Rgba([255, 0, 0, 255]))
+            .save(&path)
+            .unwrap();
+
+        let mut ctx = hot_reload_context();
+        let mut state = EventLog::default();
+
+        let texture = Texture::new(&mut ctx, &path).unwrap();
+        let clone = texture.clone();
+
+        tick(&mut ctx, &mut state);
+        assert!(state.events.is_empty());
+
+        write_changed(&path, |path| {
+            RgbaImage::from_pixel(2, 1, Rgba([0, 0, 255, 255]))
+                .save(path)
+                .unwrap()
+        });
+
+        tick(&mut ctx, &mut state);
+
+        assert!(matches!(
+            state.events.as_slice(),
+            [Event::AssetReloaded { path: reloaded }] if *reloaded == path
+        ));
+
+        assert_eq!(clone.size(), (2, 1));
+        assert_eq!(
+            clone.get_data(&mut ctx).as_bytes(),
+            [0, 0, 255, 255, 0, 0, 255, 255]
+        );
+
+        state.events.clear();
+        write_changed(&path, |path| fs::write(path, b"not an image").unwrap());
+
+        tick(&mut ctx, &mut state);
+
+        assert!(matches!(
+            state.events.as_slice(),
+            [Event::AssetReloadFailed { error, .. }] if matches!(**error, TetraError::InvalidTexture(_))
+        ));
+
+        assert_eq!(texture.size(), (2, 1));
+    }
+
+    #[test]
+    fn shaders_are_reloaded_in_place() {
+        let dir = test_dir("shader");
+        let path = dir.join("shader.frag");
+
+        fs::write(&path, "void main() {}").unwrap();
+
+        let mut ctx = hot_reload_context();
+        let mut state = EventLog::default();
+
+        let shader = Shader::from_fragment_file(&mut ctx, &path).unwrap();
+
+        write_changed(&path, |path| fs::write(path, "void main() { }").unwrap());
+        tick(&mut ctx, &mut state);
+
+        assert!(matches!(
+            state.events.as_slice(),
+            [Event::AssetReloaded { .. }]
+        ));
+
+        // Once the shader has been dropped, the file should no longer be watched.
+        drop(shader);
+
+        state.events.clear();
+        write_changed(&path, |path| fs::write(path, "void main() {}").unwrap());
+        tick(&mut ctx, &mut state);
+
+        assert!(state.events.is_empty());
+    }
+
+    #[test]
+    fn shader_uniforms_survive_reloading() {
+        let dir = test_dir("uniforms");
+        let path = dir.join("shader.frag");
+
+        fs::write(&path, "void main() {}").unwrap();
+
+        let mut ctx = hot_reload_context();
+        let mut state = EventLog::default();
+
+        let shader = Shader::from_fragment_file(&mut ctx, &path).unwrap();
+
+        shader.set_uniform(&mut ctx, "u_time", 1.0);
+        shader.set_uniform(&mut ctx, "u_time", 2.0);
+        shader.set_uniform(&mut ctx, "u_offsets", [Vec2::new(1.0, 2.0)]);
+
+        write_changed(&path, |path| fs::write(path, "void main() { }").unwrap());
+        tick(&mut ctx, &mut state);
+
+        assert!(matches!(
+            state.events.as_slice(),
+            [Event::AssetReloaded { .. }]
+        ));
+
+        let uniforms = shader.data.uniforms.borrow();
+        let uniforms = uniforms.as_ref().unwrap();
+
+        assert_eq!(uniforms.len(), 2);
+        assert_eq!(uniforms["u_time"], UniformData::F32(vec![2.0]));
+        assert_eq!(
+            uniforms["u_offsets"],
+            UniformData::Vec2(vec![Vec2::new(1.0, 2.0)])
+        );
+    }
+
+    #[test]
+    fn nothing_is_watched_by_default() {
+        let dir = test_dir("disabled");
+        let path = dir.join("shader.frag");
+
+        fs::write(&path, "void main() {}").unwrap();
+
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        let mut state = EventLog::default();
+        let _shader = Shader::from_fragment_file(&mut ctx, &path).unwrap();
+
+        write_changed(&path, |path| fs::write(path, "void main() { }").unwrap());
+        tick(&mut ctx, &mut state);
+
+        assert!(state.events.is_empty());
+    }
+}
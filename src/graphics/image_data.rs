@@ -1,6 +1,8 @@
+use std::io::Cursor;
 use std::path::Path;
 
 use half::f16;
+use image::{DynamicImage, ImageFormat, RgbaImage};
 
 use crate::error::{Result, TetraError};
 use crate::fs;
@@ -52,7 +54,7 @@ impl ImageData {
     where
         P: AsRef<Path>,
     {
-        let image = fs::read_to_image(path)?.into_rgba8();
+        let image = fs::read_asset_to_image(path)?.into_rgba8();
         let width = image.width() as usize;
         let height = image.height() as usize;
 
@@ -226,6 +228,62 @@ impl ImageData {
         Texture::from_image_data(ctx, self)
     }
 
+    /// Saves the image data to the given file.
+    ///
+    /// The format will be determined based on the file extension, and the corresponding
+    /// Cargo feature must be enabled. Images that are not stored as [`TextureFormat::Rgba8`]
+    /// will be converted to it before they are encoded.
+    ///
+    /// # Errors
+    ///
+    /// * [`TetraError::InvalidTexture`] will be returned if the image could not be encoded
+    /// in the chosen format.
+    /// * [`TetraError::FailedToSaveFile`] will be returned if the file could not be written.
+    pub fn save<P>(&self, path: P) -> Result
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let format = ImageFormat::from_path(path).map_err(TetraError::InvalidTexture)?;
+
+        fs::write(path, self.encode(format)?)
+    }
+
+    /// Encodes the image data as a PNG file, returning the encoded bytes.
+    ///
+    /// Images that are not stored as [`TextureFormat::Rgba8`] will be converted to it
+    /// before they are encoded.
+    ///
+    /// # Errors
+    ///
+    /// * [`TetraError::InvalidTexture`] will be returned if the image could not be encoded.
+    #[cfg(feature = "texture_png")]
+    pub fn encode_png(&self) -> Result<Vec<u8>> {
+        self.encode(ImageFormat::Png)
+    }
+
+    fn encode(&self, format: ImageFormat) -> Result<Vec<u8>> {
+        let pixels = match self.format {
+            TextureFormat::Rgba8 => self.data.clone(),
+            _ => self
+                .data
+                .chunks_exact(self.format.stride())
+                .flat_map(|pixel| <[u8; 4]>::from(read_color(self.format, pixel)))
+                .collect(),
+        };
+
+        let image = RgbaImage::from_raw(self.width as u32, self.height as u32, pixels)
+            .expect("buffer should match the image size");
+
+        let mut encoded = Vec::new();
+
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut Cursor::new(&mut encoded), format)
+            .map_err(TetraError::InvalidTexture)?;
+
+        Ok(encoded)
+    }
+
     /// Gets the color of the pixel at the specified location.
     ///
     /// If the image's [`TextureFormat`] does not contain one of the three color channels,
@@ -848,4 +906,22 @@ mod tests {
             bytemuck::cast_slice(&output),
         );
     }
+
+    #[test]
+    #[cfg(feature = "texture_png")]
+    fn encode_png_round_trip() {
+        let image = ImageData::from_data(
+            2,
+            1,
+            TextureFormat::Rgba8,
+            vec![0xFF, 0x00, 0x00, 0xFF, 0x00, 0x00, 0xFF, 0x80],
+        )
+        .unwrap();
+
+        let png = image.encode_png().unwrap();
+        let decoded = ImageData::from_encoded(&png).unwrap();
+
+        assert_eq!(decoded.size(), (2, 1));
+        assert_eq!(decoded.as_bytes(), image.as_bytes());
+    }
 }
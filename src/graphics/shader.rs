@@ -9,7 +9,7 @@ use hashbrown::HashMap;
 
 use crate::error::Result;
 use crate::fs;
-use crate::graphics::{Color, Texture};
+use crate::graphics::{hot_reload, Color, Texture};
 use crate::math::{Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
 use crate::platform::{GraphicsDevice, RawShader};
 use crate::Context;
@@ -35,6 +35,46 @@ pub(crate) struct ShaderSharedData {
     pub(crate) handle: RawShader,
     pub(crate) samplers: RefCell<HashMap<String, Sampler>>,
     pub(crate) next_unit: Cell<u32>,
+
+    /// The values that have been set for each uniform, if the shader can be hot reloaded.
+    /// These are needed to restore the state of the shader after it is recompiled.
+    pub(crate) uniforms: RefCell<Option<HashMap<String, UniformData>>>,
+}
+
+impl ShaderSharedData {
+    /// Recompiles the shader from the given source, leaving it untouched if
+    /// compilation fails.
+    fn reload(
+        &self,
+        device: &mut GraphicsDevice,
+        vertex_shader: &str,
+        fragment_shader: &str,
+    ) -> Result {
+        device.replace_shader(&self.handle, vertex_shader, fragment_shader)?;
+
+        // The new program starts with all of its uniforms zeroed, so the values from
+        // the old one need to be carried over. This includes the sampler units, which
+        // are stored as `int` uniforms.
+        if let Some(uniforms) = &*self.uniforms.borrow() {
+            for (name, data) in uniforms {
+                data.apply(device, &self.handle, name);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stores a copy of a uniform's value, if the shader can be hot reloaded.
+    fn store_uniform(&self, name: &str, data: impl FnOnce() -> UniformData) {
+        if let Some(uniforms) = &mut *self.uniforms.borrow_mut() {
+            match uniforms.get_mut(name) {
+                Some(existing) => *existing = data(),
+                None => {
+                    uniforms.insert(name.to_owned(), data());
+                }
+            }
+        }
+    }
 }
 
 impl PartialEq for ShaderSharedData {
@@ -116,10 +156,10 @@ impl Shader {
     where
         P: AsRef<Path>,
     {
-        Shader::with_device(
-            &mut ctx.device,
-            &fs::read_to_string(vertex_path)?,
-            &fs::read_to_string(fragment_path)?,
+        Shader::from_files(
+            ctx,
+            Some(vertex_path.as_ref()),
+            Some(fragment_path.as_ref()),
         )
     }
 
@@ -139,11 +179,7 @@ impl Shader {
     where
         P: AsRef<Path>,
     {
-        Shader::with_device(
-            &mut ctx.device,
-            &fs::read_to_string(path)?,
-            DEFAULT_FRAGMENT_SHADER,
-        )
+        Shader::from_files(ctx, Some(path.as_ref()), None)
     }
 
     /// Creates a new shader program from the given fragment shader file.
@@ -162,11 +198,7 @@ impl Shader {
     where
         P: AsRef<Path>,
     {
-        Shader::with_device(
-            &mut ctx.device,
-            DEFAULT_VERTEX_SHADER,
-            &fs::read_to_string(path)?,
-        )
+        Shader::from_files(ctx, None, Some(path.as_ref()))
     }
 
     /// Creates a new shader program from the given strings.
@@ -213,6 +245,32 @@ impl Shader {
         Shader::with_device(&mut ctx.device, DEFAULT_VERTEX_SHADER, shader)
     }
 
+    fn from_files(
+        ctx: &mut Context,
+        vertex_path: Option<&Path>,
+        fragment_path: Option<&Path>,
+    ) -> Result<Shader> {
+        let (vertex_shader, fragment_shader) = read_sources(ctx, vertex_path, fragment_path)?;
+        let shader = Shader::with_device(&mut ctx.device, &vertex_shader, &fragment_shader)?;
+
+        if ctx.graphics.hot_reload.is_some() {
+            *shader.data.uniforms.borrow_mut() = Some(HashMap::new());
+        }
+
+        let vertex_path = vertex_path.map(Path::to_owned);
+        let fragment_path = fragment_path.map(Path::to_owned);
+        let paths = vertex_path.iter().chain(&fragment_path).cloned().collect();
+
+        hot_reload::watch(ctx, &shader.data, paths, move |ctx, data| {
+            let (vertex_shader, fragment_shader) =
+                read_sources(ctx, vertex_path.as_deref(), fragment_path.as_deref())?;
+
+            data.reload(&mut ctx.device, &vertex_shader, &fragment_shader)
+        });
+
+        Ok(shader)
+    }
+
     pub(crate) fn with_device(
         device: &mut GraphicsDevice,
         vertex_shader: &str,
@@ -225,6 +283,7 @@ impl Shader {
                 handle,
                 samplers: RefCell::new(HashMap::new()),
                 next_unit: Cell::new(1),
+                uniforms: RefCell::new(None),
             }),
         })
     }
@@ -272,6 +331,26 @@ impl Shader {
     }
 }
 
+/// Reads the source code for a shader, falling back to the defaults for any stage
+/// that does not have a path.
+fn read_sources(
+    ctx: &Context,
+    vertex_path: Option<&Path>,
+    fragment_path: Option<&Path>,
+) -> Result<(String, String)> {
+    let vertex_shader = match vertex_path {
+        Some(path) => fs::read_to_string(ctx, path)?,
+        None => DEFAULT_VERTEX_SHADER.to_owned(),
+    };
+
+    let fragment_shader = match fragment_path {
+        Some(path) => fs::read_to_string(ctx, path)?,
+        None => DEFAULT_FRAGMENT_SHADER.to_owned(),
+    };
+
+    Ok((vertex_shader, fragment_shader))
+}
+
 /// Implemented for types that can be passed as a uniform value to a shader.
 ///
 /// As the implementation of this trait currently interacts directly with the platform layer,
@@ -282,7 +361,23 @@ pub trait UniformValue {
 }
 
 macro_rules! simple_uniforms {
-    ($($t:ty => $f:ident, $doc:expr, $arraydoc:expr),* $(,)?) => {
+    ($($t:ty => $f:ident, $variant:ident, $doc:expr, $arraydoc:expr),* $(,)?) => {
+        /// A copy of the value of a uniform.
+        #[derive(Debug, Clone, PartialEq)]
+        pub(crate) enum UniformData {
+            $($variant(Vec<$t>),)*
+        }
+
+        impl UniformData {
+            fn apply(&self, device: &mut GraphicsDevice, shader: &RawShader, name: &str) {
+                let location = device.get_uniform_location(shader, name);
+
+                match self {
+                    $(UniformData::$variant(values) => device.$f(shader, location.as_ref(), values),)*
+                }
+            }
+        }
+
         $(
             #[doc = $doc]
             impl UniformValue for $t {
@@ -295,6 +390,8 @@ macro_rules! simple_uniforms {
                 ) {
                     let location = ctx.device.get_uniform_location(&shader.data.handle, name);
                     ctx.device.$f(&shader.data.handle, location.as_ref(), slice::from_ref(self));
+
+                    shader.data.store_uniform(name, || UniformData::$variant(vec![*self]));
                 }
             }
 
@@ -309,6 +406,8 @@ macro_rules! simple_uniforms {
                 ) {
                     let location = ctx.device.get_uniform_location(&shader.data.handle, name);
                     ctx.device.$f(&shader.data.handle, location.as_ref(), self);
+
+                    shader.data.store_uniform(name, || UniformData::$variant(self.to_vec()));
                 }
             }
 
@@ -323,6 +422,8 @@ macro_rules! simple_uniforms {
                 ) {
                     let location = ctx.device.get_uniform_location(&shader.data.handle, name);
                     ctx.device.$f(&shader.data.handle, location.as_ref(), self);
+
+                    shader.data.store_uniform(name, || UniformData::$variant(self.to_vec()));
                 }
             }
         )*
@@ -330,16 +431,16 @@ macro_rules! simple_uniforms {
 }
 
 simple_uniforms! {
-    i32 => set_uniform_i32, "Can be accessed as an `int` in your shader.", "Can be accessed as an array of `int`s in your shader.",
-    u32 => set_uniform_u32, "Can be accessed as a `uint` in your shader.", "Can be accessed as an array of `uint`s in your shader.",
-    f32 => set_uniform_f32, "Can be accessed as a `float` in your shader.", "Can be accessed as an array of `float`s in your shader.",
-    Vec2<f32> => set_uniform_vec2, "Can be accessed as a `vec2` in your shader.", "Can be accessed as an array of `vec2`s in your shader.",
-    Vec3<f32> => set_uniform_vec3, "Can be accessed as a `vec3` in your shader.", "Can be accessed as an array of `vec3`s in your shader.",
-    Vec4<f32> => set_uniform_vec4, "Can be accessed as a `vec4` in your shader.", "Can be accessed as an array of `vec4`s in your shader.",
-    Mat2<f32> => set_uniform_mat2, "Can be accessed as a `mat2` in your shader.", "Can be accessed as an array of `mat2`s in your shader.",
-    Mat3<f32> => set_uniform_mat3, "Can be accessed as a `mat3` in your shader.", "Can be accessed as an array of `mat3`s in your shader.",
-    Mat4<f32> => set_uniform_mat4, "Can be accessed as a `mat4` in your shader.", "Can be accessed as an array of `mat4`s in your shader.",
-    Color => set_uniform_color, "Can be accessed as a `vec4` in your shader.", "Can be accessed as an array of `vec4`s in your shader.",
+    i32 => set_uniform_i32, I32, "Can be accessed as an `int` in your shader.", "Can be accessed as an array of `int`s in your shader.",
+    u32 => set_uniform_u32, U32, "Can be accessed as a `uint` in your shader.", "Can be accessed as an array of `uint`s in your shader.",
+    f32 => set_uniform_f32, F32, "Can be accessed as a `float` in your shader.", "Can be accessed as an array of `float`s in your shader.",
+    Vec2<f32> => set_uniform_vec2, Vec2, "Can be accessed as a `vec2` in your shader.", "Can be accessed as an array of `vec2`s in your shader.",
+    Vec3<f32> => set_uniform_vec3, Vec3, "Can be accessed as a `vec3` in your shader.", "Can be accessed as an array of `vec3`s in your shader.",
+    Vec4<f32> => set_uniform_vec4, Vec4, "Can be accessed as a `vec4` in your shader.", "Can be accessed as an array of `vec4`s in your shader.",
+    Mat2<f32> => set_uniform_mat2, Mat2, "Can be accessed as a `mat2` in your shader.", "Can be accessed as an array of `mat2`s in your shader.",
+    Mat3<f32> => set_uniform_mat3, Mat3, "Can be accessed as a `mat3` in your shader.", "Can be accessed as an array of `mat3`s in your shader.",
+    Mat4<f32> => set_uniform_mat4, Mat4, "Can be accessed as a `mat4` in your shader.", "Can be accessed as an array of `mat4`s in your shader.",
+    Color => set_uniform_color, Color, "Can be accessed as a `vec4` in your shader.", "Can be accessed as an array of `vec4`s in your shader.",
 }
 
 /// Can be accessed via a `sampler2D` in your shader.
@@ -8,17 +8,21 @@ mod vector;
 
 use std::cell::RefCell;
 use std::fmt::{self, Debug, Formatter};
-use std::path::Path;
+use std::iter;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 use crate::error::Result;
-use crate::graphics::text::cache::{FontCache, TextGeometry};
-use crate::graphics::{self, DrawParams, Rectangle};
+use crate::graphics::text::cache::{FontCache, Rasterizer, TextGeometry};
+use crate::graphics::{self, hot_reload, DrawParams, Rectangle};
 use crate::Context;
 
 #[cfg(feature = "font_ttf")]
 pub use crate::graphics::text::vector::VectorFontBuilder;
 
+#[cfg(feature = "font_ttf")]
+pub(crate) use crate::graphics::text::vector::load_font_vec;
+
 pub use crate::graphics::text::bmfont::BmFontBuilder;
 
 use super::FilterMode;
@@ -79,7 +83,10 @@ impl Font {
     where
         P: AsRef<Path>,
     {
-        VectorFontBuilder::new(path)?.with_size(ctx, size)
+        let font = VectorFontBuilder::new(path.as_ref())?.with_size(ctx, size)?;
+        font.watch_vector(ctx, path.as_ref(), size);
+
+        Ok(font)
     }
 
     /// Creates a `Font` from a slice of binary data.
@@ -143,7 +150,56 @@ impl Font {
     where
         P: AsRef<Path>,
     {
-        BmFontBuilder::new(path)?.build(ctx)
+        let builder = BmFontBuilder::new(path.as_ref())?;
+        let page_paths = builder.page_paths()?;
+
+        let font = builder.build(ctx)?;
+        font.watch_bmfont(ctx, path.as_ref(), page_paths);
+
+        Ok(font)
+    }
+
+    pub(crate) fn from_rasterizer(
+        ctx: &mut Context,
+        rasterizer: Box<dyn Rasterizer>,
+    ) -> Result<Font> {
+        let cache = FontCache::new(
+            &mut ctx.device,
+            rasterizer,
+            ctx.graphics.default_filter_mode,
+        )?;
+
+        Ok(Font {
+            data: Rc::new(RefCell::new(cache)),
+        })
+    }
+
+    /// Reloads the font when the vector font file at the given path changes, if hot
+    /// reloading is enabled.
+    #[cfg(feature = "font_ttf")]
+    pub(crate) fn watch_vector(&self, ctx: &mut Context, path: &Path, size: f32) {
+        let path = path.to_owned();
+
+        hot_reload::watch(ctx, &self.data, vec![path.clone()], move |ctx, data| {
+            let rasterizer = VectorFontBuilder::new(&path)?.rasterizer(size);
+
+            data.borrow_mut()
+                .set_rasterizer(&mut ctx.device, rasterizer)
+        });
+    }
+
+    /// Reloads the font when the BMFont file at the given path or any of its page
+    /// images change, if hot reloading is enabled.
+    pub(crate) fn watch_bmfont(&self, ctx: &mut Context, path: &Path, page_paths: Vec<PathBuf>) {
+        let path = path.to_owned();
+        let paths = iter::once(path.clone()).chain(page_paths).collect();
+
+        hot_reload::watch(ctx, &self.data, paths, move |ctx, data| {
+            let rasterizer = BmFontBuilder::new(&path)?.into_rasterizer()?;
+
+            data.borrow_mut()
+                .set_rasterizer(&mut ctx.device, Box::new(rasterizer))
+        });
     }
 
     /// Returns the filter mode of the font.
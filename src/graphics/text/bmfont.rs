@@ -1,6 +1,4 @@
-use std::cell::RefCell;
 use std::path::{Path, PathBuf};
-use std::rc::Rc;
 use std::str::FromStr;
 
 use hashbrown::HashMap;
@@ -11,7 +9,6 @@ use crate::math::Vec2;
 use crate::{fs, Context};
 use crate::{Result, TetraError};
 
-use super::cache::FontCache;
 use super::Font;
 
 struct BmFontGlyph {
@@ -78,7 +75,7 @@ impl BmFontBuilder {
         P: AsRef<Path>,
     {
         let path = path.as_ref();
-        let font = fs::read_to_string(path)?;
+        let font = fs::read_asset_to_string(path)?;
 
         // This should be okay to unwrap, if the font itself loaded...
         let image_dir = path.parent().unwrap().to_owned();
@@ -203,21 +200,34 @@ impl BmFontBuilder {
     /// * [`TetraError::PlatformError`] will be returned if the GPU cache for the font
     ///   could not be created.
     pub fn build(self, ctx: &mut Context) -> Result<Font> {
-        let rasterizer: Box<dyn Rasterizer> = Box::new(BmFontRasterizer::new(
-            &self.font,
-            self.image_dir,
-            self.pages,
-        )?);
-
-        let cache = FontCache::new(
-            &mut ctx.device,
-            rasterizer,
-            ctx.graphics.default_filter_mode,
-        )?;
-
-        Ok(Font {
-            data: Rc::new(RefCell::new(cache)),
-        })
+        Font::from_rasterizer(ctx, Box::new(self.into_rasterizer()?))
+    }
+
+    /// Returns the paths of the page images that will be loaded from the image directory
+    /// when the font is built.
+    pub(crate) fn page_paths(&self) -> Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+
+        for line in self.font.lines() {
+            let (tag, attributes) = parse_tag(line);
+
+            if tag == "page" {
+                let attributes = parse_attributes(attributes)?;
+                let id: u32 = attributes.parse("id")?;
+
+                if !self.pages.contains_key(&id) {
+                    if let Some(image_dir) = &self.image_dir {
+                        paths.push(image_dir.join(attributes.get("file")?));
+                    }
+                }
+            }
+        }
+
+        Ok(paths)
+    }
+
+    pub(crate) fn into_rasterizer(self) -> Result<BmFontRasterizer> {
+        BmFontRasterizer::new(&self.font, self.image_dir, self.pages)
     }
 }
 
@@ -494,4 +504,21 @@ mod tests {
 
         parse_attributes(rest).unwrap();
     }
+
+    #[test]
+    fn page_paths_skip_manually_set_pages() {
+        let font = "page id=0 file=\"font_0.png\"\npage id=1 file=\"font_1.png\"";
+
+        let builder = BmFontBuilder::from_file_data(font)
+            .with_image_dir("./fonts")
+            .with_page_image_data(
+                0,
+                ImageData::from_data(1, 1, TextureFormat::Rgba8, vec![0; 4]).unwrap(),
+            );
+
+        assert_eq!(
+            builder.page_paths().unwrap(),
+            [Path::new("./fonts").join("font_1.png")]
+        );
+    }
 }
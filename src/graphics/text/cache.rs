@@ -120,7 +120,7 @@ impl FontCache {
         self.packer.texture()
     }
 
-    /// Returns the number of times that the cache has been resized.
+    /// Returns the number of times that the cache has been resized or reset.
     ///
     /// This can be compared against the `resize_count` of the `TextGeometry` to determine
     /// if that struct's data is stale.
@@ -298,6 +298,23 @@ impl FontCache {
         }
     }
 
+    /// Replaces the cache's rasterizer, clearing any cached data.
+    pub fn set_rasterizer(
+        &mut self,
+        device: &mut GraphicsDevice,
+        rasterizer: Box<dyn Rasterizer>,
+    ) -> Result {
+        let (texture_width, texture_height) = self.packer.texture().size();
+
+        self.packer.resize(device, texture_width, texture_height)?;
+        self.rasterizer = rasterizer;
+        self.glyphs.clear();
+
+        self.resize_count += 1;
+
+        Ok(())
+    }
+
     /// Resizes the texture atlas, clearing any cached data.
     fn resize(&mut self, device: &mut GraphicsDevice) -> Result {
         let (texture_width, texture_height) = self.packer.texture().size();
@@ -1,4 +1,3 @@
-use std::cell::RefCell;
 use std::path::Path;
 use std::rc::Rc;
 
@@ -6,7 +5,7 @@ use ab_glyph::{Font as AbFont, FontRef, FontVec, PxScale, ScaleFont};
 
 use crate::error::{Result, TetraError};
 use crate::fs;
-use crate::graphics::text::cache::{FontCache, RasterizedGlyph, Rasterizer};
+use crate::graphics::text::cache::{RasterizedGlyph, Rasterizer};
 use crate::graphics::text::{Font, FontTextureStyle};
 use crate::graphics::Rectangle;
 use crate::math::Vec2;
@@ -150,13 +149,14 @@ impl VectorFontBuilder {
     where
         P: AsRef<Path>,
     {
-        let font_bytes = fs::read(path)?;
-        let font = FontVec::try_from_vec(font_bytes).map_err(|_| TetraError::InvalidFont)?;
+        Ok(VectorFontBuilder::from_font_vec(load_font_vec(path)?))
+    }
 
-        Ok(VectorFontBuilder {
+    pub(crate) fn from_font_vec(font: FontVec) -> VectorFontBuilder {
+        VectorFontBuilder {
             data: VectorFontData::Owned(Rc::new(font)),
             texture_style: FontTextureStyle::Normal,
-        })
+        }
     }
 
     /// Loads a vector font from a slice of binary data.
@@ -186,7 +186,11 @@ impl VectorFontBuilder {
     /// * [`TetraError::PlatformError`] will be returned if the GPU cache for the font
     ///   could not be created.
     pub fn with_size(&self, ctx: &mut Context, size: f32) -> Result<Font> {
-        let rasterizer: Box<dyn Rasterizer> = match &self.data {
+        Font::from_rasterizer(ctx, self.rasterizer(size))
+    }
+
+    pub(crate) fn rasterizer(&self, size: f32) -> Box<dyn Rasterizer> {
+        match &self.data {
             VectorFontData::Owned(f) => Box::new(VectorRasterizer::new(
                 Rc::clone(f),
                 size,
@@ -197,16 +201,18 @@ impl VectorFontBuilder {
                 size,
                 self.texture_style,
             )),
-        };
+        }
+    }
+}
 
-        let cache = FontCache::new(
-            &mut ctx.device,
-            rasterizer,
-            ctx.graphics.default_filter_mode,
-        )?;
+/// Loads and parses a vector font file.
+///
+/// Unlike a `VectorFontBuilder`, the result can be sent between threads.
+pub(crate) fn load_font_vec<P>(path: P) -> Result<FontVec>
+where
+    P: AsRef<Path>,
+{
+    let font_bytes = fs::read_asset(path)?;
 
-        Ok(Font {
-            data: Rc::new(RefCell::new(cache)),
-        })
-    }
+    FontVec::try_from_vec(font_bytes).map_err(|_| TetraError::InvalidFont)
 }
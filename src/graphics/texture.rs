@@ -5,7 +5,7 @@ use std::path::Path;
 use std::rc::Rc;
 
 use crate::error::Result;
-use crate::graphics::{self, DrawParams, ImageData, Rectangle};
+use crate::graphics::{self, hot_reload, DrawParams, ImageData, Rectangle};
 use crate::platform::{GraphicsDevice, RawTexture};
 use crate::Context;
 
@@ -15,6 +15,22 @@ pub(crate) struct TextureSharedData {
     filter_mode: Cell<FilterMode>,
 }
 
+impl TextureSharedData {
+    /// Replaces the texture's contents with the image at the given path, resizing
+    /// it if needed.
+    fn reload(&self, ctx: &mut Context, path: &Path) -> Result {
+        let image = ImageData::new(path)?;
+        let (width, height) = image.size();
+
+        if (width, height) != (self.handle.width(), self.handle.height()) {
+            ctx.device.resize_texture(&self.handle, width, height)?;
+        }
+
+        ctx.device
+            .set_texture_data(&self.handle, image.as_bytes(), 0, 0, width, height)
+    }
+}
+
 impl PartialEq for TextureSharedData {
     fn eq(&self, other: &TextureSharedData) -> bool {
         // filter_mode should always match what's set on the GPU,
@@ -83,8 +99,11 @@ impl Texture {
     where
         P: AsRef<Path>,
     {
-        let data = ImageData::new(path)?;
-        Texture::from_image_data(ctx, &data)
+        let data = ImageData::new(path.as_ref())?;
+        let texture = Texture::from_image_data(ctx, &data)?;
+        texture.watch(ctx, path.as_ref());
+
+        Ok(texture)
     }
 
     /// Creates a new texture from a slice of pixel data.
@@ -157,6 +176,16 @@ impl Texture {
         )
     }
 
+    /// Reloads the texture when the file at the given path changes, if hot reloading
+    /// is enabled.
+    pub(crate) fn watch(&self, ctx: &mut Context, path: &Path) {
+        let path = path.to_owned();
+
+        hot_reload::watch(ctx, &self.data, vec![path.clone()], move |ctx, data| {
+            data.reload(ctx, &path)
+        });
+    }
+
     pub(crate) fn from_raw(handle: RawTexture, filter_mode: FilterMode) -> Texture {
         Texture {
             data: Rc::new(TextureSharedData {
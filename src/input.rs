@@ -9,6 +9,40 @@
 //! a new one will be allocated. This means that if you unplug a controller and then plug it back in,
 //! it should retain its existing ID. This behaviour might be made smarter in future versions.
 //!
+//! The raw positions of the sticks and triggers can be processed before they reach your game,
+//! via [`GamepadSettings`]. This can be used to apply dead zones (to prevent stick drift), response
+//! curves, and thresholds for when the triggers count as being pressed.
+//!
+//! For local multiplayer, the [`players`] module can be used to assign gamepads (and the keyboard)
+//! to players, in a way that survives gamepads being disconnected and reconnected.
+//!
+//! # Touch
+//!
+//! Each finger that touches the screen is tracked as a separate [`Touch`], with an ID that
+//! stays the same until the finger is lifted. By default, the primary touch will also move the
+//! mouse and hold down the left mouse button, so that games which only handle mouse input can
+//! be played on a touchscreen - this can be disabled via [`set_touch_mouse_emulation`].
+//!
+//! Taps, swipes, pinches and other gestures can be recognized from the mouse and touch input
+//! via the [`gestures`] module.
+//!
+//! # Recording and Playback
+//!
+//! The player's input can be recorded via [`start_recording`] and [`stop_recording`], and then
+//! played back later via [`start_playback`]. While a recording is being played back, it replaces
+//! the input from the platform, and each frame is given the same elapsed time as when it was
+//! recorded. If your game uses [`Timestep::Fixed`](crate::time::Timestep::Fixed) and is otherwise
+//! deterministic, it will play out exactly the same way as the original session - this can be
+//! used for reproducing bugs, attract mode demos, or regression tests.
+//!
+//! Recordings can be saved to and loaded from files via [`InputRecording::save`] and
+//! [`InputRecording::load`].
+//!
+//! # Actions
+//!
+//! Rather than checking for specific keys and buttons, you can bind named actions to any
+//! number of inputs via the [`actions`] module.
+//!
 //! # Examples
 //!
 //! The [`keyboard`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/keyboard.rs)
@@ -20,21 +54,40 @@
 //! The [`gamepad`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/gamepad.rs)
 //! example demonstrates how to handle gamepad input.
 //!
+//! The [`touch`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/touch.rs)
+//! example demonstrates how to handle touch input.
+//!
+//! The [`gestures`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/gestures.rs)
+//! example demonstrates how to pan and zoom a camera using gestures.
+//!
 //! The [`text_input`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/text_input.rs)
 //! example demonstrates how to handle text entry.
+//!
+//! The [`input_replay`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/input_replay.rs)
+//! example demonstrates how to record and play back input.
+//!
+//! The [`actions`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/actions.rs)
+//! example demonstrates how to bind actions to several input devices.
 
+pub mod actions;
 mod gamepad;
+pub mod gestures;
 mod keyboard;
 mod mouse;
+pub mod players;
+mod replay;
+mod touch;
 
 use hashbrown::HashSet;
 
 use crate::math::Vec2;
-use crate::{Context, Result};
+use crate::{Context, ContextBuilder, Result};
 
 pub use gamepad::*;
 pub use keyboard::*;
 pub use mouse::*;
+pub use replay::*;
+pub use touch::*;
 
 pub(crate) struct InputContext {
     keys_down: HashSet<Key>,
@@ -49,13 +102,23 @@ pub(crate) struct InputContext {
     mouse_position: Vec2<f32>,
     mouse_wheel_movement: Vec2<i32>,
 
+    touches: Vec<Touch>,
+    touches_started: HashSet<u64>,
+    touches_ended: Vec<Touch>,
+    primary_touch: Option<u64>,
+    touch_mouse_emulation: bool,
+
     current_text_input: Option<String>,
 
     pads: Vec<Option<GamepadState>>,
+    default_gamepad_settings: GamepadSettings,
+
+    recorder: Option<Recorder>,
+    playback: Option<Playback>,
 }
 
 impl InputContext {
-    pub(crate) fn new() -> InputContext {
+    pub(crate) fn new(settings: &ContextBuilder) -> InputContext {
         InputContext {
             keys_down: HashSet::new(),
             keys_pressed: HashSet::new(),
@@ -69,9 +132,19 @@ impl InputContext {
             mouse_position: Vec2::zero(),
             mouse_wheel_movement: Vec2::zero(),
 
+            touches: Vec::new(),
+            touches_started: HashSet::new(),
+            touches_ended: Vec::new(),
+            primary_touch: None,
+            touch_mouse_emulation: settings.touch_mouse_emulation,
+
             current_text_input: None,
 
             pads: Vec::new(),
+            default_gamepad_settings: GamepadSettings::default(),
+
+            recorder: None,
+            playback: None,
         }
     }
 }
@@ -82,6 +155,8 @@ pub(crate) fn clear(ctx: &mut Context) {
     ctx.input.mouse_buttons_pressed.clear();
     ctx.input.mouse_buttons_released.clear();
     ctx.input.mouse_wheel_movement = Vec2::zero();
+    ctx.input.touches_started.clear();
+    ctx.input.touches_ended.clear();
 
     ctx.input.current_text_input = None;
 
@@ -0,0 +1,948 @@
+//! Functions and types relating to mapping the player's input to actions.
+//!
+//! Rather than checking for specific keys or buttons throughout your game, you can
+//! define the actions that the player can take (e.g. `Jump` or `Pause`), and then
+//! bind each one to as many inputs as you like via an [`ActionMap`]. This makes it
+//! easy to support several input devices at once, and to let the player change
+//! their controls.
+//!
+//! There are three kinds of binding:
+//!
+//! * Actions are digital - they are either down or up. They can be bound to keys,
+//!   mouse buttons and gamepad buttons.
+//! * Axes are analog values between `-1.0` and `1.0`. They can be bound to gamepad axes,
+//!   or to a pair of buttons.
+//! * Vectors are two-dimensional analog values, with a length of at most `1.0`. They can
+//!   be bound to gamepad sticks, or to a set of four buttons.
+//!
+//! # Rebinding
+//!
+//! The bindings in an [`ActionMap`] can be saved and loaded via a [`BindingProfile`], and
+//! [`BindingCapture`] can be used to listen for the next input that the player touches
+//! when they are remapping their controls. [`ActionMap::find_conflicts`] can then be used
+//! to check whether the same input has been bound to more than one thing.
+//!
+//! # Examples
+//!
+//! The [`actions`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/actions.rs)
+//! example demonstrates how to move a character with either the keyboard or a gamepad.
+
+mod capture;
+
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+
+use crate::input::{self, GamepadAxis, GamepadButton, GamepadStick, Key, KeyLabel, MouseButton};
+use crate::math::Vec2;
+use crate::Context;
+
+pub use capture::*;
+
+/// A digital input that can be bound to an action.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Binding {
+    /// A physical key on the keyboard.
+    Key(Key),
+
+    /// The key with the given label in the player's keyboard layout.
+    ///
+    /// In most cases, [`Binding::Key`] should be preferred, as it will work
+    /// consistently across keyboard layouts.
+    KeyLabel(KeyLabel),
+
+    /// A mouse button.
+    MouseButton(MouseButton),
+
+    /// A gamepad button.
+    GamepadButton(GamepadButton),
+}
+
+/// An input that can be bound to an axis.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum AxisBinding {
+    /// A pair of digital inputs. Holding `negative` gives a value of `-1.0`, holding
+    /// `positive` gives a value of `1.0`, and holding both gives `0.0`.
+    Buttons {
+        /// The input for the negative direction.
+        negative: Binding,
+
+        /// The input for the positive direction.
+        positive: Binding,
+    },
+
+    /// A gamepad axis.
+    GamepadAxis(GamepadAxis),
+}
+
+impl AxisBinding {
+    /// Returns the digital inputs that make up this binding, if any.
+    pub fn buttons(&self) -> Vec<Binding> {
+        match *self {
+            AxisBinding::Buttons { negative, positive } => vec![negative, positive],
+            AxisBinding::GamepadAxis(_) => Vec::new(),
+        }
+    }
+}
+
+/// An input that can be bound to a vector.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum VectorBinding {
+    /// Four digital inputs, one for each direction. Diagonals are normalized,
+    /// so that they are not faster than the other directions.
+    Buttons {
+        /// The input for the upwards direction (negative Y).
+        up: Binding,
+
+        /// The input for the downwards direction (positive Y).
+        down: Binding,
+
+        /// The input for the left direction (negative X).
+        left: Binding,
+
+        /// The input for the right direction (positive X).
+        right: Binding,
+    },
+
+    /// A gamepad stick.
+    GamepadStick(GamepadStick),
+}
+
+impl VectorBinding {
+    /// Returns the digital inputs that make up this binding, if any.
+    pub fn buttons(&self) -> Vec<Binding> {
+        match *self {
+            VectorBinding::Buttons {
+                up,
+                down,
+                left,
+                right,
+            } => vec![up, down, left, right],
+            VectorBinding::GamepadStick(_) => Vec::new(),
+        }
+    }
+
+    /// Creates a binding for the W, A, S and D keys.
+    pub fn wasd() -> VectorBinding {
+        VectorBinding::Buttons {
+            up: Binding::Key(Key::W),
+            down: Binding::Key(Key::S),
+            left: Binding::Key(Key::A),
+            right: Binding::Key(Key::D),
+        }
+    }
+
+    /// Creates a binding for the arrow keys.
+    pub fn arrow_keys() -> VectorBinding {
+        VectorBinding::Buttons {
+            up: Binding::Key(Key::Up),
+            down: Binding::Key(Key::Down),
+            left: Binding::Key(Key::Left),
+            right: Binding::Key(Key::Right),
+        }
+    }
+
+    /// Creates a binding for the D-pad on a gamepad.
+    pub fn dpad() -> VectorBinding {
+        VectorBinding::Buttons {
+            up: Binding::GamepadButton(GamepadButton::Up),
+            down: Binding::GamepadButton(GamepadButton::Down),
+            left: Binding::GamepadButton(GamepadButton::Left),
+            right: Binding::GamepadButton(GamepadButton::Right),
+        }
+    }
+}
+
+/// A saved set of bindings, which can be loaded into an [`ActionMap`].
+///
+/// This is useful for storing the player's controls in a settings file. Profiles can
+/// be created from the current state of a map via [`ActionMap::to_profile`], and
+/// loaded back in via [`ActionMap::load_profile`] or [`ActionMap::from_profile`].
+///
+/// The order of the entries is not guaranteed to be the same each time a profile
+/// is created.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature. The names of the actions must also
+/// be serializable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct BindingProfile<A> {
+    /// The bindings for each action.
+    pub actions: Vec<(A, Vec<Binding>)>,
+
+    /// The bindings for each axis.
+    pub axes: Vec<(A, Vec<AxisBinding>)>,
+
+    /// The bindings for each vector.
+    pub vectors: Vec<(A, Vec<VectorBinding>)>,
+}
+
+impl<A> Default for BindingProfile<A> {
+    fn default() -> BindingProfile<A> {
+        BindingProfile {
+            actions: Vec::new(),
+            axes: Vec::new(),
+            vectors: Vec::new(),
+        }
+    }
+}
+
+/// The name of an action, axis or vector in an [`ActionMap`].
+///
+/// As the same name can be used for one of each, this records which of them the
+/// name refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoundName<'a, A> {
+    /// The name of an action.
+    Action(&'a A),
+
+    /// The name of an axis.
+    Axis(&'a A),
+
+    /// The name of a vector.
+    Vector(&'a A),
+}
+
+impl<'a, A> BoundName<'a, A> {
+    /// Returns the name, regardless of what it refers to.
+    pub fn name(&self) -> &'a A {
+        match self {
+            BoundName::Action(name) | BoundName::Axis(name) | BoundName::Vector(name) => name,
+        }
+    }
+}
+
+/// A binding that is used by more than one action, axis or vector.
+///
+/// This is returned by [`ActionMap::find_conflicts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingConflict<'a, A> {
+    /// The binding that is used more than once.
+    pub binding: Binding,
+
+    /// The names of the actions, axes and vectors that use the binding.
+    pub names: Vec<BoundName<'a, A>>,
+}
+
+/// A set of named actions, axes and vectors, each of which can be bound to any
+/// number of inputs.
+///
+/// The names can be any type that implements [`Eq`] and [`Hash`] - usually this
+/// will be an enum that you define for your game, but strings work too. Actions,
+/// axes and vectors are stored separately, so the same name can be used for one
+/// of each.
+///
+/// When an action has several bindings, they are merged together - for example,
+/// the action is down if any of its bindings are down, and it is only reported as
+/// pressed when the first of them is pressed.
+///
+/// # Gamepads
+///
+/// By default, gamepad bindings are checked against every connected gamepad. For
+/// local multiplayer, you can create an `ActionMap` per player and restrict each
+/// one to a single gamepad via [`set_gamepad`](ActionMap::set_gamepad).
+///
+/// # Examples
+///
+/// ```
+/// # use tetra::input::{GamepadButton, Key};
+/// # use tetra::input::actions::{ActionMap, Binding, VectorBinding};
+/// # use tetra::input::GamepadStick;
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum Control {
+///     Move,
+///     Jump,
+/// }
+///
+/// let mut actions = ActionMap::new();
+///
+/// actions.bind(Control::Jump, Binding::Key(Key::Space));
+/// actions.bind(Control::Jump, Binding::GamepadButton(GamepadButton::A));
+///
+/// actions.bind_vector(Control::Move, VectorBinding::wasd());
+/// actions.bind_vector(Control::Move, VectorBinding::GamepadStick(GamepadStick::LeftStick));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ActionMap<A> {
+    actions: HashMap<A, Vec<Binding>>,
+    axes: HashMap<A, Vec<AxisBinding>>,
+    vectors: HashMap<A, Vec<VectorBinding>>,
+    gamepad: Option<usize>,
+}
+
+impl<A> ActionMap<A>
+where
+    A: Eq + Hash,
+{
+    /// Creates a new, empty `ActionMap`.
+    pub fn new() -> ActionMap<A> {
+        ActionMap {
+            actions: HashMap::new(),
+            axes: HashMap::new(),
+            vectors: HashMap::new(),
+            gamepad: None,
+        }
+    }
+
+    /// Adds a binding to an action.
+    pub fn bind(&mut self, action: A, binding: Binding) {
+        push_unique(self.actions.entry(action).or_default(), binding);
+    }
+
+    /// Adds a binding to an axis.
+    pub fn bind_axis(&mut self, axis: A, binding: AxisBinding) {
+        push_unique(self.axes.entry(axis).or_default(), binding);
+    }
+
+    /// Adds a binding to a vector.
+    pub fn bind_vector(&mut self, vector: A, binding: VectorBinding) {
+        push_unique(self.vectors.entry(vector).or_default(), binding);
+    }
+
+    /// Removes a binding from an action, returning `true` if it was bound.
+    pub fn unbind(&mut self, action: &A, binding: Binding) -> bool {
+        remove_binding(self.actions.get_mut(action), &binding)
+    }
+
+    /// Removes a binding from an axis, returning `true` if it was bound.
+    pub fn unbind_axis(&mut self, axis: &A, binding: AxisBinding) -> bool {
+        remove_binding(self.axes.get_mut(axis), &binding)
+    }
+
+    /// Removes a binding from a vector, returning `true` if it was bound.
+    pub fn unbind_vector(&mut self, vector: &A, binding: VectorBinding) -> bool {
+        remove_binding(self.vectors.get_mut(vector), &binding)
+    }
+
+    /// Removes all of the bindings from an action, axis and vector with the given name.
+    pub fn clear_bindings(&mut self, name: &A) {
+        self.actions.remove(name);
+        self.axes.remove(name);
+        self.vectors.remove(name);
+    }
+
+    /// Returns the bindings for an action.
+    pub fn get_bindings(&self, action: &A) -> &[Binding] {
+        self.actions.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the bindings for an axis.
+    pub fn get_axis_bindings(&self, axis: &A) -> &[AxisBinding] {
+        self.axes.get(axis).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the bindings for a vector.
+    pub fn get_vector_bindings(&self, vector: &A) -> &[VectorBinding] {
+        self.vectors.get(vector).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the gamepad that gamepad bindings are checked against, or `None`
+    /// if every connected gamepad is checked.
+    pub fn get_gamepad(&self) -> Option<usize> {
+        self.gamepad
+    }
+
+    /// Sets the gamepad that gamepad bindings are checked against. If this is
+    /// `None`, every connected gamepad will be checked.
+    ///
+    /// Defaults to `None`.
+    pub fn set_gamepad(&mut self, gamepad_id: Option<usize>) {
+        self.gamepad = gamepad_id;
+    }
+
+    /// Returns the names of the actions, axes and vectors that use the given binding.
+    ///
+    /// Axes and vectors are included if the binding is one of their buttons. This can be
+    /// used to warn the player that an input is already in use before rebinding it.
+    pub fn get_names_bound_to(&self, binding: Binding) -> Vec<BoundName<'_, A>> {
+        let mut names = Vec::new();
+
+        for (name, bindings) in &self.actions {
+            if bindings.contains(&binding) {
+                names.push(BoundName::Action(name));
+            }
+        }
+
+        for (name, bindings) in &self.axes {
+            if bindings.iter().any(|b| b.buttons().contains(&binding)) {
+                names.push(BoundName::Axis(name));
+            }
+        }
+
+        for (name, bindings) in &self.vectors {
+            if bindings.iter().any(|b| b.buttons().contains(&binding)) {
+                names.push(BoundName::Vector(name));
+            }
+        }
+
+        names
+    }
+
+    /// Returns every binding that is used by more than one action, axis or vector.
+    ///
+    /// Only digital bindings (including the buttons of axes and vectors) are checked - two
+    /// axes being bound to the same gamepad axis will not be reported.
+    pub fn find_conflicts(&self) -> Vec<BindingConflict<'_, A>> {
+        let mut seen = Vec::new();
+        let mut conflicts = Vec::new();
+
+        let all_bindings = self
+            .actions
+            .values()
+            .flatten()
+            .copied()
+            .chain(self.axes.values().flatten().flat_map(AxisBinding::buttons))
+            .chain(
+                self.vectors
+                    .values()
+                    .flatten()
+                    .flat_map(VectorBinding::buttons),
+            );
+
+        for binding in all_bindings {
+            if seen.contains(&binding) {
+                continue;
+            }
+
+            seen.push(binding);
+
+            let names = self.get_names_bound_to(binding);
+
+            if names.len() > 1 {
+                conflicts.push(BindingConflict { binding, names });
+            }
+        }
+
+        conflicts
+    }
+
+    /// Replaces all of the bindings in this map with the ones from a profile.
+    ///
+    /// The gamepad selected via [`set_gamepad`](ActionMap::set_gamepad) is not changed.
+    pub fn load_profile(&mut self, profile: BindingProfile<A>) {
+        self.actions.clear();
+        self.axes.clear();
+        self.vectors.clear();
+
+        for (name, bindings) in profile.actions {
+            let existing = self.actions.entry(name).or_default();
+
+            for binding in bindings {
+                push_unique(existing, binding);
+            }
+        }
+
+        for (name, bindings) in profile.axes {
+            let existing = self.axes.entry(name).or_default();
+
+            for binding in bindings {
+                push_unique(existing, binding);
+            }
+        }
+
+        for (name, bindings) in profile.vectors {
+            let existing = self.vectors.entry(name).or_default();
+
+            for binding in bindings {
+                push_unique(existing, binding);
+            }
+        }
+    }
+
+    /// Returns true if any of the action's bindings are currently down.
+    pub fn is_action_down(&self, ctx: &Context, action: &A) -> bool {
+        self.get_bindings(action)
+            .iter()
+            .any(|binding| self.is_binding_down(ctx, *binding))
+    }
+
+    /// Returns true if none of the action's bindings are currently down.
+    pub fn is_action_up(&self, ctx: &Context, action: &A) -> bool {
+        !self.is_action_down(ctx, action)
+    }
+
+    /// Returns true if the action was pressed since the last update.
+    ///
+    /// This will not be the case if another of the action's bindings was already
+    /// being held down.
+    pub fn is_action_pressed(&self, ctx: &Context, action: &A) -> bool {
+        let mut pressed = false;
+
+        for binding in self.get_bindings(action) {
+            for source in self.sources(ctx, *binding) {
+                if source.is_pressed(ctx) {
+                    pressed = true;
+                } else if source.is_down(ctx) {
+                    return false;
+                }
+            }
+        }
+
+        pressed
+    }
+
+    /// Returns true if the action was released since the last update.
+    ///
+    /// This will not be the case if another of the action's bindings is still
+    /// being held down.
+    pub fn is_action_released(&self, ctx: &Context, action: &A) -> bool {
+        let bindings = self.get_bindings(action);
+
+        bindings.iter().any(|binding| {
+            self.sources(ctx, *binding)
+                .any(|source| source.is_released(ctx))
+        }) && !self.is_action_down(ctx, action)
+    }
+
+    /// Returns the current value of an axis, between `-1.0` and `1.0`.
+    ///
+    /// If several of the axis's bindings are active, the one that is furthest from
+    /// zero is used.
+    pub fn get_axis_value(&self, ctx: &Context, axis: &A) -> f32 {
+        let mut value: f32 = 0.0;
+
+        for binding in self.get_axis_bindings(axis) {
+            let binding_value = match *binding {
+                AxisBinding::Buttons { negative, positive } => {
+                    self.button_value(ctx, positive) - self.button_value(ctx, negative)
+                }
+
+                AxisBinding::GamepadAxis(gamepad_axis) => self
+                    .gamepads(ctx)
+                    .map(|id| input::get_gamepad_axis_position(ctx, id, gamepad_axis))
+                    .fold(0.0, furthest_from_zero),
+            };
+
+            value = furthest_from_zero(value, binding_value);
+        }
+
+        value.clamp(-1.0, 1.0)
+    }
+
+    /// Returns the current value of a vector, with a length of at most `1.0`.
+    ///
+    /// If several of the vector's bindings are active, the longest one is used.
+    pub fn get_vector_value(&self, ctx: &Context, vector: &A) -> Vec2<f32> {
+        let mut value = Vec2::zero();
+
+        for binding in self.get_vector_bindings(vector) {
+            let binding_value = match *binding {
+                VectorBinding::Buttons {
+                    up,
+                    down,
+                    left,
+                    right,
+                } => {
+                    let value = Vec2::new(
+                        self.button_value(ctx, right) - self.button_value(ctx, left),
+                        self.button_value(ctx, down) - self.button_value(ctx, up),
+                    );
+
+                    value.try_normalized().unwrap_or_default()
+                }
+
+                VectorBinding::GamepadStick(stick) => self
+                    .gamepads(ctx)
+                    .map(|id| input::get_gamepad_stick_position(ctx, id, stick))
+                    .fold(Vec2::zero(), longest),
+            };
+
+            value = longest(value, binding_value);
+        }
+
+        if value.magnitude_squared() > 1.0 {
+            value.normalized()
+        } else {
+            value
+        }
+    }
+
+    fn is_binding_down(&self, ctx: &Context, binding: Binding) -> bool {
+        self.sources(ctx, binding).any(|source| source.is_down(ctx))
+    }
+
+    fn button_value(&self, ctx: &Context, binding: Binding) -> f32 {
+        if self.is_binding_down(ctx, binding) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the IDs of the gamepads that bindings should be checked against.
+    fn gamepads<'a>(&self, ctx: &'a Context) -> impl Iterator<Item = usize> + 'a {
+        let selected = self.gamepad;
+
+        (0..ctx.input.pads.len())
+            .filter(move |id| selected.is_none() || selected == Some(*id))
+            .filter(move |id| input::is_gamepad_connected(ctx, *id))
+    }
+
+    /// Resolves a binding into the concrete inputs that it refers to.
+    fn sources<'a>(&self, ctx: &'a Context, binding: Binding) -> impl Iterator<Item = Source> + 'a {
+        let (single, gamepad_button) = match binding {
+            Binding::Key(key) => (Some(Source::Key(key)), None),
+            Binding::KeyLabel(label) => {
+                (input::get_key_with_label(ctx, label).map(Source::Key), None)
+            }
+            Binding::MouseButton(button) => (Some(Source::MouseButton(button)), None),
+            Binding::GamepadButton(button) => (None, Some(button)),
+        };
+
+        let gamepads = self
+            .gamepads(ctx)
+            .filter_map(move |id| gamepad_button.map(|button| Source::GamepadButton(id, button)));
+
+        single.into_iter().chain(gamepads)
+    }
+}
+
+impl<A> ActionMap<A>
+where
+    A: Eq + Hash + Clone,
+{
+    /// Creates a new `ActionMap` from the bindings in a profile.
+    pub fn from_profile(profile: BindingProfile<A>) -> ActionMap<A> {
+        let mut map = ActionMap::new();
+        map.load_profile(profile);
+        map
+    }
+
+    /// Copies the bindings in this map into a profile, so that they can be saved.
+    pub fn to_profile(&self) -> BindingProfile<A> {
+        fn entries<A, T>(map: &HashMap<A, Vec<T>>) -> Vec<(A, Vec<T>)>
+        where
+            A: Clone,
+            T: Clone,
+        {
+            map.iter()
+                .filter(|(_, bindings)| !bindings.is_empty())
+                .map(|(name, bindings)| (name.clone(), bindings.clone()))
+                .collect()
+        }
+
+        BindingProfile {
+            actions: entries(&self.actions),
+            axes: entries(&self.axes),
+            vectors: entries(&self.vectors),
+        }
+    }
+}
+
+impl<A> Default for ActionMap<A>
+where
+    A: Eq + Hash,
+{
+    fn default() -> ActionMap<A> {
+        ActionMap::new()
+    }
+}
+
+/// A single concrete input.
+#[derive(Copy, Clone)]
+enum Source {
+    Key(Key),
+    MouseButton(MouseButton),
+    GamepadButton(usize, GamepadButton),
+}
+
+impl Source {
+    fn is_down(self, ctx: &Context) -> bool {
+        match self {
+            Source::Key(key) => input::is_key_down(ctx, key),
+            Source::MouseButton(button) => input::is_mouse_button_down(ctx, button),
+            Source::GamepadButton(id, button) => input::is_gamepad_button_down(ctx, id, button),
+        }
+    }
+
+    fn is_pressed(self, ctx: &Context) -> bool {
+        match self {
+            Source::Key(key) => input::is_key_pressed(ctx, key),
+            Source::MouseButton(button) => input::is_mouse_button_pressed(ctx, button),
+            Source::GamepadButton(id, button) => input::is_gamepad_button_pressed(ctx, id, button),
+        }
+    }
+
+    fn is_released(self, ctx: &Context) -> bool {
+        match self {
+            Source::Key(key) => input::is_key_released(ctx, key),
+            Source::MouseButton(button) => input::is_mouse_button_released(ctx, button),
+            Source::GamepadButton(id, button) => input::is_gamepad_button_released(ctx, id, button),
+        }
+    }
+}
+
+fn push_unique<T>(bindings: &mut Vec<T>, binding: T)
+where
+    T: PartialEq,
+{
+    if !bindings.contains(&binding) {
+        bindings.push(binding);
+    }
+}
+
+fn remove_binding<T>(bindings: Option<&mut Vec<T>>, binding: &T) -> bool
+where
+    T: PartialEq,
+{
+    match bindings {
+        Some(bindings) => {
+            let len = bindings.len();
+            bindings.retain(|b| b != binding);
+            bindings.len() != len
+        }
+
+        None => false,
+    }
+}
+
+fn furthest_from_zero(a: f32, b: f32) -> f32 {
+    if b.abs() > a.abs() {
+        b
+    } else {
+        a
+    }
+}
+
+fn longest(a: Vec2<f32>, b: Vec2<f32>) -> Vec2<f32> {
+    if b.magnitude_squared() > a.magnitude_squared() {
+        b
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{add_gamepad, clear, get_gamepad_mut, set_key_down, set_key_up};
+    use crate::ContextBuilder;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Control {
+        Jump,
+        Move,
+    }
+
+    fn headless_context() -> Context {
+        ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap()
+    }
+
+    fn set_button(ctx: &mut Context, id: usize, button: GamepadButton, down: bool) {
+        let pad = get_gamepad_mut(ctx, id).unwrap();
+
+        if down {
+            pad.set_button_down(button);
+        } else {
+            pad.set_button_up(button);
+        }
+    }
+
+    #[test]
+    fn actions_merge_bindings() {
+        let mut ctx = headless_context();
+        let pad = add_gamepad(&mut ctx, 0);
+
+        let mut actions = ActionMap::new();
+        actions.bind(Control::Jump, Binding::Key(Key::Space));
+        actions.bind(Control::Jump, Binding::GamepadButton(GamepadButton::A));
+
+        set_key_down(&mut ctx, Key::Space);
+        assert!(actions.is_action_pressed(&ctx, &Control::Jump));
+        assert!(actions.is_action_down(&ctx, &Control::Jump));
+        clear(&mut ctx);
+
+        // Pressing a second binding while the first is held shouldn't count as
+        // a new press.
+        set_button(&mut ctx, pad, GamepadButton::A, true);
+        assert!(!actions.is_action_pressed(&ctx, &Control::Jump));
+        clear(&mut ctx);
+
+        set_key_up(&mut ctx, Key::Space);
+        assert!(!actions.is_action_released(&ctx, &Control::Jump));
+        assert!(actions.is_action_down(&ctx, &Control::Jump));
+        clear(&mut ctx);
+
+        set_button(&mut ctx, pad, GamepadButton::A, false);
+        assert!(actions.is_action_released(&ctx, &Control::Jump));
+        assert!(actions.is_action_up(&ctx, &Control::Jump));
+    }
+
+    #[test]
+    fn gamepad_selection() {
+        let mut ctx = headless_context();
+        add_gamepad(&mut ctx, 0);
+        let second = add_gamepad(&mut ctx, 1);
+
+        let mut actions = ActionMap::new();
+        actions.bind(Control::Jump, Binding::GamepadButton(GamepadButton::A));
+
+        set_button(&mut ctx, second, GamepadButton::A, true);
+        assert!(actions.is_action_down(&ctx, &Control::Jump));
+
+        actions.set_gamepad(Some(0));
+        assert!(!actions.is_action_down(&ctx, &Control::Jump));
+    }
+
+    #[test]
+    fn axes_and_vectors() {
+        let mut ctx = headless_context();
+        let pad = add_gamepad(&mut ctx, 0);
+
+        let mut actions = ActionMap::new();
+        actions.bind_axis(
+            Control::Move,
+            AxisBinding::Buttons {
+                negative: Binding::Key(Key::A),
+                positive: Binding::Key(Key::D),
+            },
+        );
+        actions.bind_axis(
+            Control::Move,
+            AxisBinding::GamepadAxis(GamepadAxis::LeftStickX),
+        );
+        actions.bind_vector(Control::Move, VectorBinding::wasd());
+        actions.bind_vector(
+            Control::Move,
+            VectorBinding::GamepadStick(GamepadStick::LeftStick),
+        );
+
+        get_gamepad_mut(&mut ctx, pad)
+            .unwrap()
+            .set_axis_position(GamepadAxis::LeftStickX, -0.5);
+
+        assert_eq!(actions.get_axis_value(&ctx, &Control::Move), -0.5);
+        assert_eq!(
+            actions.get_vector_value(&ctx, &Control::Move),
+            Vec2::new(-0.5, 0.0)
+        );
+
+        set_key_down(&mut ctx, Key::D);
+        set_key_down(&mut ctx, Key::S);
+
+        assert_eq!(actions.get_axis_value(&ctx, &Control::Move), 1.0);
+
+        let diagonal = actions.get_vector_value(&ctx, &Control::Move);
+        assert!((diagonal.magnitude() - 1.0).abs() < 0.0001);
+        assert!(diagonal.x > 0.0 && diagonal.y > 0.0);
+
+        assert!(actions.unbind_vector(&Control::Move, VectorBinding::wasd()));
+        assert_eq!(
+            actions.get_vector_value(&ctx, &Control::Move),
+            Vec2::new(-0.5, 0.0)
+        );
+    }
+
+    #[test]
+    fn profiles_round_trip() {
+        let mut actions = ActionMap::new();
+        actions.bind(Control::Jump, Binding::Key(Key::Space));
+        actions.bind(Control::Jump, Binding::GamepadButton(GamepadButton::A));
+        actions.bind_vector(Control::Move, VectorBinding::wasd());
+        actions.set_gamepad(Some(1));
+
+        let profile = actions.to_profile();
+
+        let loaded = ActionMap::from_profile(profile.clone());
+        assert_eq!(
+            loaded.get_bindings(&Control::Jump),
+            actions.get_bindings(&Control::Jump)
+        );
+        assert_eq!(
+            loaded.get_vector_bindings(&Control::Move),
+            &[VectorBinding::wasd()]
+        );
+        assert_eq!(loaded.get_gamepad(), None);
+
+        actions.unbind(&Control::Jump, Binding::Key(Key::Space));
+        actions.load_profile(profile);
+        assert_eq!(actions.get_bindings(&Control::Jump).len(), 2);
+        assert_eq!(actions.get_gamepad(), Some(1));
+    }
+
+    #[test]
+    fn conflicts() {
+        let mut actions = ActionMap::new();
+        actions.bind(Control::Jump, Binding::Key(Key::W));
+        actions.bind(Control::Jump, Binding::Key(Key::Space));
+        actions.bind_vector(Control::Move, VectorBinding::wasd());
+
+        let conflicts = actions.find_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].binding, Binding::Key(Key::W));
+        assert_eq!(conflicts[0].names.len(), 2);
+        assert!(conflicts[0]
+            .names
+            .contains(&BoundName::Action(&Control::Jump)));
+        assert!(conflicts[0]
+            .names
+            .contains(&BoundName::Vector(&Control::Move)));
+
+        assert_eq!(
+            actions.get_names_bound_to(Binding::Key(Key::Space)),
+            vec![BoundName::Action(&Control::Jump)]
+        );
+
+        actions.unbind(&Control::Jump, Binding::Key(Key::W));
+        assert!(actions.find_conflicts().is_empty());
+    }
+
+    #[test]
+    fn conflicts_between_kinds_with_the_same_name() {
+        let mut actions = ActionMap::new();
+        actions.bind(Control::Move, Binding::Key(Key::W));
+        actions.bind_vector(Control::Move, VectorBinding::wasd());
+
+        assert_eq!(
+            actions.get_names_bound_to(Binding::Key(Key::W)),
+            vec![
+                BoundName::Action(&Control::Move),
+                BoundName::Vector(&Control::Move)
+            ]
+        );
+
+        let conflicts = actions.find_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].binding, Binding::Key(Key::W));
+        assert_eq!(conflicts[0].names[0].name(), &Control::Move);
+    }
+}
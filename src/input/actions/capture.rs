@@ -0,0 +1,334 @@
+use crate::input::{self, GamepadAxis, GamepadButton, Key, MouseButton};
+use crate::Context;
+
+use super::{AxisBinding, Binding};
+
+const AXES: [GamepadAxis; 6] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::LeftTrigger,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+    GamepadAxis::RightTrigger,
+];
+
+/// An input that was detected by a [`BindingCapture`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CapturedInput {
+    /// A key was pressed.
+    Key(Key),
+
+    /// A mouse button was pressed.
+    MouseButton(MouseButton),
+
+    /// A gamepad button was pressed.
+    GamepadButton {
+        /// The ID of the gamepad.
+        gamepad_id: usize,
+
+        /// The button that was pressed.
+        button: GamepadButton,
+    },
+
+    /// A gamepad axis was moved past the capture's threshold.
+    GamepadAxis {
+        /// The ID of the gamepad.
+        gamepad_id: usize,
+
+        /// The axis that was moved.
+        axis: GamepadAxis,
+
+        /// Whether the axis was moved in the positive direction.
+        positive: bool,
+    },
+}
+
+impl CapturedInput {
+    /// Converts the input into a binding for an action.
+    ///
+    /// Gamepad axes are converted into the virtual button for the direction that they
+    /// were moved in (e.g. [`GamepadButton::LeftStickUp`]).
+    pub fn to_binding(self) -> Option<Binding> {
+        match self {
+            CapturedInput::Key(key) => Some(Binding::Key(key)),
+            CapturedInput::MouseButton(button) => Some(Binding::MouseButton(button)),
+            CapturedInput::GamepadButton { button, .. } => Some(Binding::GamepadButton(button)),
+            CapturedInput::GamepadAxis { axis, positive, .. } => {
+                Some(Binding::GamepadButton(axis_button(axis, positive)))
+            }
+        }
+    }
+
+    /// Converts the input into a binding for an axis.
+    ///
+    /// Returns `None` for buttons, as a single button cannot be bound to an axis.
+    pub fn to_axis_binding(self) -> Option<AxisBinding> {
+        match self {
+            CapturedInput::GamepadAxis { axis, .. } => Some(AxisBinding::GamepadAxis(axis)),
+            _ => None,
+        }
+    }
+}
+
+/// Listens for the next input that the player touches, so that it can be bound to an action.
+///
+/// This is intended for use in a controls menu - create a `BindingCapture` when the player
+/// chooses to rebind an action, and then call [`update`](BindingCapture::update) each frame
+/// until it returns an input.
+///
+/// The first update after a capture is created (or [`reset`](BindingCapture::reset)) only
+/// records the current state of the inputs. This means that the button that was used to
+/// start listening will not be captured, and sticks or triggers that are already being held
+/// will be ignored until they are released.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use tetra::Context;
+/// # use tetra::input::Key;
+/// # use tetra::input::actions::{ActionMap, BindingCapture};
+/// # fn update(ctx: &mut Context, actions: &mut ActionMap<&'static str>, capture: &mut BindingCapture) {
+/// if let Some(input) = capture.update(ctx) {
+///     if let Some(binding) = input.to_binding() {
+///         actions.clear_bindings(&"jump");
+///         actions.bind("jump", binding);
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BindingCapture {
+    axis_threshold: f32,
+    gamepad: Option<usize>,
+    started: bool,
+    held_axes: Vec<(usize, GamepadAxis)>,
+}
+
+impl BindingCapture {
+    /// Creates a new `BindingCapture`.
+    pub fn new() -> BindingCapture {
+        BindingCapture {
+            axis_threshold: 0.5,
+            gamepad: None,
+            started: false,
+            held_axes: Vec::new(),
+        }
+    }
+
+    /// Returns how far a gamepad axis has to be moved before it is captured.
+    pub fn get_axis_threshold(&self) -> f32 {
+        self.axis_threshold
+    }
+
+    /// Sets how far a gamepad axis has to be moved before it is captured.
+    ///
+    /// Defaults to `0.5`.
+    pub fn set_axis_threshold(&mut self, threshold: f32) {
+        self.axis_threshold = threshold;
+    }
+
+    /// Returns the gamepad that is being listened to, or `None` if every connected
+    /// gamepad is being listened to.
+    pub fn get_gamepad(&self) -> Option<usize> {
+        self.gamepad
+    }
+
+    /// Sets the gamepad that is being listened to. If this is `None`, every connected
+    /// gamepad will be listened to.
+    ///
+    /// Defaults to `None`.
+    pub fn set_gamepad(&mut self, gamepad_id: Option<usize>) {
+        self.gamepad = gamepad_id;
+    }
+
+    /// Starts listening again from scratch.
+    pub fn reset(&mut self) {
+        self.started = false;
+        self.held_axes.clear();
+    }
+
+    /// Checks for new input, returning the first one that is found.
+    ///
+    /// Once an input has been returned, the capture resets itself, so it can be
+    /// used again to capture another input.
+    pub fn update(&mut self, ctx: &Context) -> Option<CapturedInput> {
+        if !self.started {
+            self.started = true;
+            self.held_axes = self.axes_past_threshold(ctx).collect();
+
+            return None;
+        }
+
+        let captured = self.find_input(ctx);
+
+        if captured.is_some() {
+            self.reset();
+        }
+
+        captured
+    }
+
+    fn find_input(&mut self, ctx: &Context) -> Option<CapturedInput> {
+        if let Some(key) = input::get_keys_pressed(ctx).next() {
+            return Some(CapturedInput::Key(*key));
+        }
+
+        if let Some(button) = ctx.input.mouse_buttons_pressed.iter().next() {
+            return Some(CapturedInput::MouseButton(*button));
+        }
+
+        for gamepad_id in self.gamepads(ctx) {
+            // The virtual buttons for the sticks are skipped, as they are reported
+            // as axes instead.
+            let button = input::get_gamepad_buttons_pressed(ctx, gamepad_id)
+                .find(|button| !is_stick_button(**button));
+
+            if let Some(button) = button {
+                return Some(CapturedInput::GamepadButton {
+                    gamepad_id,
+                    button: *button,
+                });
+            }
+        }
+
+        let active: Vec<_> = self.axes_past_threshold(ctx).collect();
+
+        // Axes that were already held have to return to rest before they can be captured.
+        self.held_axes.retain(|held| active.contains(held));
+
+        let (gamepad_id, axis) = active
+            .into_iter()
+            .find(|axis| !self.held_axes.contains(axis))?;
+
+        Some(CapturedInput::GamepadAxis {
+            gamepad_id,
+            axis,
+            positive: input::get_gamepad_axis_position(ctx, gamepad_id, axis) > 0.0,
+        })
+    }
+
+    fn gamepads<'a>(&self, ctx: &'a Context) -> impl Iterator<Item = usize> + 'a {
+        let selected = self.gamepad;
+
+        (0..ctx.input.pads.len())
+            .filter(move |id| selected.is_none() || selected == Some(*id))
+            .filter(move |id| input::is_gamepad_connected(ctx, *id))
+    }
+
+    fn axes_past_threshold<'a>(
+        &self,
+        ctx: &'a Context,
+    ) -> impl Iterator<Item = (usize, GamepadAxis)> + 'a {
+        let threshold = self.axis_threshold;
+
+        self.gamepads(ctx)
+            .flat_map(|gamepad_id| AXES.iter().map(move |axis| (gamepad_id, *axis)))
+            .filter(move |(gamepad_id, axis)| {
+                input::get_gamepad_axis_position(ctx, *gamepad_id, *axis).abs() >= threshold
+            })
+    }
+}
+
+/// Returns the virtual button that is pressed by moving an axis in the given direction.
+fn axis_button(axis: GamepadAxis, positive: bool) -> GamepadButton {
+    match (axis, positive) {
+        (GamepadAxis::LeftStickX, false) => GamepadButton::LeftStickLeft,
+        (GamepadAxis::LeftStickX, true) => GamepadButton::LeftStickRight,
+        (GamepadAxis::LeftStickY, false) => GamepadButton::LeftStickUp,
+        (GamepadAxis::LeftStickY, true) => GamepadButton::LeftStickDown,
+        (GamepadAxis::RightStickX, false) => GamepadButton::RightStickLeft,
+        (GamepadAxis::RightStickX, true) => GamepadButton::RightStickRight,
+        (GamepadAxis::RightStickY, false) => GamepadButton::RightStickUp,
+        (GamepadAxis::RightStickY, true) => GamepadButton::RightStickDown,
+        (GamepadAxis::LeftTrigger, _) => GamepadButton::LeftTrigger,
+        (GamepadAxis::RightTrigger, _) => GamepadButton::RightTrigger,
+    }
+}
+
+fn is_stick_button(button: GamepadButton) -> bool {
+    matches!(
+        button,
+        GamepadButton::LeftStickUp
+            | GamepadButton::LeftStickDown
+            | GamepadButton::LeftStickLeft
+            | GamepadButton::LeftStickRight
+            | GamepadButton::RightStickUp
+            | GamepadButton::RightStickDown
+            | GamepadButton::RightStickLeft
+            | GamepadButton::RightStickRight
+    )
+}
+
+impl Default for BindingCapture {
+    fn default() -> BindingCapture {
+        BindingCapture::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{add_gamepad, clear, get_gamepad_mut, set_key_down};
+    use crate::ContextBuilder;
+
+    #[test]
+    fn captures_next_input() {
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        let pad = add_gamepad(&mut ctx, 0);
+
+        // Inputs that are active when the capture starts should be ignored.
+        set_key_down(&mut ctx, Key::Enter);
+        get_gamepad_mut(&mut ctx, pad)
+            .unwrap()
+            .set_axis_position(GamepadAxis::LeftStickX, 1.0);
+
+        let mut capture = BindingCapture::new();
+        assert_eq!(capture.update(&ctx), None);
+        clear(&mut ctx);
+
+        assert_eq!(capture.update(&ctx), None);
+
+        get_gamepad_mut(&mut ctx, pad)
+            .unwrap()
+            .set_axis_position(GamepadAxis::LeftStickX, 0.0);
+        assert_eq!(capture.update(&ctx), None);
+
+        get_gamepad_mut(&mut ctx, pad)
+            .unwrap()
+            .set_axis_position(GamepadAxis::LeftStickX, -0.8);
+
+        let captured = capture.update(&ctx);
+        assert_eq!(
+            captured,
+            Some(CapturedInput::GamepadAxis {
+                gamepad_id: pad,
+                axis: GamepadAxis::LeftStickX,
+                positive: false,
+            })
+        );
+        assert_eq!(
+            captured.unwrap().to_axis_binding(),
+            Some(AxisBinding::GamepadAxis(GamepadAxis::LeftStickX))
+        );
+        assert_eq!(
+            captured.unwrap().to_binding(),
+            Some(Binding::GamepadButton(GamepadButton::LeftStickLeft))
+        );
+
+        // After capturing, the capture should start listening again from scratch.
+        set_key_down(&mut ctx, Key::Space);
+        assert_eq!(capture.update(&ctx), None);
+        clear(&mut ctx);
+
+        set_key_down(&mut ctx, Key::Q);
+
+        let captured = capture.update(&ctx);
+        assert_eq!(captured, Some(CapturedInput::Key(Key::Q)));
+        assert_eq!(captured.unwrap().to_binding(), Some(Binding::Key(Key::Q)));
+    }
+}
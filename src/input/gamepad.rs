@@ -1,24 +1,42 @@
+mod labels;
+mod settings;
+
+use std::path::Path;
+
 use hashbrown::{HashMap, HashSet};
 
+use crate::fs;
 use crate::math::Vec2;
-use crate::Context;
+use crate::{Context, Result};
+
+pub use labels::*;
+pub use settings::*;
+
+/// A database of game controller mappings, taken from SDL's built-in copy of
+/// https://github.com/mdqinc/SDL_GameControllerDB. See the header of the file for details.
+#[cfg(any(test, feature = "embedded_gamepad_mappings"))]
+pub(crate) const DEFAULT_GAMEPAD_MAPPINGS: &str = include_str!("gamecontrollerdb.txt");
 
 pub(crate) struct GamepadState {
     pub platform_id: u32,
     pub buttons_down: HashSet<GamepadButton>,
     pub buttons_pressed: HashSet<GamepadButton>,
     pub buttons_released: HashSet<GamepadButton>,
+    pub raw_axis_state: HashMap<GamepadAxis, f32>,
     pub current_axis_state: HashMap<GamepadAxis, f32>,
+    pub settings: GamepadSettings,
 }
 
 impl GamepadState {
-    pub(crate) fn new(platform_id: u32) -> GamepadState {
+    pub(crate) fn new(platform_id: u32, settings: GamepadSettings) -> GamepadState {
         GamepadState {
             platform_id,
             buttons_down: HashSet::new(),
             buttons_pressed: HashSet::new(),
             buttons_released: HashSet::new(),
+            raw_axis_state: HashMap::new(),
             current_axis_state: HashMap::new(),
+            settings,
         }
     }
 
@@ -42,13 +60,152 @@ impl GamepadState {
         was_down
     }
 
+    /// Sets the raw position of an axis, and updates the processed positions of
+    /// any axes that depend on it.
     pub(crate) fn set_axis_position(&mut self, axis: GamepadAxis, value: f32) {
-        self.current_axis_state.insert(axis, value);
+        self.raw_axis_state.insert(axis, value);
+        self.process_axis(axis);
+    }
+
+    /// Re-applies the settings to all of the raw axis positions.
+    pub(crate) fn process_all_axes(&mut self) {
+        for axis in [
+            GamepadAxis::LeftStickX,
+            GamepadAxis::LeftTrigger,
+            GamepadAxis::RightStickX,
+            GamepadAxis::RightTrigger,
+        ] {
+            self.process_axis(axis);
+        }
+    }
+
+    /// Presses or releases the buttons that are driven by the gamepad's axes, returning
+    /// the buttons that changed along with whether they are now down.
+    pub(crate) fn update_virtual_buttons(&mut self) -> Vec<(GamepadButton, bool)> {
+        let mut changes = Vec::new();
+
+        for (button, axis, direction) in VIRTUAL_BUTTONS {
+            let (press_threshold, release_threshold) = match axis {
+                GamepadAxis::LeftStickX | GamepadAxis::LeftStickY => (
+                    self.settings.left_stick.button_threshold,
+                    self.settings.left_stick.button_release_threshold,
+                ),
+                GamepadAxis::RightStickX | GamepadAxis::RightStickY => (
+                    self.settings.right_stick.button_threshold,
+                    self.settings.right_stick.button_release_threshold,
+                ),
+                GamepadAxis::LeftTrigger => (
+                    self.settings.left_trigger.button_threshold,
+                    self.settings.left_trigger.button_release_threshold,
+                ),
+                GamepadAxis::RightTrigger => (
+                    self.settings.right_trigger.button_threshold,
+                    self.settings.right_trigger.button_release_threshold,
+                ),
+            };
+
+            let position = self.get_axis_position(axis) * direction;
+
+            // Using a lower threshold for releasing than for pressing stops the button
+            // from flickering when the axis is held close to the threshold.
+            if self.buttons_down.contains(&button) {
+                if position <= release_threshold {
+                    self.set_button_up(button);
+                    changes.push((button, false));
+                }
+            } else if position > press_threshold {
+                self.set_button_down(button);
+                changes.push((button, true));
+            }
+        }
+
+        changes
+    }
+
+    fn get_axis_position(&self, axis: GamepadAxis) -> f32 {
+        self.current_axis_state.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    fn get_raw_axis_position(&self, axis: GamepadAxis) -> f32 {
+        self.raw_axis_state.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    fn process_axis(&mut self, axis: GamepadAxis) {
+        let (x_axis, y_axis, stick_settings) = match axis {
+            GamepadAxis::LeftStickX | GamepadAxis::LeftStickY => (
+                GamepadAxis::LeftStickX,
+                GamepadAxis::LeftStickY,
+                self.settings.left_stick,
+            ),
+
+            GamepadAxis::RightStickX | GamepadAxis::RightStickY => (
+                GamepadAxis::RightStickX,
+                GamepadAxis::RightStickY,
+                self.settings.right_stick,
+            ),
+
+            GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger => {
+                let trigger_settings = if axis == GamepadAxis::LeftTrigger {
+                    self.settings.left_trigger
+                } else {
+                    self.settings.right_trigger
+                };
+
+                let position = trigger_settings.apply(self.get_raw_axis_position(axis));
+                self.current_axis_state.insert(axis, position);
+
+                return;
+            }
+        };
+
+        // A radial dead zone depends on both axes of the stick, so they are always
+        // processed together.
+        let position = stick_settings.apply(Vec2::new(
+            self.get_raw_axis_position(x_axis),
+            self.get_raw_axis_position(y_axis),
+        ));
+
+        self.current_axis_state.insert(x_axis, position.x);
+        self.current_axis_state.insert(y_axis, position.y);
     }
 }
 
+/// The buttons that are driven by an axis, along with the direction that the axis
+/// has to move in to press them.
+const VIRTUAL_BUTTONS: [(GamepadButton, GamepadAxis, f32); 10] = [
+    (GamepadButton::LeftTrigger, GamepadAxis::LeftTrigger, 1.0),
+    (GamepadButton::RightTrigger, GamepadAxis::RightTrigger, 1.0),
+    (GamepadButton::LeftStickUp, GamepadAxis::LeftStickY, -1.0),
+    (GamepadButton::LeftStickDown, GamepadAxis::LeftStickY, 1.0),
+    (GamepadButton::LeftStickLeft, GamepadAxis::LeftStickX, -1.0),
+    (GamepadButton::LeftStickRight, GamepadAxis::LeftStickX, 1.0),
+    (GamepadButton::RightStickUp, GamepadAxis::RightStickY, -1.0),
+    (GamepadButton::RightStickDown, GamepadAxis::RightStickY, 1.0),
+    (
+        GamepadButton::RightStickLeft,
+        GamepadAxis::RightStickX,
+        -1.0,
+    ),
+    (
+        GamepadButton::RightStickRight,
+        GamepadAxis::RightStickX,
+        1.0,
+    ),
+];
+
 /// A button on a gamepad.
 ///
+/// The face buttons are named after their position on an Xbox controller - for example,
+/// [`GamepadButton::A`] is the bottom face button, even on a PlayStation controller
+/// where that button is labelled 'Cross'. The exception is Nintendo controllers, where
+/// the face buttons are mapped by the label printed on them, so [`GamepadButton::A`] is
+/// the right face button. [`GamepadType::button_label`] can be used to get the label
+/// that the player will see.
+///
+/// As well as the physical buttons, this includes virtual buttons for the triggers
+/// and for each direction of the sticks. These are pressed when the trigger or stick
+/// is moved past a threshold, which can be configured via [`GamepadSettings`].
+///
 /// # Serde
 ///
 /// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
@@ -78,6 +235,30 @@ pub enum GamepadButton {
     Start,
     Back,
     Guide,
+
+    /// The left stick being pushed upwards.
+    LeftStickUp,
+
+    /// The left stick being pushed downwards.
+    LeftStickDown,
+
+    /// The left stick being pushed to the left.
+    LeftStickLeft,
+
+    /// The left stick being pushed to the right.
+    LeftStickRight,
+
+    /// The right stick being pushed upwards.
+    RightStickUp,
+
+    /// The right stick being pushed downwards.
+    RightStickDown,
+
+    /// The right stick being pushed to the left.
+    RightStickLeft,
+
+    /// The right stick being pushed to the right.
+    RightStickRight,
 }
 
 /// An axis of movement on a gamepad.
@@ -246,6 +427,8 @@ pub fn get_gamepad_buttons_released(
 
 /// Returns the current position of the specified gamepad axis.
 ///
+/// The gamepad's [`GamepadSettings`] (e.g. dead zones) will have been applied to this value.
+///
 /// If the gamepad is disconnected, this will always return `0.0`.
 pub fn get_gamepad_axis_position(ctx: &Context, gamepad_id: usize, axis: GamepadAxis) -> f32 {
     if let Some(pad) = get_gamepad(ctx, gamepad_id) {
@@ -261,6 +444,8 @@ pub fn get_gamepad_axis_position(ctx: &Context, gamepad_id: usize, axis: Gamepad
 
 /// Returns the current position of the specified gamepad control stick.
 ///
+/// The gamepad's [`GamepadSettings`] (e.g. dead zones) will have been applied to this value.
+///
 /// If the gamepad is disconnected, this will always return `(0.0, 0.0)`.
 pub fn get_gamepad_stick_position(
     ctx: &Context,
@@ -278,6 +463,141 @@ pub fn get_gamepad_stick_position(
     )
 }
 
+/// Returns the settings that are used to process the specified gamepad's analog inputs.
+///
+/// If the gamepad is disconnected, this will return `None`.
+pub fn get_gamepad_settings(ctx: &Context, gamepad_id: usize) -> Option<GamepadSettings> {
+    get_gamepad(ctx, gamepad_id).map(|pad| pad.settings)
+}
+
+/// Sets the settings that are used to process the specified gamepad's analog inputs.
+///
+/// The new settings are applied to the gamepad's current state immediately, but no events
+/// will be fired as a result. When the gamepad is disconnected, its settings will be reset
+/// to the defaults set via [`set_default_gamepad_settings`].
+///
+/// If the gamepad is disconnected, this will have no effect.
+pub fn set_gamepad_settings(ctx: &mut Context, gamepad_id: usize, settings: GamepadSettings) {
+    if let Some(pad) = get_gamepad_mut(ctx, gamepad_id) {
+        pad.settings = settings;
+        pad.process_all_axes();
+        pad.update_virtual_buttons();
+    }
+}
+
+/// Returns the settings that are used for newly connected gamepads.
+pub fn get_default_gamepad_settings(ctx: &Context) -> GamepadSettings {
+    ctx.input.default_gamepad_settings
+}
+
+/// Sets the settings that are used for newly connected gamepads.
+///
+/// Gamepads that are already connected will not be affected - use [`set_gamepad_settings`]
+/// to change their settings.
+///
+/// Defaults to [`GamepadSettings::default`], which does not apply any dead zones.
+pub fn set_default_gamepad_settings(ctx: &mut Context, settings: GamepadSettings) {
+    ctx.input.default_gamepad_settings = settings;
+}
+
+/// Adds gamepad mappings from a string in the
+/// [SDL_GameControllerDB](https://github.com/mdqinc/SDL_GameControllerDB) format, returning
+/// the number of mappings that were added.
+///
+/// Gamepads will only be recognized by Tetra if there is a mapping for them. SDL has
+/// mappings for most common gamepads built in (and a copy of them can be embedded via the
+/// `embedded_gamepad_mappings` feature, for when an older version of SDL is used), but you
+/// can use this function to support more unusual devices - for example, by loading the
+/// latest version of the community database, or a database that the player provides.
+///
+/// Each line should contain a single mapping. Lines that start with `#`, and mappings
+/// for other platforms, will be ignored. If a gamepad that is already plugged in gains
+/// a mapping, it will be connected, and [`Event::GamepadAdded`](crate::Event::GamepadAdded)
+/// will be fired as usual.
+///
+/// # Errors
+///
+/// * [`TetraError::PlatformError`](crate::TetraError::PlatformError) will be returned if the
+/// mappings could not be added.
+pub fn add_gamepad_mappings_from_str(ctx: &mut Context, mappings: &str) -> Result<usize> {
+    ctx.window.add_gamepad_mappings(mappings)
+}
+
+/// Adds gamepad mappings from a file in the
+/// [SDL_GameControllerDB](https://github.com/mdqinc/SDL_GameControllerDB) format, returning
+/// the number of mappings that were added.
+///
+/// See [`add_gamepad_mappings_from_str`] for more details.
+///
+/// # Errors
+///
+/// * [`TetraError::FailedToLoadAsset`](crate::TetraError::FailedToLoadAsset) will be returned
+/// if the file could not be loaded.
+/// * [`TetraError::PlatformError`](crate::TetraError::PlatformError) will be returned if the
+/// mappings could not be added.
+pub fn add_gamepad_mappings_from_file<P>(ctx: &mut Context, path: P) -> Result<usize>
+where
+    P: AsRef<Path>,
+{
+    let mappings = fs::read_to_string(ctx, path)?;
+    add_gamepad_mappings_from_str(ctx, &mappings)
+}
+
+/// Returns the type of the specified gamepad.
+///
+/// If the gamepad is disconnected, this will return `None`.
+pub fn get_gamepad_type(ctx: &Context, gamepad_id: usize) -> Option<GamepadType> {
+    get_gamepad(ctx, gamepad_id)
+        .map(|g| g.platform_id)
+        .map(|id| ctx.window.get_gamepad_type(id))
+}
+
+/// Returns the GUID of the specified gamepad, as a hex string.
+///
+/// This identifies the model of the gamepad, rather than the individual device - two
+/// identical gamepads will usually have the same GUID. It will stay the same if the
+/// gamepad is disconnected and reconnected, so it can be used to give the gamepad
+/// back to the same player.
+///
+/// If the gamepad is disconnected, or the GUID is not available, this will return `None`.
+pub fn get_gamepad_guid(ctx: &Context, gamepad_id: usize) -> Option<String> {
+    get_gamepad(ctx, gamepad_id)
+        .map(|g| g.platform_id)
+        .and_then(|id| ctx.window.get_gamepad_guid(id))
+}
+
+/// Returns the USB vendor ID of the specified gamepad.
+///
+/// If the gamepad is disconnected, or the vendor ID is not available, this will return `None`.
+pub fn get_gamepad_vendor_id(ctx: &Context, gamepad_id: usize) -> Option<u16> {
+    get_gamepad(ctx, gamepad_id)
+        .map(|g| g.platform_id)
+        .and_then(|id| ctx.window.get_gamepad_vendor_id(id))
+}
+
+/// Returns the USB product ID of the specified gamepad.
+///
+/// If the gamepad is disconnected, or the product ID is not available, this will return `None`.
+pub fn get_gamepad_product_id(ctx: &Context, gamepad_id: usize) -> Option<u16> {
+    get_gamepad(ctx, gamepad_id)
+        .map(|g| g.platform_id)
+        .and_then(|id| ctx.window.get_gamepad_product_id(id))
+}
+
+/// Returns the label that is printed on the specified gamepad for the given button.
+///
+/// This is a shortcut for calling [`GamepadType::button_label`] with the type of the gamepad.
+/// If the gamepad is disconnected, the labels for [`GamepadType::Generic`] will be used.
+pub fn get_gamepad_button_label(
+    ctx: &Context,
+    gamepad_id: usize,
+    button: GamepadButton,
+) -> &'static str {
+    get_gamepad_type(ctx, gamepad_id)
+        .unwrap_or_default()
+        .button_label(button)
+}
+
 /// Returns true if the specified gamepad supports vibration.
 ///
 /// If the gamepad is disconnected, this will always return `false`.
@@ -313,16 +633,20 @@ pub fn stop_gamepad_vibration(ctx: &mut Context, gamepad_id: usize) {
 }
 
 pub(crate) fn add_gamepad(ctx: &mut Context, platform_id: u32) -> usize {
+    let settings = ctx.input.default_gamepad_settings;
+
     for (i, slot) in ctx.input.pads.iter_mut().enumerate() {
         if slot.is_none() {
-            *slot = Some(GamepadState::new(platform_id));
+            *slot = Some(GamepadState::new(platform_id, settings));
             return i;
         }
     }
 
     // There wasn't an existing free slot...
     let i = ctx.input.pads.len();
-    ctx.input.pads.push(Some(GamepadState::new(platform_id)));
+    ctx.input
+        .pads
+        .push(Some(GamepadState::new(platform_id, settings)));
     i
 }
 
@@ -345,3 +669,73 @@ pub(crate) fn get_gamepad_mut(ctx: &mut Context, gamepad_id: usize) -> Option<&m
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContextBuilder, TetraError};
+
+    #[test]
+    fn missing_mapping_file() {
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            add_gamepad_mappings_from_file(&mut ctx, "./does_not_exist.txt"),
+            Err(TetraError::FailedToLoadAsset { .. })
+        ));
+    }
+
+    #[test]
+    fn changing_settings_updates_virtual_buttons() {
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        let id = add_gamepad(&mut ctx, 0);
+        let pad = get_gamepad_mut(&mut ctx, id).unwrap();
+
+        pad.set_axis_position(GamepadAxis::LeftTrigger, 0.5);
+        pad.update_virtual_buttons();
+
+        assert!(is_gamepad_button_down(&ctx, id, GamepadButton::LeftTrigger));
+
+        let mut settings = GamepadSettings::default();
+        settings.left_trigger.button_threshold = 0.8;
+        settings.left_trigger.button_release_threshold = 0.7;
+
+        set_gamepad_settings(&mut ctx, id, settings);
+        assert!(!is_gamepad_button_down(
+            &ctx,
+            id,
+            GamepadButton::LeftTrigger
+        ));
+
+        settings.left_trigger.button_threshold = 0.2;
+        settings.left_trigger.button_release_threshold = 0.1;
+
+        set_gamepad_settings(&mut ctx, id, settings);
+        assert!(is_gamepad_button_down(&ctx, id, GamepadButton::LeftTrigger));
+    }
+
+    #[test]
+    fn embedded_mappings_are_valid() {
+        let mappings: Vec<&str> = DEFAULT_GAMEPAD_MAPPINGS
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+
+        assert!(!mappings.is_empty());
+
+        for mapping in mappings {
+            let guid = mapping.split(',').next().unwrap();
+
+            assert_eq!(guid.len(), 32, "invalid GUID in {}", mapping);
+            assert!(guid.chars().all(|c| c.is_ascii_hexdigit()));
+            assert!(mapping.contains(",platform:"), "no platform in {}", mapping);
+        }
+    }
+}
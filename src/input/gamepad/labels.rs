@@ -0,0 +1,197 @@
+use super::GamepadButton;
+
+/// The type of a gamepad.
+///
+/// This can be used to decide which button prompts to show to the player.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum GamepadType {
+    /// A gamepad that could not be identified, or that doesn't fit into any of the
+    /// other categories.
+    #[default]
+    Generic,
+
+    /// An Xbox 360 controller.
+    Xbox360,
+
+    /// An Xbox One or Xbox Series controller.
+    XboxOne,
+
+    /// A PlayStation 3 controller.
+    PlayStation3,
+
+    /// A PlayStation 4 controller.
+    PlayStation4,
+
+    /// A PlayStation 5 controller.
+    PlayStation5,
+
+    /// A Nintendo Switch Pro Controller.
+    SwitchPro,
+
+    /// A single left Joy-Con.
+    JoyConLeft,
+
+    /// A single right Joy-Con.
+    JoyConRight,
+
+    /// A pair of Joy-Cons, being used as a single gamepad.
+    JoyConPair,
+}
+
+impl GamepadType {
+    /// Returns true if this is an Xbox controller.
+    pub fn is_xbox(self) -> bool {
+        matches!(self, GamepadType::Xbox360 | GamepadType::XboxOne)
+    }
+
+    /// Returns true if this is a PlayStation controller.
+    pub fn is_playstation(self) -> bool {
+        matches!(
+            self,
+            GamepadType::PlayStation3 | GamepadType::PlayStation4 | GamepadType::PlayStation5
+        )
+    }
+
+    /// Returns true if this is a Nintendo controller.
+    pub fn is_nintendo(self) -> bool {
+        matches!(
+            self,
+            GamepadType::SwitchPro
+                | GamepadType::JoyConLeft
+                | GamepadType::JoyConRight
+                | GamepadType::JoyConPair
+        )
+    }
+
+    /// Returns the label that is printed on this type of gamepad for the given button.
+    ///
+    /// As [`GamepadButton`] is mostly based on the position of each button, the label
+    /// will vary between manufacturers - for example, [`GamepadButton::A`] (the bottom
+    /// face button) is labelled 'Cross' on a PlayStation controller. Nintendo controllers
+    /// have their face buttons mapped by label, so these will match the button's name.
+    /// Generic gamepads use the same labels as an Xbox controller.
+    pub fn button_label(self, button: GamepadButton) -> &'static str {
+        if self.is_playstation() {
+            playstation_label(self, button)
+        } else if self.is_nintendo() {
+            nintendo_label(button)
+        } else {
+            xbox_label(self, button)
+        }
+    }
+}
+
+/// The labels for an Xbox controller, which are also used for any buttons that
+/// don't have a more specific label on other gamepads.
+fn xbox_label(gamepad_type: GamepadType, button: GamepadButton) -> &'static str {
+    let is_360 = gamepad_type == GamepadType::Xbox360;
+
+    match button {
+        GamepadButton::A => "A",
+        GamepadButton::B => "B",
+        GamepadButton::X => "X",
+        GamepadButton::Y => "Y",
+        GamepadButton::LeftShoulder => "LB",
+        GamepadButton::LeftTrigger => "LT",
+        GamepadButton::LeftStick => "LS",
+        GamepadButton::RightShoulder => "RB",
+        GamepadButton::RightTrigger => "RT",
+        GamepadButton::RightStick => "RS",
+        GamepadButton::Start if is_360 => "Start",
+        GamepadButton::Start => "Menu",
+        GamepadButton::Back if is_360 => "Back",
+        GamepadButton::Back => "View",
+        GamepadButton::Guide => "Guide",
+        GamepadButton::Up => "Up",
+        GamepadButton::Down => "Down",
+        GamepadButton::Left => "Left",
+        GamepadButton::Right => "Right",
+        GamepadButton::LeftStickUp => "Left Stick Up",
+        GamepadButton::LeftStickDown => "Left Stick Down",
+        GamepadButton::LeftStickLeft => "Left Stick Left",
+        GamepadButton::LeftStickRight => "Left Stick Right",
+        GamepadButton::RightStickUp => "Right Stick Up",
+        GamepadButton::RightStickDown => "Right Stick Down",
+        GamepadButton::RightStickLeft => "Right Stick Left",
+        GamepadButton::RightStickRight => "Right Stick Right",
+    }
+}
+
+fn playstation_label(gamepad_type: GamepadType, button: GamepadButton) -> &'static str {
+    match button {
+        GamepadButton::A => "Cross",
+        GamepadButton::B => "Circle",
+        GamepadButton::X => "Square",
+        GamepadButton::Y => "Triangle",
+        GamepadButton::LeftShoulder => "L1",
+        GamepadButton::LeftTrigger => "L2",
+        GamepadButton::LeftStick => "L3",
+        GamepadButton::RightShoulder => "R1",
+        GamepadButton::RightTrigger => "R2",
+        GamepadButton::RightStick => "R3",
+        GamepadButton::Start if gamepad_type == GamepadType::PlayStation3 => "Start",
+        GamepadButton::Start => "Options",
+        GamepadButton::Back => match gamepad_type {
+            GamepadType::PlayStation3 => "Select",
+            GamepadType::PlayStation4 => "Share",
+            _ => "Create",
+        },
+        GamepadButton::Guide => "PS",
+        _ => xbox_label(gamepad_type, button),
+    }
+}
+
+fn nintendo_label(button: GamepadButton) -> &'static str {
+    match button {
+        GamepadButton::LeftShoulder => "L",
+        GamepadButton::LeftTrigger => "ZL",
+        GamepadButton::LeftStick => "L Stick",
+        GamepadButton::RightShoulder => "R",
+        GamepadButton::RightTrigger => "ZR",
+        GamepadButton::RightStick => "R Stick",
+        GamepadButton::Start => "+",
+        GamepadButton::Back => "-",
+        GamepadButton::Guide => "Home",
+        _ => xbox_label(GamepadType::Generic, button),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_match_button_mapping() {
+        assert_eq!(GamepadType::XboxOne.button_label(GamepadButton::A), "A");
+        assert_eq!(
+            GamepadType::PlayStation5.button_label(GamepadButton::A),
+            "Cross"
+        );
+        assert_eq!(GamepadType::SwitchPro.button_label(GamepadButton::A), "A");
+        assert_eq!(GamepadType::SwitchPro.button_label(GamepadButton::X), "X");
+        assert_eq!(GamepadType::Generic.button_label(GamepadButton::Y), "Y");
+
+        assert_eq!(
+            GamepadType::PlayStation4.button_label(GamepadButton::Back),
+            "Share"
+        );
+        assert_eq!(
+            GamepadType::JoyConPair.button_label(GamepadButton::Start),
+            "+"
+        );
+        assert_eq!(
+            GamepadType::Xbox360.button_label(GamepadButton::LeftStickUp),
+            "Left Stick Up"
+        );
+    }
+}
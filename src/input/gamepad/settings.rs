@@ -0,0 +1,291 @@
+use crate::math::Vec2;
+
+/// How a dead zone is applied to a control stick.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum DeadZoneShape {
+    /// The dead zone is applied to the length of the stick's position, so it is
+    /// circular. This gives smooth movement in all directions, and is usually the
+    /// best choice for sticks that control movement or aiming.
+    #[default]
+    Radial,
+
+    /// The dead zone is applied to each axis separately, so it is cross-shaped.
+    /// This makes it easier to move in exactly one direction (e.g. for menus or
+    /// 2D platformers), at the cost of snapping near the axes.
+    Axial,
+}
+
+/// A curve that is applied to an analog input after its dead zone.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum ResponseCurve {
+    /// The output is proportional to the input.
+    #[default]
+    Linear,
+
+    /// The input is raised to the given power. Values above `1.0` give finer
+    /// control near the center, and values below `1.0` make the input more sensitive.
+    Power(f32),
+}
+
+impl ResponseCurve {
+    /// Applies the curve to a value between `0.0` and `1.0`.
+    pub fn apply(self, value: f32) -> f32 {
+        match self {
+            ResponseCurve::Linear => value,
+            ResponseCurve::Power(exponent) => value.powf(exponent),
+        }
+    }
+}
+
+/// Settings that control how the raw position of a control stick is processed.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct StickSettings {
+    /// The shape of the dead zone.
+    ///
+    /// Defaults to [`DeadZoneShape::Radial`].
+    pub dead_zone_shape: DeadZoneShape,
+
+    /// Positions closer to the center than this will be treated as `0.0`. Increasing this
+    /// prevents worn sticks from drifting - a value of around `0.2` works for most controllers.
+    ///
+    /// Defaults to `0.0`.
+    pub inner_dead_zone: f32,
+
+    /// Positions further from the center than this will be treated as `1.0`. Decreasing
+    /// this allows sticks that can't reach their full range to still be pushed all the way.
+    ///
+    /// Defaults to `1.0`.
+    pub outer_dead_zone: f32,
+
+    /// The curve that is applied to the position after the dead zones.
+    ///
+    /// Defaults to [`ResponseCurve::Linear`].
+    pub response_curve: ResponseCurve,
+
+    /// How far the stick has to be pushed in a direction (after the dead zones and
+    /// response curve have been applied) before the button for that direction
+    /// (e.g. [`GamepadButton::LeftStickUp`](super::GamepadButton::LeftStickUp)) is pressed.
+    ///
+    /// Defaults to `0.5`.
+    pub button_threshold: f32,
+
+    /// How far the stick has to return towards the center before the button for a
+    /// direction is released. This should be lower than `button_threshold`, so that the
+    /// button doesn't flicker on and off when the stick is held near the threshold.
+    ///
+    /// Defaults to `0.4`.
+    pub button_release_threshold: f32,
+}
+
+impl StickSettings {
+    pub(crate) fn apply(&self, raw: Vec2<f32>) -> Vec2<f32> {
+        match self.dead_zone_shape {
+            DeadZoneShape::Radial => {
+                let length = raw.magnitude();
+
+                if length == 0.0 {
+                    return Vec2::zero();
+                }
+
+                raw / length * self.apply_magnitude(length)
+            }
+
+            DeadZoneShape::Axial => Vec2::new(
+                self.apply_magnitude(raw.x.abs()).copysign(raw.x),
+                self.apply_magnitude(raw.y.abs()).copysign(raw.y),
+            ),
+        }
+    }
+
+    fn apply_magnitude(&self, magnitude: f32) -> f32 {
+        self.response_curve.apply(rescale(
+            magnitude,
+            self.inner_dead_zone,
+            self.outer_dead_zone,
+        ))
+    }
+}
+
+impl Default for StickSettings {
+    fn default() -> StickSettings {
+        StickSettings {
+            dead_zone_shape: DeadZoneShape::Radial,
+            inner_dead_zone: 0.0,
+            outer_dead_zone: 1.0,
+            response_curve: ResponseCurve::Linear,
+            button_threshold: 0.5,
+            button_release_threshold: 0.4,
+        }
+    }
+}
+
+/// Settings that control how the raw position of a trigger is processed.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct TriggerSettings {
+    /// Positions below this will be treated as `0.0`.
+    ///
+    /// Defaults to `0.0`.
+    pub inner_dead_zone: f32,
+
+    /// Positions above this will be treated as `1.0`.
+    ///
+    /// Defaults to `1.0`.
+    pub outer_dead_zone: f32,
+
+    /// The curve that is applied to the position after the dead zones.
+    ///
+    /// Defaults to [`ResponseCurve::Linear`].
+    pub response_curve: ResponseCurve,
+
+    /// How far the trigger has to be pulled (after the dead zones and response curve
+    /// have been applied) before it counts as the trigger's button being pressed.
+    ///
+    /// Defaults to `0.0`, meaning that any movement counts as a press.
+    pub button_threshold: f32,
+
+    /// How far the trigger has to be released before the trigger's button is released.
+    /// Setting this lower than `button_threshold` stops the button from flickering on
+    /// and off when the trigger is held near the threshold.
+    ///
+    /// Defaults to `0.0`.
+    pub button_release_threshold: f32,
+}
+
+impl TriggerSettings {
+    pub(crate) fn apply(&self, raw: f32) -> f32 {
+        self.response_curve.apply(rescale(
+            raw.max(0.0),
+            self.inner_dead_zone,
+            self.outer_dead_zone,
+        ))
+    }
+}
+
+impl Default for TriggerSettings {
+    fn default() -> TriggerSettings {
+        TriggerSettings {
+            inner_dead_zone: 0.0,
+            outer_dead_zone: 1.0,
+            response_curve: ResponseCurve::Linear,
+            button_threshold: 0.0,
+            button_release_threshold: 0.0,
+        }
+    }
+}
+
+/// Settings that control how a gamepad's analog inputs are processed.
+///
+/// These are applied before the values are stored or passed to any events, so
+/// functions such as [`get_gamepad_axis_position`](super::get_gamepad_axis_position)
+/// and events such as [`Event::GamepadStickMoved`](crate::Event::GamepadStickMoved)
+/// will always see the processed values.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct GamepadSettings {
+    /// The settings for the left stick.
+    pub left_stick: StickSettings,
+
+    /// The settings for the right stick.
+    pub right_stick: StickSettings,
+
+    /// The settings for the left trigger.
+    pub left_trigger: TriggerSettings,
+
+    /// The settings for the right trigger.
+    pub right_trigger: TriggerSettings,
+}
+
+/// Maps a magnitude between the inner and outer dead zones onto `0.0..=1.0`.
+fn rescale(magnitude: f32, inner: f32, outer: f32) -> f32 {
+    if magnitude <= inner {
+        0.0
+    } else if magnitude >= outer {
+        1.0
+    } else {
+        (magnitude - inner) / (outer - inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stick_dead_zones() {
+        let mut settings = StickSettings {
+            inner_dead_zone: 0.2,
+            outer_dead_zone: 0.9,
+            ..StickSettings::default()
+        };
+
+        assert_eq!(settings.apply(Vec2::new(0.1, -0.1)), Vec2::zero());
+        assert_eq!(settings.apply(Vec2::new(0.0, -0.95)), Vec2::new(0.0, -1.0));
+
+        // The radial dead zone keeps the direction of the stick intact.
+        let diagonal = settings.apply(Vec2::new(0.4, 0.4));
+        assert!((diagonal.x - diagonal.y).abs() < 0.0001);
+
+        // The axial dead zone snaps small offsets onto the axes.
+        settings.dead_zone_shape = DeadZoneShape::Axial;
+        let snapped = settings.apply(Vec2::new(0.55, 0.1));
+        assert!((snapped.x - 0.5).abs() < 0.0001);
+        assert_eq!(snapped.y, 0.0);
+    }
+
+    #[test]
+    fn trigger_curve() {
+        let settings = TriggerSettings {
+            inner_dead_zone: 0.5,
+            response_curve: ResponseCurve::Power(2.0),
+            ..TriggerSettings::default()
+        };
+
+        assert_eq!(settings.apply(0.4), 0.0);
+        assert_eq!(settings.apply(0.75), 0.25);
+        assert_eq!(settings.apply(1.0), 1.0);
+    }
+}
@@ -0,0 +1,787 @@
+//! Functions and types relating to recognizing gestures, such as taps, swipes and pinches.
+//!
+//! [`GestureRecognizer`] watches the mouse and touch state, and reports gestures as the player
+//! performs them. The left mouse button is treated the same as a single touch, so gestures that
+//! only need one pointer (e.g. tapping or dragging) will also work on platforms without a
+//! touchscreen.
+//!
+//! # Examples
+//!
+//! The [`gestures`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/gestures.rs)
+//! example demonstrates how to use gestures to move a [`Camera`](crate::graphics::Camera)
+//! around a map.
+
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use crate::input::{self, MouseButton};
+use crate::math::Vec2;
+use crate::Context;
+
+/// How far back in time a pointer's movement is tracked when calculating its velocity.
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+
+/// The direction of a [`Swipe`](Gesture::Swipe).
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[allow(missing_docs)]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A gesture that was recognized by a [`GestureRecognizer`].
+///
+/// All positions are in window co-ordinates.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Gesture {
+    /// The player tapped (or clicked) without moving.
+    Tap {
+        /// The position of the tap.
+        position: Vec2<f32>,
+    },
+
+    /// The player tapped twice in quick succession, in roughly the same place.
+    ///
+    /// The first tap will have already been reported as a [`Tap`](Gesture::Tap).
+    DoubleTap {
+        /// The position of the second tap.
+        position: Vec2<f32>,
+    },
+
+    /// The player held a pointer down without moving it. No [`Tap`](Gesture::Tap)
+    /// will be reported when it is released.
+    LongPress {
+        /// The position of the pointer.
+        position: Vec2<f32>,
+    },
+
+    /// The player moved a pointer while holding it down.
+    ///
+    /// While two or more touches are active, this tracks the point between the
+    /// first two of them, allowing the player to drag with two fingers while pinching
+    /// or rotating.
+    Drag {
+        /// The new position of the pointer.
+        position: Vec2<f32>,
+
+        /// The movement of the pointer since the last `Drag`.
+        delta: Vec2<f32>,
+    },
+
+    /// The player quickly flicked a pointer in a direction, and then released it.
+    ///
+    /// The movement leading up to the swipe will have already been reported as
+    /// [`Drag`](Gesture::Drag)s.
+    Swipe {
+        /// The direction of the swipe.
+        direction: SwipeDirection,
+
+        /// The velocity of the pointer when it was released, in pixels per second.
+        velocity: Vec2<f32>,
+    },
+
+    /// The player moved two touches closer together or further apart.
+    Pinch {
+        /// The point between the two touches.
+        center: Vec2<f32>,
+
+        /// How much the distance between the touches changed since the last `Pinch`.
+        /// Values above `1.0` mean that the touches moved apart, so this can be
+        /// multiplied into a [`Camera`](crate::graphics::Camera)'s scale to zoom in.
+        scale: f32,
+    },
+
+    /// The player rotated two touches around each other.
+    Rotate {
+        /// The point between the two touches.
+        center: Vec2<f32>,
+
+        /// How far the touches rotated since the last `Rotate`, in radians.
+        /// Positive values are clockwise.
+        angle: f32,
+    },
+}
+
+/// Settings that control how gestures are recognized.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct GestureSettings {
+    /// Whether the left mouse button should be treated as a pointer. The mouse is
+    /// ignored while any touches are active.
+    ///
+    /// Defaults to `true`.
+    pub use_mouse: bool,
+
+    /// How far a pointer can move before it no longer counts as a tap or a long
+    /// press, and starts to count as a drag.
+    ///
+    /// Defaults to `10.0`.
+    pub tap_max_distance: f32,
+
+    /// How long a pointer can be held down for while still counting as a tap.
+    ///
+    /// Defaults to 300 milliseconds.
+    pub tap_max_duration: Duration,
+
+    /// How much time can pass between the two taps of a double tap.
+    ///
+    /// Defaults to 300 milliseconds.
+    pub double_tap_max_interval: Duration,
+
+    /// How far apart the two taps of a double tap can be.
+    ///
+    /// Defaults to `40.0`.
+    pub double_tap_max_distance: f32,
+
+    /// How long a pointer has to be held down for to count as a long press.
+    ///
+    /// Defaults to 500 milliseconds.
+    pub long_press_duration: Duration,
+
+    /// How far a pointer has to move from where it started to count as a swipe.
+    ///
+    /// Defaults to `50.0`.
+    pub swipe_min_distance: f32,
+
+    /// How fast a pointer has to be moving when it is released to count as a swipe,
+    /// in pixels per second.
+    ///
+    /// Defaults to `500.0`.
+    pub swipe_min_velocity: f32,
+
+    /// How much the distance between two touches has to change before pinches
+    /// start being reported.
+    ///
+    /// Defaults to `10.0`.
+    pub pinch_min_distance: f32,
+
+    /// How far two touches have to rotate around each other (in radians) before
+    /// rotations start being reported.
+    ///
+    /// Defaults to `0.1`.
+    pub rotate_min_angle: f32,
+}
+
+impl Default for GestureSettings {
+    fn default() -> GestureSettings {
+        GestureSettings {
+            use_mouse: true,
+            tap_max_distance: 10.0,
+            tap_max_duration: Duration::from_millis(300),
+            double_tap_max_interval: Duration::from_millis(300),
+            double_tap_max_distance: 40.0,
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 50.0,
+            swipe_min_velocity: 500.0,
+            pinch_min_distance: 10.0,
+            rotate_min_angle: 0.1,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum PointerId {
+    Mouse,
+    Touch(u64),
+}
+
+type PointerPositions = Vec<(PointerId, Vec2<f32>)>;
+
+#[derive(Debug, Clone)]
+struct Pointer {
+    id: PointerId,
+    start_position: Vec2<f32>,
+    start_time: Duration,
+    position: Vec2<f32>,
+
+    /// The position that the last `Drag` was reported from.
+    drag_position: Vec2<f32>,
+
+    /// Recent positions, used to calculate the velocity.
+    samples: Vec<(Duration, Vec2<f32>)>,
+
+    moved: bool,
+    long_pressed: bool,
+
+    /// Whether this pointer has been down at the same time as another, which stops
+    /// it from counting as a tap, long press or swipe.
+    multi: bool,
+}
+
+impl Pointer {
+    fn velocity(&self) -> Vec2<f32> {
+        match (self.samples.first(), self.samples.last()) {
+            (Some(&(start_time, start)), Some(&(end_time, end))) if end_time > start_time => {
+                (end - start) / (end_time - start_time).as_secs_f32()
+            }
+            _ => Vec2::zero(),
+        }
+    }
+}
+
+/// The state of a two-touch gesture.
+#[derive(Debug, Clone)]
+struct Pair {
+    ids: (PointerId, PointerId),
+    center: Vec2<f32>,
+    distance: f32,
+    angle: f32,
+    pinching: bool,
+    rotating: bool,
+}
+
+/// Recognizes gestures from the mouse and touch input.
+///
+/// [`update`](GestureRecognizer::update) should be called once per update, and returns
+/// the gestures that were recognized since the last call.
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    settings: GestureSettings,
+    time: Duration,
+    pointers: Vec<Pointer>,
+    pair: Option<Pair>,
+    last_tap: Option<(Duration, Vec2<f32>)>,
+}
+
+impl GestureRecognizer {
+    /// Creates a new `GestureRecognizer`, with the default settings.
+    pub fn new() -> GestureRecognizer {
+        GestureRecognizer::default()
+    }
+
+    /// Creates a new `GestureRecognizer`, with the given settings.
+    pub fn with_settings(settings: GestureSettings) -> GestureRecognizer {
+        GestureRecognizer {
+            settings,
+            ..GestureRecognizer::default()
+        }
+    }
+
+    /// Returns the settings that are used to recognize gestures.
+    pub fn get_settings(&self) -> GestureSettings {
+        self.settings
+    }
+
+    /// Sets the settings that are used to recognize gestures.
+    pub fn set_settings(&mut self, settings: GestureSettings) {
+        self.settings = settings;
+    }
+
+    /// Forgets about any gestures that are in progress.
+    ///
+    /// Pointers that are currently held down will be treated as if they had just been pressed.
+    pub fn reset(&mut self) {
+        self.pointers.clear();
+        self.pair = None;
+        self.last_tap = None;
+    }
+
+    /// Checks the mouse and touch state for gestures, returning the ones that were
+    /// recognized since the last update.
+    ///
+    /// This should be called once per update.
+    ///
+    /// Gestures are timed in real time, so the durations in the [`GestureSettings`]
+    /// are not affected by the [time scale](crate::time::set_time_scale).
+    pub fn update(&mut self, ctx: &Context) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+
+        self.time = ctx.time.unscaled_time;
+
+        let (active, ended) = self.read_pointers(ctx);
+
+        for &(id, position) in active.iter().chain(&ended) {
+            match self.pointers.iter_mut().find(|p| p.id == id) {
+                Some(pointer) => {
+                    pointer.position = position;
+
+                    if pointer.start_position.distance(position) > self.settings.tap_max_distance {
+                        pointer.moved = true;
+                    }
+                }
+
+                None => self.pointers.push(Pointer {
+                    id,
+                    start_position: position,
+                    start_time: self.time,
+                    position,
+                    drag_position: position,
+                    samples: Vec::new(),
+                    moved: false,
+                    long_pressed: false,
+                    multi: false,
+                }),
+            }
+        }
+
+        let time = self.time;
+
+        for pointer in &mut self.pointers {
+            pointer
+                .samples
+                .retain(|&(sample_time, _)| time - sample_time <= VELOCITY_WINDOW);
+
+            pointer.samples.push((time, pointer.position));
+        }
+
+        if self.pointers.len() > 1 {
+            for pointer in &mut self.pointers {
+                pointer.multi = true;
+            }
+
+            self.recognize_pair(&mut gestures);
+        } else {
+            self.pair = None;
+
+            if let Some(pointer) = self.pointers.first_mut() {
+                recognize_single(pointer, &self.settings, time, &mut gestures);
+            }
+        }
+
+        for &(id, _) in &ended {
+            if let Some(index) = self.pointers.iter().position(|p| p.id == id) {
+                let pointer = self.pointers.remove(index);
+                self.recognize_release(&pointer, &mut gestures);
+            }
+        }
+
+        // Pointers can also disappear without being released (e.g. when input playback
+        // stops), in which case they shouldn't trigger anything.
+        self.pointers
+            .retain(|p| active.iter().any(|&(id, _)| id == p.id));
+
+        gestures
+    }
+
+    fn read_pointers(&self, ctx: &Context) -> (PointerPositions, PointerPositions) {
+        let touches = input::get_touches(ctx);
+        let ended_touches = input::get_ended_touches(ctx);
+
+        // While touches are being used, the mouse is ignored - otherwise, the mouse
+        // events that are emulated from the primary touch would be counted twice.
+        let using_touch = !touches.is_empty()
+            || !ended_touches.is_empty()
+            || self
+                .pointers
+                .iter()
+                .any(|p| matches!(p.id, PointerId::Touch(_)));
+
+        if using_touch {
+            return (
+                touches
+                    .iter()
+                    .map(|t| (PointerId::Touch(t.id), t.position))
+                    .collect(),
+                ended_touches
+                    .iter()
+                    .map(|t| (PointerId::Touch(t.id), t.position))
+                    .collect(),
+            );
+        }
+
+        let mut active = Vec::new();
+        let mut ended = Vec::new();
+
+        if self.settings.use_mouse {
+            let position = input::get_mouse_position(ctx);
+
+            if input::is_mouse_button_down(ctx, MouseButton::Left) {
+                active.push((PointerId::Mouse, position));
+            }
+
+            if input::is_mouse_button_released(ctx, MouseButton::Left) {
+                ended.push((PointerId::Mouse, position));
+            }
+        }
+
+        (active, ended)
+    }
+
+    fn recognize_pair(&mut self, gestures: &mut Vec<Gesture>) {
+        let (first, second) = (&self.pointers[0], &self.pointers[1]);
+        let ids = (first.id, second.id);
+
+        let center = (first.position + second.position) / 2.0;
+        let offset = second.position - first.position;
+        let distance = offset.magnitude();
+        let angle = offset.y.atan2(offset.x);
+
+        for pointer in &mut self.pointers {
+            pointer.drag_position = pointer.position;
+        }
+
+        let pair = match &mut self.pair {
+            Some(pair) if pair.ids == ids => pair,
+
+            _ => {
+                self.pair = Some(Pair {
+                    ids,
+                    center,
+                    distance,
+                    angle,
+                    pinching: false,
+                    rotating: false,
+                });
+
+                return;
+            }
+        };
+
+        if center != pair.center {
+            gestures.push(Gesture::Drag {
+                position: center,
+                delta: center - pair.center,
+            });
+
+            pair.center = center;
+        }
+
+        // The distance and angle are only updated once the thresholds have been passed,
+        // so that the first pinch or rotation includes the movement that it took to
+        // get past them.
+        if !pair.pinching && (distance - pair.distance).abs() >= self.settings.pinch_min_distance {
+            pair.pinching = true;
+        }
+
+        if pair.pinching && distance != pair.distance && pair.distance > 0.0 {
+            gestures.push(Gesture::Pinch {
+                center,
+                scale: distance / pair.distance,
+            });
+
+            pair.distance = distance;
+        }
+
+        let rotation = wrap_angle(angle - pair.angle);
+
+        if !pair.rotating && rotation.abs() >= self.settings.rotate_min_angle {
+            pair.rotating = true;
+        }
+
+        if pair.rotating && rotation != 0.0 {
+            gestures.push(Gesture::Rotate {
+                center,
+                angle: rotation,
+            });
+
+            pair.angle = angle;
+        }
+    }
+
+    fn recognize_release(&mut self, pointer: &Pointer, gestures: &mut Vec<Gesture>) {
+        if pointer.multi || pointer.long_pressed {
+            return;
+        }
+
+        let position = pointer.position;
+
+        if !pointer.moved {
+            if self.time - pointer.start_time > self.settings.tap_max_duration {
+                return;
+            }
+
+            let is_double_tap = match self.last_tap {
+                Some((time, last_position)) => {
+                    pointer.start_time.saturating_sub(time) <= self.settings.double_tap_max_interval
+                        && last_position.distance(position) <= self.settings.double_tap_max_distance
+                }
+
+                None => false,
+            };
+
+            if is_double_tap {
+                gestures.push(Gesture::DoubleTap { position });
+                self.last_tap = None;
+            } else {
+                gestures.push(Gesture::Tap { position });
+                self.last_tap = Some((self.time, position));
+            }
+
+            return;
+        }
+
+        let velocity = pointer.velocity();
+
+        if pointer.start_position.distance(position) >= self.settings.swipe_min_distance
+            && velocity.magnitude() >= self.settings.swipe_min_velocity
+        {
+            let direction = if velocity.x.abs() >= velocity.y.abs() {
+                if velocity.x > 0.0 {
+                    SwipeDirection::Right
+                } else {
+                    SwipeDirection::Left
+                }
+            } else if velocity.y > 0.0 {
+                SwipeDirection::Down
+            } else {
+                SwipeDirection::Up
+            };
+
+            gestures.push(Gesture::Swipe {
+                direction,
+                velocity,
+            });
+        }
+    }
+}
+
+fn recognize_single(
+    pointer: &mut Pointer,
+    settings: &GestureSettings,
+    time: Duration,
+    gestures: &mut Vec<Gesture>,
+) {
+    if pointer.moved || pointer.multi {
+        if pointer.position != pointer.drag_position {
+            gestures.push(Gesture::Drag {
+                position: pointer.position,
+                delta: pointer.position - pointer.drag_position,
+            });
+
+            pointer.drag_position = pointer.position;
+        }
+    } else if !pointer.long_pressed && time - pointer.start_time >= settings.long_press_duration {
+        gestures.push(Gesture::LongPress {
+            position: pointer.position,
+        });
+
+        pointer.long_pressed = true;
+    }
+}
+
+/// Wraps an angle into the range `-PI..=PI`.
+fn wrap_angle(angle: f32) -> f32 {
+    let angle = angle % (2.0 * PI);
+
+    if angle > PI {
+        angle - 2.0 * PI
+    } else if angle < -PI {
+        angle + 2.0 * PI
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::InputEvent;
+    use crate::time::{self, Timestep};
+    use crate::{ContextBuilder, Result, State};
+
+    struct GestureState {
+        gestures: GestureRecognizer,
+        log: Vec<Gesture>,
+    }
+
+    impl State for GestureState {
+        fn update(&mut self, ctx: &mut Context) -> Result {
+            self.log.extend(self.gestures.update(ctx));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn gestures_are_recognized() {
+        let mut ctx = ContextBuilder::new("Test", 640, 480)
+            .headless(true)
+            .timestep(Timestep::Variable)
+            .build()
+            .unwrap();
+
+        let mut state = GestureState {
+            gestures: GestureRecognizer::new(),
+            log: Vec::new(),
+        };
+
+        let mut tick = |events: Vec<InputEvent>| {
+            for event in events {
+                ctx.window.push_input_event(event);
+            }
+
+            state.log.clear();
+            ctx.tick(&mut state, Duration::from_millis(16)).unwrap();
+            std::mem::take(&mut state.log)
+        };
+
+        let start = |id, x, y| InputEvent::TouchStarted {
+            id,
+            position: Vec2::new(x, y),
+            pressure: 1.0,
+        };
+
+        let moved = |id, x, y| InputEvent::TouchMoved {
+            id,
+            position: Vec2::new(x, y),
+            pressure: 1.0,
+        };
+
+        let end = |id, x, y| InputEvent::TouchEnded {
+            id,
+            position: Vec2::new(x, y),
+        };
+
+        // The mouse events emulated from the touch shouldn't cause a second tap.
+        tick(vec![start(1, 10.0, 10.0)]);
+        assert_eq!(
+            tick(vec![end(1, 12.0, 10.0)]),
+            vec![Gesture::Tap {
+                position: Vec2::new(12.0, 10.0)
+            }]
+        );
+
+        assert_eq!(
+            tick(vec![start(1, 14.0, 10.0), end(1, 14.0, 10.0)]),
+            vec![Gesture::DoubleTap {
+                position: Vec2::new(14.0, 10.0)
+            }]
+        );
+
+        tick(vec![start(1, 100.0, 100.0)]);
+
+        let held: Vec<_> = (0..40).flat_map(|_| tick(vec![])).collect();
+        assert_eq!(
+            held,
+            vec![Gesture::LongPress {
+                position: Vec2::new(100.0, 100.0)
+            }]
+        );
+
+        assert!(tick(vec![end(1, 100.0, 100.0)]).is_empty());
+
+        // The first drag should include the distance it took to start dragging.
+        tick(vec![start(1, 0.0, 0.0)]);
+        assert_eq!(
+            tick(vec![moved(1, 60.0, 0.0)]),
+            vec![Gesture::Drag {
+                position: Vec2::new(60.0, 0.0),
+                delta: Vec2::new(60.0, 0.0),
+            }]
+        );
+        tick(vec![moved(1, 120.0, 0.0)]);
+
+        match tick(vec![end(1, 120.0, 0.0)])[..] {
+            [Gesture::Swipe {
+                direction: SwipeDirection::Right,
+                velocity,
+            }] => assert!(velocity.x > 2000.0),
+            ref other => panic!("expected a swipe, got {:?}", other),
+        }
+
+        tick(vec![start(1, 100.0, 100.0), start(2, 200.0, 100.0)]);
+        assert_eq!(
+            tick(vec![moved(2, 300.0, 100.0)]),
+            vec![
+                Gesture::Drag {
+                    position: Vec2::new(200.0, 100.0),
+                    delta: Vec2::new(50.0, 0.0),
+                },
+                Gesture::Pinch {
+                    center: Vec2::new(200.0, 100.0),
+                    scale: 2.0,
+                },
+            ]
+        );
+
+        match tick(vec![moved(2, 100.0, 300.0)])[..] {
+            [Gesture::Drag { .. }, Gesture::Rotate { angle, .. }] => {
+                assert!((angle - PI / 2.0).abs() < 0.001)
+            }
+            ref other => panic!("expected a rotation, got {:?}", other),
+        }
+
+        // Lifting the fingers after a pinch shouldn't count as a tap.
+        assert!(tick(vec![end(1, 100.0, 100.0), end(2, 100.0, 300.0)]).is_empty());
+    }
+
+    #[test]
+    fn gestures_ignore_time_scale() {
+        let mut ctx = ContextBuilder::new("Test", 640, 480)
+            .headless(true)
+            .timestep(Timestep::Variable)
+            .build()
+            .unwrap();
+
+        let mut state = GestureState {
+            gestures: GestureRecognizer::new(),
+            log: Vec::new(),
+        };
+
+        let mut tick = |ctx: &mut Context, events: Vec<InputEvent>| {
+            for event in events {
+                ctx.window.push_input_event(event);
+            }
+
+            state.log.clear();
+            ctx.tick(&mut state, Duration::from_millis(16)).unwrap();
+            std::mem::take(&mut state.log)
+        };
+
+        let start = InputEvent::TouchStarted {
+            id: 1,
+            position: Vec2::new(10.0, 10.0),
+            pressure: 1.0,
+        };
+
+        let end = InputEvent::TouchEnded {
+            id: 1,
+            position: Vec2::new(10.0, 10.0),
+        };
+
+        // At four times speed, this tap takes 320ms of game time, but only 80ms of
+        // real time.
+        time::set_time_scale(&mut ctx, 4.0);
+
+        tick(&mut ctx, vec![start.clone()]);
+
+        for _ in 0..3 {
+            tick(&mut ctx, vec![]);
+        }
+
+        assert_eq!(
+            tick(&mut ctx, vec![end.clone()]),
+            vec![Gesture::Tap {
+                position: Vec2::new(10.0, 10.0)
+            }]
+        );
+
+        // In slow motion, a long press should still take the same amount of real time.
+        time::set_time_scale(&mut ctx, 0.1);
+
+        for _ in 0..30 {
+            tick(&mut ctx, vec![]);
+        }
+
+        tick(&mut ctx, vec![start]);
+
+        let held: Vec<_> = (0..40).flat_map(|_| tick(&mut ctx, vec![])).collect();
+        assert_eq!(
+            held,
+            vec![Gesture::LongPress {
+                position: Vec2::new(10.0, 10.0)
+            }]
+        );
+
+        tick(&mut ctx, vec![end]);
+    }
+}
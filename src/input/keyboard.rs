@@ -491,7 +491,7 @@ impl Display for KeyModifier {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
 pub(crate) struct KeyModifierState {
     pub ctrl: bool,
     pub alt: bool,
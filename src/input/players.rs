@@ -0,0 +1,383 @@
+//! Functions and types relating to assigning input devices to players, for local multiplayer.
+//!
+//! Gamepad IDs are not a good way of identifying players, as they can be reused when a
+//! gamepad is disconnected and another one is connected. [`PlayerSlots`] keeps track of
+//! which device belongs to which player instead, allowing players to join by pressing a
+//! button, and giving a player their slot back when their gamepad reconnects.
+//!
+//! # Examples
+//!
+//! The [`players`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/players.rs)
+//! example demonstrates how to let players join a local multiplayer game.
+
+use crate::input::{self, GamepadButton, Key};
+use crate::{Context, Event};
+
+/// A device that can be assigned to a player.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum InputDevice {
+    /// The keyboard and mouse, treated as a single device.
+    Keyboard,
+
+    /// The gamepad with the given ID.
+    Gamepad(usize),
+}
+
+/// A change to the players in a [`PlayerSlots`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PlayerEvent {
+    /// A player joined the game.
+    Joined {
+        /// The player's slot.
+        player: usize,
+
+        /// The device that the player joined with.
+        device: InputDevice,
+    },
+
+    /// A player's gamepad was disconnected.
+    ///
+    /// The player keeps their slot until they are removed via
+    /// [`PlayerSlots::remove_player`], so that they can rejoin if their
+    /// gamepad reconnects.
+    Left {
+        /// The player's slot.
+        player: usize,
+
+        /// The device that was disconnected.
+        device: InputDevice,
+    },
+
+    /// A player's gamepad was reconnected, after they had left.
+    Rejoined {
+        /// The player's slot.
+        player: usize,
+
+        /// The device that the player rejoined with.
+        device: InputDevice,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    device: Option<InputDevice>,
+    guid: Option<String>,
+}
+
+/// Assigns input devices to a fixed number of player slots.
+///
+/// Players join by pressing the join button on their gamepad (or the join key on the
+/// keyboard), and are given the first free slot. Each device can only be assigned to
+/// one player at a time.
+///
+/// To keep track of connections, the [`GamepadAdded`](Event::GamepadAdded) and
+/// [`GamepadRemoved`](Event::GamepadRemoved) events should be passed to
+/// [`event`](PlayerSlots::event). When a player's gamepad is disconnected, they keep
+/// their slot, and the next gamepad of the same model (as identified by
+/// [`get_gamepad_guid`](input::get_gamepad_guid)) that connects will be given back to them.
+#[derive(Debug, Clone)]
+pub struct PlayerSlots {
+    slots: Vec<Option<Slot>>,
+    accepting_joins: bool,
+    join_key: Option<Key>,
+    join_button: Option<GamepadButton>,
+}
+
+impl PlayerSlots {
+    /// Creates a new `PlayerSlots`, with room for the given number of players.
+    pub fn new(max_players: usize) -> PlayerSlots {
+        PlayerSlots {
+            slots: vec![None; max_players],
+            accepting_joins: true,
+            join_key: Some(Key::Enter),
+            join_button: Some(GamepadButton::Start),
+        }
+    }
+
+    /// Returns the maximum number of players.
+    pub fn max_players(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of players that have joined, including players whose
+    /// gamepad is currently disconnected.
+    pub fn player_count(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Returns an iterator of the slots that players have joined.
+    pub fn players(&self) -> impl Iterator<Item = usize> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.is_some())
+            .map(|(player, _)| player)
+    }
+
+    /// Returns whether new players are currently able to join.
+    pub fn is_accepting_joins(&self) -> bool {
+        self.accepting_joins
+    }
+
+    /// Sets whether new players are able to join. This can be used to stop players
+    /// from joining once the game has started - players who have left will still
+    /// be able to rejoin.
+    ///
+    /// Defaults to `true`.
+    pub fn set_accepting_joins(&mut self, accepting_joins: bool) {
+        self.accepting_joins = accepting_joins;
+    }
+
+    /// Returns the key that is used to join via the keyboard.
+    pub fn get_join_key(&self) -> Option<Key> {
+        self.join_key
+    }
+
+    /// Sets the key that is used to join via the keyboard. If this is `None`, players
+    /// will not be able to join via the keyboard.
+    ///
+    /// Defaults to `Some(Key::Enter)`.
+    pub fn set_join_key(&mut self, key: Option<Key>) {
+        self.join_key = key;
+    }
+
+    /// Returns the button that is used to join via a gamepad.
+    pub fn get_join_button(&self) -> Option<GamepadButton> {
+        self.join_button
+    }
+
+    /// Sets the button that is used to join via a gamepad. If this is `None`, players
+    /// will not be able to join via a gamepad.
+    ///
+    /// Defaults to `Some(GamepadButton::Start)`.
+    pub fn set_join_button(&mut self, button: Option<GamepadButton>) {
+        self.join_button = button;
+    }
+
+    /// Returns the device that is assigned to a player.
+    ///
+    /// If the player has not joined, or their gamepad is disconnected, this will
+    /// return `None`.
+    pub fn get_device(&self, player: usize) -> Option<InputDevice> {
+        self.slots
+            .get(player)
+            .and_then(|slot| slot.as_ref())
+            .and_then(|slot| slot.device)
+    }
+
+    /// Returns the player that a device is assigned to, if any.
+    pub fn get_player(&self, device: InputDevice) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|slot| matches!(slot, Some(slot) if slot.device == Some(device)))
+    }
+
+    /// Returns true if a player has joined the given slot.
+    pub fn is_joined(&self, player: usize) -> bool {
+        matches!(self.slots.get(player), Some(Some(_)))
+    }
+
+    /// Assigns a device to the first free slot, returning the slot.
+    ///
+    /// This ignores whether the `PlayerSlots` is accepting joins, so it can be used to
+    /// add players from your own code (e.g. to add a player for the keyboard automatically).
+    /// If there are no free slots, or the device is already assigned, this will return `None`.
+    pub fn join(&mut self, ctx: &Context, device: InputDevice) -> Option<usize> {
+        if self.get_player(device).is_some() {
+            return None;
+        }
+
+        let player = self.slots.iter().position(|slot| slot.is_none())?;
+
+        self.slots[player] = Some(Slot {
+            device: Some(device),
+            guid: match device {
+                InputDevice::Keyboard => None,
+                InputDevice::Gamepad(id) => input::get_gamepad_guid(ctx, id),
+            },
+        });
+
+        Some(player)
+    }
+
+    /// Removes a player, freeing up their slot. Returns `true` if the player had joined.
+    pub fn remove_player(&mut self, player: usize) -> bool {
+        match self.slots.get_mut(player) {
+            Some(slot) => slot.take().is_some(),
+            None => false,
+        }
+    }
+
+    /// Removes all players.
+    pub fn clear(&mut self) {
+        for slot in &mut self.slots {
+            *slot = None;
+        }
+    }
+
+    /// Checks whether any unassigned devices have pressed the join key or button, and
+    /// assigns them to players if so.
+    ///
+    /// This should be called once per update.
+    pub fn update(&mut self, ctx: &Context) -> Vec<PlayerEvent> {
+        let mut events = Vec::new();
+
+        if !self.accepting_joins {
+            return events;
+        }
+
+        if let Some(key) = self.join_key {
+            if input::is_key_pressed(ctx, key) {
+                self.try_join(ctx, InputDevice::Keyboard, &mut events);
+            }
+        }
+
+        if let Some(button) = self.join_button {
+            for id in 0..ctx.input.pads.len() {
+                if input::is_gamepad_button_pressed(ctx, id, button) {
+                    self.try_join(ctx, InputDevice::Gamepad(id), &mut events);
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Updates the players when a gamepad is connected or disconnected.
+    ///
+    /// This should be called from [`State::event`](crate::State::event) for every event -
+    /// events other than [`GamepadAdded`](Event::GamepadAdded) and
+    /// [`GamepadRemoved`](Event::GamepadRemoved) will be ignored.
+    pub fn event(&mut self, ctx: &Context, event: &Event) -> Option<PlayerEvent> {
+        match *event {
+            Event::GamepadRemoved { id } => {
+                let device = InputDevice::Gamepad(id);
+                let player = self.get_player(device)?;
+
+                if let Some(slot) = &mut self.slots[player] {
+                    slot.device = None;
+                }
+
+                Some(PlayerEvent::Left { player, device })
+            }
+
+            Event::GamepadAdded { id } => {
+                let device = InputDevice::Gamepad(id);
+                let guid = input::get_gamepad_guid(ctx, id)?;
+
+                let player = self.slots.iter().position(|slot| match slot {
+                    Some(slot) => slot.device.is_none() && slot.guid.as_ref() == Some(&guid),
+                    None => false,
+                })?;
+
+                if let Some(slot) = &mut self.slots[player] {
+                    slot.device = Some(device);
+                }
+
+                Some(PlayerEvent::Rejoined { player, device })
+            }
+
+            _ => None,
+        }
+    }
+
+    fn try_join(&mut self, ctx: &Context, device: InputDevice, events: &mut Vec<PlayerEvent>) {
+        if let Some(player) = self.join(ctx, device) {
+            events.push(PlayerEvent::Joined { player, device });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{add_gamepad, clear, get_gamepad_mut, remove_gamepad, set_key_down};
+    use crate::ContextBuilder;
+
+    fn press_start(ctx: &mut Context, id: usize) {
+        get_gamepad_mut(ctx, id)
+            .unwrap()
+            .set_button_down(GamepadButton::Start);
+    }
+
+    #[test]
+    fn players_join_and_rejoin() {
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        let mut players = PlayerSlots::new(2);
+
+        let first = add_gamepad(&mut ctx, 10);
+        let second = add_gamepad(&mut ctx, 20);
+
+        press_start(&mut ctx, second);
+        set_key_down(&mut ctx, Key::Enter);
+
+        assert_eq!(
+            players.update(&ctx),
+            vec![
+                PlayerEvent::Joined {
+                    player: 0,
+                    device: InputDevice::Keyboard
+                },
+                PlayerEvent::Joined {
+                    player: 1,
+                    device: InputDevice::Gamepad(second)
+                },
+            ]
+        );
+
+        clear(&mut ctx);
+
+        // The game is full, so the first gamepad can't join.
+        press_start(&mut ctx, first);
+        assert!(players.update(&ctx).is_empty());
+        assert_eq!(players.get_player(InputDevice::Gamepad(first)), None);
+
+        // When the second gamepad is unplugged, its player should keep their slot...
+        remove_gamepad(&mut ctx, second);
+        assert_eq!(
+            players.event(&ctx, &Event::GamepadRemoved { id: second }),
+            Some(PlayerEvent::Left {
+                player: 1,
+                device: InputDevice::Gamepad(second)
+            })
+        );
+        assert!(players.is_joined(1));
+        assert_eq!(players.get_device(1), None);
+
+        // ...even if a different gamepad is given the same ID...
+        let other = add_gamepad(&mut ctx, 30);
+        assert_eq!(other, second);
+        assert_eq!(
+            players.event(&ctx, &Event::GamepadAdded { id: other }),
+            None
+        );
+
+        // ...and get it back when their gamepad reconnects.
+        let reconnected = add_gamepad(&mut ctx, 20);
+        assert_ne!(reconnected, second);
+        assert_eq!(
+            players.event(&ctx, &Event::GamepadAdded { id: reconnected }),
+            Some(PlayerEvent::Rejoined {
+                player: 1,
+                device: InputDevice::Gamepad(reconnected)
+            })
+        );
+
+        assert!(players.remove_player(0));
+        assert_eq!(players.player_count(), 1);
+        assert_eq!(players.players().collect::<Vec<_>>(), vec![1]);
+    }
+}
@@ -0,0 +1,1685 @@
+use std::mem;
+use std::path::Path;
+use std::result;
+use std::time::Duration;
+
+use crate::error::{Result, TetraError};
+use crate::fs;
+use crate::math::Vec2;
+use crate::{Context, Event, State};
+
+use super::{
+    apply_mouse_wheel_movement, end_touch, get_gamepad, get_gamepad_axis_position, get_gamepad_mut,
+    get_gamepad_stick_position, move_touch, push_text_input, remove_gamepad, set_key_down,
+    set_key_modifier_state, set_key_up, set_mouse_button_down, set_mouse_button_up,
+    set_mouse_position, start_touch, GamepadAxis, GamepadButton, GamepadState, GamepadStick, Key,
+    KeyModifierState, MouseButton, Touch,
+};
+
+const MAGIC: &[u8; 4] = b"TTIR";
+const VERSION: u8 = 2;
+
+/// The platform ID given to gamepads that only exist because a recording
+/// connected them during playback.
+const REPLAYED_GAMEPAD_ID: u32 = u32::MAX;
+
+/// The number of gamepad slots that a recording is allowed to use. Recordings
+/// that refer to gamepads beyond this are rejected, so that a corrupt file
+/// can't cause a huge number of slots to be allocated.
+const MAX_GAMEPADS: usize = 64;
+
+/// An input event, as reported by the platform layer.
+///
+/// All input goes through this type (rather than being applied to the `InputContext`
+/// directly), so that it can be recorded and played back.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum InputEvent {
+    KeyPressed {
+        key: Key,
+        modifiers: KeyModifierState,
+    },
+    KeyReleased {
+        key: Key,
+        modifiers: KeyModifierState,
+    },
+    MouseButtonPressed {
+        button: MouseButton,
+    },
+    MouseButtonReleased {
+        button: MouseButton,
+    },
+    MouseMoved {
+        position: Vec2<f32>,
+        delta: Vec2<f32>,
+    },
+    MouseWheelMoved {
+        amount: Vec2<i32>,
+    },
+    TextInput {
+        text: String,
+    },
+    TouchStarted {
+        id: u64,
+        position: Vec2<f32>,
+        pressure: f32,
+    },
+    TouchMoved {
+        id: u64,
+        position: Vec2<f32>,
+        pressure: f32,
+    },
+    TouchEnded {
+        id: u64,
+        position: Vec2<f32>,
+    },
+    GamepadAdded {
+        id: usize,
+    },
+    GamepadRemoved {
+        id: usize,
+    },
+    GamepadButtonPressed {
+        id: usize,
+        button: GamepadButton,
+    },
+    GamepadButtonReleased {
+        id: usize,
+        button: GamepadButton,
+    },
+    GamepadAxisMoved {
+        id: usize,
+        axis: GamepadAxis,
+        position: f32,
+    },
+}
+
+/// A recording of the player's input, which can be played back via [`start_playback`].
+///
+/// Recordings are made up of a snapshot of the input state at the point where recording
+/// began, followed by the input events that occurred during each subsequent frame, and
+/// the amount of time that each frame took. Window events (e.g. resizing or focus
+/// changes) are not recorded.
+///
+/// Recordings can be converted to and from a compact binary format via [`to_bytes`](Self::to_bytes)
+/// and [`from_bytes`](Self::from_bytes), or saved to and loaded from a file via
+/// [`save`](Self::save) and [`load`](Self::load). This format is not guaranteed to be
+/// compatible between releases of Tetra.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputRecording {
+    initial: InputSnapshot,
+    frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    /// Loads a recording from the given file.
+    ///
+    /// # Errors
+    ///
+    /// * [`TetraError::FailedToLoadAsset`](crate::TetraError::FailedToLoadAsset) will be returned
+    /// if the file could not be loaded.
+    /// * [`TetraError::InvalidInputRecording`](crate::TetraError::InvalidInputRecording) will be
+    /// returned if the file was not a valid recording.
+    pub fn load<P>(path: P) -> Result<InputRecording>
+    where
+        P: AsRef<Path>,
+    {
+        InputRecording::from_bytes(&fs::read_asset(path)?)
+    }
+
+    /// Decodes a recording from a slice of bytes, as produced by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// * [`TetraError::InvalidInputRecording`](crate::TetraError::InvalidInputRecording) will be
+    /// returned if the data was not a valid recording.
+    pub fn from_bytes(data: &[u8]) -> Result<InputRecording> {
+        let mut decoder = Decoder { data };
+
+        if decoder.bytes(MAGIC.len())? != MAGIC || decoder.u8()? != VERSION {
+            return Err(TetraError::InvalidInputRecording);
+        }
+
+        let initial = InputSnapshot::decode(&mut decoder)?;
+
+        let frame_count = decoder.len()?;
+        let mut frames = Vec::new();
+
+        for _ in 0..frame_count {
+            let elapsed = Duration::from_nanos(decoder.varint()?);
+            let event_count = decoder.len()?;
+            let mut events = Vec::new();
+
+            for _ in 0..event_count {
+                events.push(decode_event(&mut decoder)?);
+            }
+
+            frames.push(RecordedFrame { elapsed, events });
+        }
+
+        if !decoder.data.is_empty() {
+            return Err(TetraError::InvalidInputRecording);
+        }
+
+        Ok(InputRecording { initial, frames })
+    }
+
+    /// Saves the recording to the given file.
+    ///
+    /// # Errors
+    ///
+    /// * [`TetraError::FailedToSaveFile`](crate::TetraError::FailedToSaveFile) will be returned
+    /// if the file could not be written.
+    pub fn save<P>(&self, path: P) -> Result
+    where
+        P: AsRef<Path>,
+    {
+        fs::write(path, self.to_bytes())
+    }
+
+    /// Encodes the recording into a compact binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::default();
+
+        encoder.data.extend_from_slice(MAGIC);
+        encoder.u8(VERSION);
+
+        self.initial.encode(&mut encoder);
+
+        encoder.len(self.frames.len());
+
+        for frame in &self.frames {
+            encoder.varint(frame.elapsed.as_nanos() as u64);
+            encoder.len(frame.events.len());
+
+            for event in &frame.events {
+                encode_event(&mut encoder, event);
+            }
+        }
+
+        encoder.data
+    }
+
+    /// Returns the number of frames in the recording.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the total amount of time covered by the recording.
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|f| f.elapsed).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RecordedFrame {
+    elapsed: Duration,
+    events: Vec<InputEvent>,
+}
+
+/// The input that was being held down when a recording started.
+#[derive(Debug, Clone, PartialEq)]
+struct InputSnapshot {
+    accumulator: Duration,
+    key_modifier_state: KeyModifierState,
+    keys_down: Vec<Key>,
+    mouse_buttons_down: Vec<MouseButton>,
+    mouse_position: Vec2<f32>,
+    touches: Vec<Touch>,
+    primary_touch: Option<u64>,
+    pads: Vec<Option<GamepadSnapshot>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct GamepadSnapshot {
+    buttons_down: Vec<GamepadButton>,
+    axes: Vec<(GamepadAxis, f32)>,
+}
+
+impl InputSnapshot {
+    fn capture(ctx: &Context) -> InputSnapshot {
+        InputSnapshot {
+            accumulator: ctx.time.accumulator,
+            key_modifier_state: ctx.input.key_modifier_state,
+            keys_down: ctx.input.keys_down.iter().copied().collect(),
+            mouse_buttons_down: ctx.input.mouse_buttons_down.iter().copied().collect(),
+            mouse_position: ctx.input.mouse_position,
+            touches: ctx.input.touches.clone(),
+            primary_touch: ctx.input.primary_touch,
+            pads: ctx
+                .input
+                .pads
+                .iter()
+                .map(|pad| {
+                    pad.as_ref().map(|pad| GamepadSnapshot {
+                        buttons_down: pad.buttons_down.iter().copied().collect(),
+                        axes: pad
+                            .raw_axis_state
+                            .iter()
+                            .map(|(&axis, &position)| (axis, position))
+                            .collect(),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    fn restore(&self, ctx: &mut Context) {
+        release_all_input(ctx);
+
+        ctx.time.accumulator = self.accumulator;
+
+        ctx.input.key_modifier_state = self.key_modifier_state;
+        ctx.input.keys_down.extend(self.keys_down.iter().copied());
+        ctx.input
+            .mouse_buttons_down
+            .extend(self.mouse_buttons_down.iter().copied());
+        ctx.input.mouse_position = self.mouse_position;
+        ctx.input.touches.clone_from(&self.touches);
+        ctx.input.primary_touch = self.primary_touch;
+
+        for (id, snapshot) in self.pads.iter().enumerate() {
+            if let Some(snapshot) = snapshot {
+                let pad = ensure_gamepad(ctx, id);
+
+                pad.buttons_down
+                    .extend(snapshot.buttons_down.iter().copied());
+
+                for &(axis, position) in &snapshot.axes {
+                    pad.set_axis_position(axis, position);
+                }
+            }
+        }
+    }
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.varint(self.accumulator.as_nanos() as u64);
+        encoder.modifiers(self.key_modifier_state);
+        encoder.codes(&self.keys_down);
+        encoder.codes(&self.mouse_buttons_down);
+        encoder.vec2(self.mouse_position);
+
+        encoder.len(self.touches.len());
+
+        for touch in &self.touches {
+            encoder.varint(touch.id);
+            encoder.vec2(touch.position);
+            encoder.f32(touch.pressure);
+        }
+
+        match self.primary_touch {
+            Some(id) => {
+                encoder.u8(1);
+                encoder.varint(id);
+            }
+            None => encoder.u8(0),
+        }
+
+        encoder.len(self.pads.len());
+
+        for pad in &self.pads {
+            match pad {
+                Some(pad) => {
+                    encoder.u8(1);
+                    encoder.codes(&pad.buttons_down);
+                    encoder.len(pad.axes.len());
+
+                    for &(axis, position) in &pad.axes {
+                        encoder.code(axis);
+                        encoder.f32(position);
+                    }
+                }
+                None => encoder.u8(0),
+            }
+        }
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<InputSnapshot> {
+        let accumulator = Duration::from_nanos(decoder.varint()?);
+        let key_modifier_state = decoder.modifiers()?;
+        let keys_down = decoder.codes()?;
+        let mouse_buttons_down = decoder.codes()?;
+        let mouse_position = decoder.vec2()?;
+
+        let touch_count = decoder.len()?;
+        let mut touches = Vec::new();
+
+        for _ in 0..touch_count {
+            touches.push(Touch {
+                id: decoder.varint()?,
+                position: decoder.vec2()?,
+                pressure: decoder.f32()?,
+            });
+        }
+
+        let primary_touch = match decoder.u8()? {
+            0 => None,
+            1 => Some(decoder.varint()?),
+            _ => return Err(TetraError::InvalidInputRecording),
+        };
+
+        let pad_count = decoder.len()?;
+
+        if pad_count > MAX_GAMEPADS {
+            return Err(TetraError::InvalidInputRecording);
+        }
+
+        let mut pads = Vec::new();
+
+        for _ in 0..pad_count {
+            let pad = match decoder.u8()? {
+                0 => None,
+                1 => {
+                    let buttons_down = decoder.codes()?;
+                    let axis_count = decoder.len()?;
+                    let mut axes = Vec::new();
+
+                    for _ in 0..axis_count {
+                        axes.push((decoder.code()?, decoder.f32()?));
+                    }
+
+                    Some(GamepadSnapshot { buttons_down, axes })
+                }
+                _ => return Err(TetraError::InvalidInputRecording),
+            };
+
+            pads.push(pad);
+        }
+
+        Ok(InputSnapshot {
+            accumulator,
+            key_modifier_state,
+            keys_down,
+            mouse_buttons_down,
+            mouse_position,
+            touches,
+            primary_touch,
+            pads,
+        })
+    }
+}
+
+pub(crate) struct Recorder {
+    initial: Option<InputSnapshot>,
+    frames: Vec<RecordedFrame>,
+}
+
+pub(crate) struct Playback {
+    recording: InputRecording,
+    next_frame: usize,
+    started: bool,
+    pending: Vec<InputEvent>,
+}
+
+/// Starts recording the player's input.
+///
+/// Recording begins at the start of the next frame, and continues until
+/// [`stop_recording`] is called. If a recording was already in progress, it
+/// will be discarded.
+///
+/// Input that is being played back via [`start_playback`] will also be recorded.
+pub fn start_recording(ctx: &mut Context) {
+    ctx.input.recorder = Some(Recorder {
+        initial: None,
+        frames: Vec::new(),
+    });
+}
+
+/// Stops recording the player's input, returning the recording.
+///
+/// If no recording was in progress, this will return `None`.
+pub fn stop_recording(ctx: &mut Context) -> Option<InputRecording> {
+    let recorder = ctx.input.recorder.take()?;
+
+    Some(InputRecording {
+        initial: recorder
+            .initial
+            .unwrap_or_else(|| InputSnapshot::capture(ctx)),
+        frames: recorder.frames,
+    })
+}
+
+/// Returns true if the player's input is currently being recorded.
+pub fn is_recording(ctx: &Context) -> bool {
+    ctx.input.recorder.is_some()
+}
+
+/// Starts playing back a recording of the player's input.
+///
+/// Playback begins at the start of the next frame. While a recording is being
+/// played back, input from the platform is ignored, and each frame is given the
+/// same elapsed time as when it was recorded - this means that if your game uses
+/// [`Timestep::Fixed`](crate::time::Timestep::Fixed) and is otherwise deterministic,
+/// it will behave exactly the same as it did during recording.
+///
+/// Once the end of the recording is reached, playback will stop automatically, and any
+/// keys or buttons that the recording was holding down will be released (without any
+/// events being fired). If a recording was already being played back, it will be replaced.
+///
+/// Gamepads that are connected or disconnected during playback will not take or free up
+/// a gamepad ID until playback has finished, so that they don't interfere with the
+/// gamepads that the recording uses.
+pub fn start_playback(ctx: &mut Context, recording: InputRecording) {
+    ctx.input.playback = Some(Playback {
+        recording,
+        next_frame: 0,
+        started: false,
+        pending: Vec::new(),
+    });
+}
+
+/// Stops playing back a recording of the player's input.
+///
+/// Any keys or buttons that the recording was holding down will be released
+/// (without any events being fired).
+pub fn stop_playback(ctx: &mut Context) {
+    if let Some(playback) = ctx.input.playback.take() {
+        if playback.started {
+            release_all_input(ctx);
+        }
+    }
+}
+
+/// Returns true if a recording of the player's input is currently being played back.
+pub fn is_playing_back(ctx: &Context) -> bool {
+    ctx.input.playback.is_some()
+}
+
+/// Prepares the input recorder/playback for a new frame, returning the amount of
+/// time that the frame should be treated as having taken.
+pub(crate) fn begin_frame(ctx: &mut Context, elapsed: Duration) -> Duration {
+    let mut elapsed = elapsed;
+
+    if let Some(mut playback) = ctx.input.playback.take() {
+        if !playback.started {
+            playback.recording.initial.restore(ctx);
+            playback.started = true;
+        }
+
+        match playback.recording.frames.get_mut(playback.next_frame) {
+            Some(frame) => {
+                elapsed = frame.elapsed;
+                playback.pending = mem::take(&mut frame.events);
+                playback.next_frame += 1;
+
+                ctx.input.playback = Some(playback);
+            }
+
+            None => release_all_input(ctx),
+        }
+    }
+
+    if matches!(&ctx.input.recorder, Some(recorder) if recorder.initial.is_none()) {
+        let initial = InputSnapshot::capture(ctx);
+
+        if let Some(recorder) = &mut ctx.input.recorder {
+            recorder.initial = Some(initial);
+        }
+    }
+
+    if let Some(recorder) = &mut ctx.input.recorder {
+        recorder.frames.push(RecordedFrame {
+            elapsed,
+            events: Vec::new(),
+        });
+    }
+
+    elapsed
+}
+
+/// Delivers the events for the current frame of playback, if there is one.
+///
+/// This should be called after the platform's events have been handled.
+pub(crate) fn play_back_events<S, E>(ctx: &mut Context, state: &mut S) -> result::Result<(), E>
+where
+    S: State<E>,
+    E: From<TetraError>,
+{
+    let events = match &mut ctx.input.playback {
+        Some(playback) => mem::take(&mut playback.pending),
+        None => return Ok(()),
+    };
+
+    for event in events {
+        record_and_apply(ctx, state, event)?;
+    }
+
+    Ok(())
+}
+
+/// Handles an input event from the platform.
+///
+/// The event will be applied to the input state and then passed to the game's `State`,
+/// unless a recording is being played back, in which case it will be ignored.
+pub(crate) fn handle_input_event<S, E>(
+    ctx: &mut Context,
+    state: &mut S,
+    event: InputEvent,
+) -> result::Result<(), E>
+where
+    S: State<E>,
+    E: From<TetraError>,
+{
+    if ctx.input.playback.is_some() {
+        return Ok(());
+    }
+
+    record_and_apply(ctx, state, event)
+}
+
+fn record_and_apply<S, E>(
+    ctx: &mut Context,
+    state: &mut S,
+    event: InputEvent,
+) -> result::Result<(), E>
+where
+    S: State<E>,
+    E: From<TetraError>,
+{
+    if let Some(frame) = ctx
+        .input
+        .recorder
+        .as_mut()
+        .and_then(|r| r.frames.last_mut())
+    {
+        frame.events.push(event.clone());
+    }
+
+    match event {
+        InputEvent::KeyPressed { key, modifiers } => {
+            set_key_modifier_state(ctx, modifiers);
+            set_key_down(ctx, key);
+            state.event(ctx, Event::KeyPressed { key })?;
+        }
+
+        InputEvent::KeyReleased { key, modifiers } => {
+            set_key_modifier_state(ctx, modifiers);
+            set_key_up(ctx, key);
+            state.event(ctx, Event::KeyReleased { key })?;
+        }
+
+        InputEvent::MouseButtonPressed { button } => {
+            set_mouse_button_down(ctx, button);
+            state.event(ctx, Event::MouseButtonPressed { button })?;
+        }
+
+        InputEvent::MouseButtonReleased { button } => {
+            set_mouse_button_up(ctx, button);
+            state.event(ctx, Event::MouseButtonReleased { button })?;
+        }
+
+        InputEvent::MouseMoved { position, delta } => {
+            set_mouse_position(ctx, position);
+            state.event(ctx, Event::MouseMoved { position, delta })?;
+        }
+
+        InputEvent::MouseWheelMoved { amount } => {
+            apply_mouse_wheel_movement(ctx, amount);
+            state.event(ctx, Event::MouseWheelMoved { amount })?;
+        }
+
+        InputEvent::TextInput { text } => {
+            push_text_input(ctx, &text);
+            state.event(ctx, Event::TextInput { text })?;
+        }
+
+        InputEvent::TouchStarted {
+            id,
+            position,
+            pressure,
+        } => {
+            let primary = start_touch(
+                ctx,
+                Touch {
+                    id,
+                    position,
+                    pressure,
+                },
+            );
+
+            state.event(
+                ctx,
+                Event::TouchStarted {
+                    id,
+                    position,
+                    pressure,
+                },
+            )?;
+
+            if primary && ctx.input.touch_mouse_emulation {
+                emulate_mouse_move(ctx, state, position)?;
+
+                if set_mouse_button_down(ctx, MouseButton::Left) {
+                    state.event(
+                        ctx,
+                        Event::MouseButtonPressed {
+                            button: MouseButton::Left,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        InputEvent::TouchMoved {
+            id,
+            position,
+            pressure,
+        } => {
+            if let Some(delta) = move_touch(ctx, id, position, pressure) {
+                state.event(
+                    ctx,
+                    Event::TouchMoved {
+                        id,
+                        position,
+                        delta,
+                        pressure,
+                    },
+                )?;
+
+                if ctx.input.primary_touch == Some(id) && ctx.input.touch_mouse_emulation {
+                    emulate_mouse_move(ctx, state, position)?;
+                }
+            }
+        }
+
+        InputEvent::TouchEnded { id, position } => {
+            let primary = ctx.input.primary_touch == Some(id);
+
+            if end_touch(ctx, id, position).is_some() {
+                state.event(ctx, Event::TouchEnded { id, position })?;
+
+                if primary && ctx.input.touch_mouse_emulation {
+                    emulate_mouse_move(ctx, state, position)?;
+
+                    if set_mouse_button_up(ctx, MouseButton::Left) {
+                        state.event(
+                            ctx,
+                            Event::MouseButtonReleased {
+                                button: MouseButton::Left,
+                            },
+                        )?;
+                    }
+                }
+            }
+        }
+
+        InputEvent::GamepadAdded { id } => {
+            ensure_gamepad(ctx, id);
+            state.event(ctx, Event::GamepadAdded { id })?;
+        }
+
+        InputEvent::GamepadRemoved { id } => {
+            if get_gamepad(ctx, id).is_some() {
+                remove_gamepad(ctx, id);
+            }
+
+            state.event(ctx, Event::GamepadRemoved { id })?;
+        }
+
+        InputEvent::GamepadButtonPressed { id, button } => {
+            if let Some(pad) = get_gamepad_mut(ctx, id) {
+                pad.set_button_down(button);
+                state.event(ctx, Event::GamepadButtonPressed { id, button })?;
+            }
+        }
+
+        InputEvent::GamepadButtonReleased { id, button } => {
+            if let Some(pad) = get_gamepad_mut(ctx, id) {
+                // TODO: This can cause some inputs to be missed at low tick rates.
+                // Could consider buffering input releases like Otter2D does?
+                pad.set_button_up(button);
+                state.event(ctx, Event::GamepadButtonReleased { id, button })?;
+            }
+        }
+
+        InputEvent::GamepadAxisMoved { id, axis, position } => {
+            if let Some(pad) = get_gamepad_mut(ctx, id) {
+                // The settings for a stick can cause both of its axes to change when
+                // only one of them has moved, so both need to be checked for changes.
+                let (axes, stick): (&[GamepadAxis], _) = match axis {
+                    GamepadAxis::LeftStickX | GamepadAxis::LeftStickY => (
+                        &[GamepadAxis::LeftStickX, GamepadAxis::LeftStickY],
+                        Some(GamepadStick::LeftStick),
+                    ),
+                    GamepadAxis::RightStickX | GamepadAxis::RightStickY => (
+                        &[GamepadAxis::RightStickX, GamepadAxis::RightStickY],
+                        Some(GamepadStick::RightStick),
+                    ),
+                    GamepadAxis::LeftTrigger => (&[GamepadAxis::LeftTrigger], None),
+                    GamepadAxis::RightTrigger => (&[GamepadAxis::RightTrigger], None),
+                };
+
+                let previous = [0, 1].map(|i| {
+                    axes.get(i).map_or(0.0, |axis| {
+                        pad.current_axis_state.get(axis).copied().unwrap_or(0.0)
+                    })
+                });
+
+                pad.set_axis_position(axis, position);
+
+                for (button, down) in pad.update_virtual_buttons() {
+                    if down {
+                        state.event(ctx, Event::GamepadButtonPressed { id, button })?;
+                    } else {
+                        state.event(ctx, Event::GamepadButtonReleased { id, button })?;
+                    }
+                }
+
+                let mut changed = false;
+
+                for (&axis, previous) in axes.iter().zip(previous) {
+                    let position = get_gamepad_axis_position(ctx, id, axis);
+
+                    if previous != position {
+                        changed = true;
+                        state.event(ctx, Event::GamepadAxisMoved { id, axis, position })?;
+                    }
+                }
+
+                if let Some(stick) = stick {
+                    if changed {
+                        state.event(
+                            ctx,
+                            Event::GamepadStickMoved {
+                                id,
+                                stick,
+                                position: get_gamepad_stick_position(ctx, id, stick),
+                            },
+                        )?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves the mouse to the position of the primary touch, if it isn't there already.
+fn emulate_mouse_move<S, E>(
+    ctx: &mut Context,
+    state: &mut S,
+    position: Vec2<f32>,
+) -> result::Result<(), E>
+where
+    S: State<E>,
+    E: From<TetraError>,
+{
+    let delta = position - ctx.input.mouse_position;
+
+    if delta != Vec2::zero() {
+        set_mouse_position(ctx, position);
+        state.event(ctx, Event::MouseMoved { position, delta })?;
+    }
+
+    Ok(())
+}
+
+/// Returns the gamepad in the given slot, connecting a placeholder gamepad if
+/// the slot is empty.
+fn ensure_gamepad(ctx: &mut Context, id: usize) -> &mut GamepadState {
+    if ctx.input.pads.len() <= id {
+        ctx.input.pads.resize_with(id + 1, || None);
+    }
+
+    let settings = ctx.input.default_gamepad_settings;
+
+    ctx.input.pads[id].get_or_insert_with(|| GamepadState::new(REPLAYED_GAMEPAD_ID, settings))
+}
+
+/// Releases all held keys and buttons (without firing any events), and disconnects
+/// any gamepads that were connected by a recording.
+fn release_all_input(ctx: &mut Context) {
+    ctx.input.keys_down.clear();
+    ctx.input.key_modifier_state = KeyModifierState::default();
+    ctx.input.mouse_buttons_down.clear();
+    ctx.input.touches.clear();
+    ctx.input.primary_touch = None;
+
+    for slot in &mut ctx.input.pads {
+        if let Some(pad) = slot {
+            if pad.platform_id == REPLAYED_GAMEPAD_ID {
+                *slot = None;
+            } else {
+                pad.buttons_down.clear();
+                pad.raw_axis_state.clear();
+                pad.current_axis_state.clear();
+            }
+        }
+    }
+}
+
+macro_rules! codes {
+    ($($ty:ident { $($variant:ident),* $(,)? })*) => {
+        $(
+            impl Code for $ty {
+                const VARIANTS: &'static [$ty] = &[$($ty::$variant),*];
+
+                fn to_code(self) -> u8 {
+                    // This will fail to compile if a variant is missing from the table.
+                    match self {
+                        $($ty::$variant)|* => {}
+                    }
+
+                    Self::VARIANTS.iter().position(|&v| v == self).unwrap() as u8
+                }
+            }
+        )*
+    };
+}
+
+/// Maps the variants of an input enum to and from single bytes, in declaration order.
+trait Code: Copy + PartialEq + 'static {
+    const VARIANTS: &'static [Self];
+
+    fn to_code(self) -> u8;
+
+    fn from_code(code: u8) -> Result<Self> {
+        Self::VARIANTS
+            .get(usize::from(code))
+            .copied()
+            .ok_or(TetraError::InvalidInputRecording)
+    }
+}
+
+codes! {
+    Key {
+        A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, Num0, Num1,
+        Num2, Num3, Num4, Num5, Num6, Num7, Num8, Num9, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10,
+        F11, F12, F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24, NumLock, NumPad1,
+        NumPad2, NumPad3, NumPad4, NumPad5, NumPad6, NumPad7, NumPad8, NumPad9, NumPad0,
+        NumPadPlus, NumPadMinus, NumPadMultiply, NumPadDivide, NumPadEnter, LeftCtrl, LeftShift,
+        LeftAlt, RightCtrl, RightShift, RightAlt, Up, Down, Left, Right, Backquote, Backslash,
+        Backspace, CapsLock, Comma, Delete, End, Enter, Equals, Escape, Home, Insert, LeftBracket,
+        Minus, PageDown, PageUp, Pause, Period, PrintScreen, Quote, RightBracket, ScrollLock,
+        Semicolon, Slash, Space, Tab,
+    }
+
+    MouseButton {
+        Left, Middle, Right, X1, X2,
+    }
+
+    GamepadButton {
+        A, B, X, Y, Up, Down, Left, Right, LeftShoulder, LeftTrigger, LeftStick, RightShoulder,
+        RightTrigger, RightStick, Start, Back, Guide, LeftStickUp, LeftStickDown, LeftStickLeft,
+        LeftStickRight, RightStickUp, RightStickDown, RightStickLeft, RightStickRight,
+    }
+
+    GamepadAxis {
+        LeftStickX, LeftStickY, LeftTrigger, RightStickX, RightStickY, RightTrigger,
+    }
+}
+
+const KEY_PRESSED: u8 = 0;
+const KEY_RELEASED: u8 = 1;
+const MOUSE_BUTTON_PRESSED: u8 = 2;
+const MOUSE_BUTTON_RELEASED: u8 = 3;
+const MOUSE_MOVED: u8 = 4;
+const MOUSE_WHEEL_MOVED: u8 = 5;
+const TEXT_INPUT: u8 = 6;
+const GAMEPAD_ADDED: u8 = 7;
+const GAMEPAD_REMOVED: u8 = 8;
+const GAMEPAD_BUTTON_PRESSED: u8 = 9;
+const GAMEPAD_BUTTON_RELEASED: u8 = 10;
+const GAMEPAD_AXIS_MOVED: u8 = 11;
+const TOUCH_STARTED: u8 = 12;
+const TOUCH_MOVED: u8 = 13;
+const TOUCH_ENDED: u8 = 14;
+
+fn encode_event(encoder: &mut Encoder, event: &InputEvent) {
+    match event {
+        InputEvent::KeyPressed { key, modifiers } => {
+            encoder.u8(KEY_PRESSED);
+            encoder.code(*key);
+            encoder.modifiers(*modifiers);
+        }
+
+        InputEvent::KeyReleased { key, modifiers } => {
+            encoder.u8(KEY_RELEASED);
+            encoder.code(*key);
+            encoder.modifiers(*modifiers);
+        }
+
+        InputEvent::MouseButtonPressed { button } => {
+            encoder.u8(MOUSE_BUTTON_PRESSED);
+            encoder.code(*button);
+        }
+
+        InputEvent::MouseButtonReleased { button } => {
+            encoder.u8(MOUSE_BUTTON_RELEASED);
+            encoder.code(*button);
+        }
+
+        InputEvent::MouseMoved { position, delta } => {
+            encoder.u8(MOUSE_MOVED);
+            encoder.vec2(*position);
+            encoder.vec2(*delta);
+        }
+
+        InputEvent::MouseWheelMoved { amount } => {
+            encoder.u8(MOUSE_WHEEL_MOVED);
+            encoder.i32(amount.x);
+            encoder.i32(amount.y);
+        }
+
+        InputEvent::TextInput { text } => {
+            encoder.u8(TEXT_INPUT);
+            encoder.len(text.len());
+            encoder.data.extend_from_slice(text.as_bytes());
+        }
+
+        InputEvent::TouchStarted {
+            id,
+            position,
+            pressure,
+        } => {
+            encoder.u8(TOUCH_STARTED);
+            encoder.varint(*id);
+            encoder.vec2(*position);
+            encoder.f32(*pressure);
+        }
+
+        InputEvent::TouchMoved {
+            id,
+            position,
+            pressure,
+        } => {
+            encoder.u8(TOUCH_MOVED);
+            encoder.varint(*id);
+            encoder.vec2(*position);
+            encoder.f32(*pressure);
+        }
+
+        InputEvent::TouchEnded { id, position } => {
+            encoder.u8(TOUCH_ENDED);
+            encoder.varint(*id);
+            encoder.vec2(*position);
+        }
+
+        InputEvent::GamepadAdded { id } => {
+            encoder.u8(GAMEPAD_ADDED);
+            encoder.id(*id);
+        }
+
+        InputEvent::GamepadRemoved { id } => {
+            encoder.u8(GAMEPAD_REMOVED);
+            encoder.id(*id);
+        }
+
+        InputEvent::GamepadButtonPressed { id, button } => {
+            encoder.u8(GAMEPAD_BUTTON_PRESSED);
+            encoder.id(*id);
+            encoder.code(*button);
+        }
+
+        InputEvent::GamepadButtonReleased { id, button } => {
+            encoder.u8(GAMEPAD_BUTTON_RELEASED);
+            encoder.id(*id);
+            encoder.code(*button);
+        }
+
+        InputEvent::GamepadAxisMoved { id, axis, position } => {
+            encoder.u8(GAMEPAD_AXIS_MOVED);
+            encoder.id(*id);
+            encoder.code(*axis);
+            encoder.f32(*position);
+        }
+    }
+}
+
+fn decode_event(decoder: &mut Decoder<'_>) -> Result<InputEvent> {
+    let event = match decoder.u8()? {
+        KEY_PRESSED => InputEvent::KeyPressed {
+            key: decoder.code()?,
+            modifiers: decoder.modifiers()?,
+        },
+
+        KEY_RELEASED => InputEvent::KeyReleased {
+            key: decoder.code()?,
+            modifiers: decoder.modifiers()?,
+        },
+
+        MOUSE_BUTTON_PRESSED => InputEvent::MouseButtonPressed {
+            button: decoder.code()?,
+        },
+
+        MOUSE_BUTTON_RELEASED => InputEvent::MouseButtonReleased {
+            button: decoder.code()?,
+        },
+
+        MOUSE_MOVED => InputEvent::MouseMoved {
+            position: decoder.vec2()?,
+            delta: decoder.vec2()?,
+        },
+
+        MOUSE_WHEEL_MOVED => InputEvent::MouseWheelMoved {
+            amount: Vec2::new(decoder.i32()?, decoder.i32()?),
+        },
+
+        TEXT_INPUT => {
+            let len = decoder.len()?;
+            let bytes = decoder.bytes(len)?;
+
+            InputEvent::TextInput {
+                text: String::from_utf8(bytes.to_vec())
+                    .map_err(|_| TetraError::InvalidInputRecording)?,
+            }
+        }
+
+        TOUCH_STARTED => InputEvent::TouchStarted {
+            id: decoder.varint()?,
+            position: decoder.vec2()?,
+            pressure: decoder.f32()?,
+        },
+
+        TOUCH_MOVED => InputEvent::TouchMoved {
+            id: decoder.varint()?,
+            position: decoder.vec2()?,
+            pressure: decoder.f32()?,
+        },
+
+        TOUCH_ENDED => InputEvent::TouchEnded {
+            id: decoder.varint()?,
+            position: decoder.vec2()?,
+        },
+
+        GAMEPAD_ADDED => InputEvent::GamepadAdded { id: decoder.id()? },
+
+        GAMEPAD_REMOVED => InputEvent::GamepadRemoved { id: decoder.id()? },
+
+        GAMEPAD_BUTTON_PRESSED => InputEvent::GamepadButtonPressed {
+            id: decoder.id()?,
+            button: decoder.code()?,
+        },
+
+        GAMEPAD_BUTTON_RELEASED => InputEvent::GamepadButtonReleased {
+            id: decoder.id()?,
+            button: decoder.code()?,
+        },
+
+        GAMEPAD_AXIS_MOVED => InputEvent::GamepadAxisMoved {
+            id: decoder.id()?,
+            axis: decoder.code()?,
+            position: decoder.f32()?,
+        },
+
+        _ => return Err(TetraError::InvalidInputRecording),
+    };
+
+    Ok(event)
+}
+
+#[derive(Default)]
+struct Encoder {
+    data: Vec<u8>,
+}
+
+impl Encoder {
+    fn u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    /// Writes an unsigned LEB128 integer.
+    fn varint(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+
+            if value == 0 {
+                self.data.push(byte);
+                break;
+            }
+
+            self.data.push(byte | 0x80);
+        }
+    }
+
+    fn len(&mut self, value: usize) {
+        self.varint(value as u64);
+    }
+
+    fn id(&mut self, value: usize) {
+        self.varint(value as u64);
+    }
+
+    /// Writes a zigzag-encoded signed integer.
+    fn i32(&mut self, value: i32) {
+        self.varint(((value << 1) ^ (value >> 31)) as u32 as u64);
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn vec2(&mut self, value: Vec2<f32>) {
+        self.f32(value.x);
+        self.f32(value.y);
+    }
+
+    fn code<T: Code>(&mut self, value: T) {
+        self.u8(value.to_code());
+    }
+
+    fn codes<T: Code>(&mut self, values: &[T]) {
+        self.len(values.len());
+
+        for &value in values {
+            self.code(value);
+        }
+    }
+
+    fn modifiers(&mut self, modifiers: KeyModifierState) {
+        self.u8(modifiers.ctrl as u8 | (modifiers.alt as u8) << 1 | (modifiers.shift as u8) << 2);
+    }
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(TetraError::InvalidInputRecording);
+        }
+
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(TetraError::InvalidInputRecording)
+    }
+
+    fn len(&mut self) -> Result<usize> {
+        let value = self.varint()?;
+
+        // Every item takes up at least one byte, so a length can never be larger than
+        // the remaining data. Checking this stops corrupt files from causing huge allocations.
+        if value > self.data.len() as u64 {
+            return Err(TetraError::InvalidInputRecording);
+        }
+
+        Ok(value as usize)
+    }
+
+    fn id(&mut self) -> Result<usize> {
+        let value = self.varint()?;
+
+        if value >= MAX_GAMEPADS as u64 {
+            return Err(TetraError::InvalidInputRecording);
+        }
+
+        Ok(value as usize)
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        let value = self.varint()?;
+
+        if value > u64::from(u32::MAX) {
+            return Err(TetraError::InvalidInputRecording);
+        }
+
+        let value = value as u32;
+        Ok((value >> 1) as i32 ^ -((value & 1) as i32))
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.bytes(4)?);
+
+        Ok(f32::from_le_bytes(bytes))
+    }
+
+    fn vec2(&mut self) -> Result<Vec2<f32>> {
+        Ok(Vec2::new(self.f32()?, self.f32()?))
+    }
+
+    fn code<T: Code>(&mut self) -> Result<T> {
+        T::from_code(self.u8()?)
+    }
+
+    fn codes<T: Code>(&mut self) -> Result<Vec<T>> {
+        let len = self.len()?;
+        let mut values = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            values.push(self.code()?);
+        }
+
+        Ok(values)
+    }
+
+    fn modifiers(&mut self) -> Result<KeyModifierState> {
+        let bits = self.u8()?;
+
+        if bits > 0b111 {
+            return Err(TetraError::InvalidInputRecording);
+        }
+
+        Ok(KeyModifierState {
+            ctrl: bits & 0b001 != 0,
+            alt: bits & 0b010 != 0,
+            shift: bits & 0b100 != 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::ContextBuilder;
+
+    /// Logs every update, along with the input state that it saw.
+    #[derive(Default)]
+    struct LoggingState {
+        log: Vec<String>,
+    }
+
+    impl State for LoggingState {
+        fn update(&mut self, ctx: &mut Context) -> Result {
+            self.log.push(format!(
+                "update space_down={} space_pressed={} mouse={:?} text={:?} trigger={}",
+                input::is_key_down(ctx, Key::Space),
+                input::is_key_pressed(ctx, Key::Space),
+                input::get_mouse_position(ctx),
+                input::get_text_input(ctx),
+                input::is_gamepad_button_down(ctx, 0, GamepadButton::LeftTrigger),
+            ));
+
+            Ok(())
+        }
+
+        fn event(&mut self, _: &mut Context, event: Event) -> Result {
+            self.log.push(format!("{:?}", event));
+            Ok(())
+        }
+    }
+
+    fn frame_time() -> Duration {
+        // Deliberately not a multiple of the tick rate, so that the accumulator
+        // carries time over between frames.
+        Duration::from_secs_f64(1.0 / 45.0)
+    }
+
+    fn headless_context() -> Context {
+        ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap()
+    }
+
+    fn simulate_session(ctx: &mut Context, state: &mut LoggingState) {
+        let frames = vec![
+            vec![InputEvent::KeyPressed {
+                key: Key::Space,
+                modifiers: KeyModifierState::default(),
+            }],
+            vec![InputEvent::TouchStarted {
+                id: 3,
+                position: Vec2::new(5.0, 6.0),
+                pressure: 0.75,
+            }],
+            vec![
+                InputEvent::MouseMoved {
+                    position: Vec2::new(12.0, 34.0),
+                    delta: Vec2::new(12.0, 34.0),
+                },
+                InputEvent::TextInput {
+                    text: "héllo".into(),
+                },
+                InputEvent::GamepadAdded { id: 0 },
+                InputEvent::GamepadAxisMoved {
+                    id: 0,
+                    axis: GamepadAxis::LeftTrigger,
+                    position: 0.5,
+                },
+            ],
+            vec![
+                InputEvent::KeyReleased {
+                    key: Key::Space,
+                    modifiers: KeyModifierState::default(),
+                },
+                InputEvent::TouchMoved {
+                    id: 3,
+                    position: Vec2::new(7.0, 8.0),
+                    pressure: 1.0,
+                },
+            ],
+            vec![InputEvent::TouchEnded {
+                id: 3,
+                position: Vec2::new(7.0, 8.0),
+            }],
+        ];
+
+        for events in frames {
+            for event in events {
+                ctx.window.push_input_event(event);
+            }
+
+            ctx.tick(state, frame_time()).unwrap();
+        }
+    }
+
+    #[test]
+    fn playback_matches_recording() {
+        let mut ctx = headless_context();
+        let mut state = LoggingState::default();
+
+        // Start partway through a frame, with a key already held:
+        ctx.window.push_input_event(InputEvent::KeyPressed {
+            key: Key::A,
+            modifiers: KeyModifierState::default(),
+        });
+
+        ctx.tick(&mut state, Duration::from_millis(5)).unwrap();
+
+        input::start_recording(&mut ctx);
+        assert!(input::is_recording(&ctx));
+
+        state.log.clear();
+        simulate_session(&mut ctx, &mut state);
+        let recorded_log = std::mem::take(&mut state.log);
+
+        let recording = input::stop_recording(&mut ctx).unwrap();
+        assert_eq!(recording.frame_count(), 5);
+
+        let recording = InputRecording::from_bytes(&recording.to_bytes()).unwrap();
+
+        let mut ctx = headless_context();
+        let mut state = LoggingState::default();
+
+        input::start_playback(&mut ctx, recording);
+
+        // Platform input should be ignored during playback, and the frame
+        // times should come from the recording.
+        for _ in 0..5 {
+            ctx.window.push_input_event(InputEvent::KeyPressed {
+                key: Key::Space,
+                modifiers: KeyModifierState::default(),
+            });
+
+            ctx.tick(&mut state, Duration::from_secs(1)).unwrap();
+        }
+
+        assert_eq!(state.log, recorded_log);
+        assert!(input::is_key_down(&ctx, Key::A));
+
+        ctx.tick(&mut state, frame_time()).unwrap();
+
+        assert!(!input::is_playing_back(&ctx));
+        assert!(!input::is_key_down(&ctx, Key::A));
+        assert!(!input::is_gamepad_connected(&ctx, 0));
+    }
+
+    #[test]
+    fn gamepad_settings_are_applied_before_events() {
+        let mut ctx = headless_context();
+        let mut state = LoggingState::default();
+
+        let mut settings = input::GamepadSettings::default();
+        settings.left_stick.inner_dead_zone = 0.2;
+        settings.left_trigger.button_threshold = 0.5;
+        input::set_default_gamepad_settings(&mut ctx, settings);
+
+        ctx.window
+            .push_input_event(InputEvent::GamepadAdded { id: 0 });
+        ctx.tick(&mut state, frame_time()).unwrap();
+        assert_eq!(input::get_gamepad_settings(&ctx, 0), Some(settings));
+
+        for (axis, position) in [
+            (GamepadAxis::LeftStickX, 0.1),
+            (GamepadAxis::LeftStickY, -0.15),
+            (GamepadAxis::LeftTrigger, 0.3),
+        ] {
+            ctx.window.push_input_event(InputEvent::GamepadAxisMoved {
+                id: 0,
+                axis,
+                position,
+            });
+        }
+
+        state.log.clear();
+        ctx.tick(&mut state, frame_time()).unwrap();
+
+        // The stick is still inside the dead zone, so only the trigger should have moved,
+        // and it isn't far enough to count as a button press.
+        assert!(state.log.iter().all(|entry| !entry.contains("Stick")));
+        assert!(state
+            .log
+            .iter()
+            .any(|entry| entry.contains("LeftTrigger, position: 0.3")));
+        assert!(!input::is_gamepad_button_down(
+            &ctx,
+            0,
+            GamepadButton::LeftTrigger
+        ));
+
+        ctx.window.push_input_event(InputEvent::GamepadAxisMoved {
+            id: 0,
+            axis: GamepadAxis::LeftStickX,
+            position: 0.6,
+        });
+
+        state.log.clear();
+        ctx.tick(&mut state, frame_time()).unwrap();
+
+        // Leaving the radial dead zone moves both axes of the stick at once.
+        let stick = input::get_gamepad_stick_position(&ctx, 0, GamepadStick::LeftStick);
+        assert!(stick.x > 0.0 && stick.x < 0.6);
+        assert!(stick.y < 0.0);
+        assert_eq!(
+            state
+                .log
+                .iter()
+                .filter(|entry| entry.starts_with("GamepadAxisMoved"))
+                .count(),
+            2
+        );
+        assert!(state
+            .log
+            .iter()
+            .any(|entry| entry.starts_with("GamepadStickMoved")));
+    }
+
+    #[test]
+    fn stick_directions_act_as_buttons() {
+        let mut ctx = headless_context();
+        let mut state = LoggingState::default();
+
+        ctx.window
+            .push_input_event(InputEvent::GamepadAdded { id: 0 });
+        ctx.tick(&mut state, frame_time()).unwrap();
+
+        let pressed = String::from("GamepadButtonPressed { id: 0, button: LeftStickUp }");
+        let released = String::from("GamepadButtonReleased { id: 0, button: LeftStickUp }");
+
+        let mut move_stick = |position: f32| {
+            ctx.window.push_input_event(InputEvent::GamepadAxisMoved {
+                id: 0,
+                axis: GamepadAxis::LeftStickY,
+                position,
+            });
+
+            state.log.clear();
+            ctx.tick(&mut state, frame_time()).unwrap();
+
+            let down = input::is_gamepad_button_down(&ctx, 0, GamepadButton::LeftStickUp);
+
+            (down, std::mem::take(&mut state.log))
+        };
+
+        let (down, log) = move_stick(-0.6);
+        assert!(down);
+        assert!(log.contains(&pressed));
+
+        // Dropping just below the press threshold shouldn't release the button...
+        let (down, log) = move_stick(-0.45);
+        assert!(down);
+        assert!(!log.contains(&released));
+
+        // ...until the stick passes the release threshold.
+        let (down, log) = move_stick(-0.3);
+        assert!(!down);
+        assert!(log.contains(&released));
+
+        let (down, log) = move_stick(-0.45);
+        assert!(!down);
+        assert!(!log.contains(&pressed));
+    }
+
+    #[test]
+    fn primary_touch_emulates_mouse() {
+        let mut ctx = headless_context();
+        let mut state = LoggingState::default();
+
+        let mut push_and_tick = |event: InputEvent| {
+            ctx.window.push_input_event(event);
+            state.log.clear();
+            ctx.tick(&mut state, frame_time()).unwrap();
+
+            (
+                input::get_primary_touch(&ctx).map(|t| t.id),
+                input::is_mouse_button_down(&ctx, MouseButton::Left),
+                input::get_mouse_position(&ctx),
+                input::get_touch_count(&ctx),
+            )
+        };
+
+        assert_eq!(
+            push_and_tick(InputEvent::TouchStarted {
+                id: 1,
+                position: Vec2::new(10.0, 10.0),
+                pressure: 1.0,
+            }),
+            (Some(1), true, Vec2::new(10.0, 10.0), 1)
+        );
+
+        // Other touches shouldn't affect the mouse...
+        assert_eq!(
+            push_and_tick(InputEvent::TouchStarted {
+                id: 2,
+                position: Vec2::new(50.0, 50.0),
+                pressure: 1.0,
+            }),
+            (Some(1), true, Vec2::new(10.0, 10.0), 2)
+        );
+
+        assert_eq!(
+            push_and_tick(InputEvent::TouchMoved {
+                id: 1,
+                position: Vec2::new(20.0, 15.0),
+                pressure: 1.0,
+            }),
+            (Some(1), true, Vec2::new(20.0, 15.0), 2)
+        );
+
+        // ...and once the primary touch ends, there shouldn't be a new one until
+        // all of the touches have ended.
+        assert_eq!(
+            push_and_tick(InputEvent::TouchEnded {
+                id: 1,
+                position: Vec2::new(20.0, 15.0),
+            }),
+            (None, false, Vec2::new(20.0, 15.0), 1)
+        );
+
+        assert_eq!(
+            push_and_tick(InputEvent::TouchMoved {
+                id: 2,
+                position: Vec2::new(40.0, 40.0),
+                pressure: 1.0,
+            }),
+            (None, false, Vec2::new(20.0, 15.0), 1)
+        );
+
+        assert_eq!(
+            push_and_tick(InputEvent::TouchEnded {
+                id: 2,
+                position: Vec2::new(40.0, 40.0),
+            }),
+            (None, false, Vec2::new(20.0, 15.0), 0)
+        );
+
+        input::set_touch_mouse_emulation(&mut ctx, false);
+
+        ctx.window.push_input_event(InputEvent::TouchStarted {
+            id: 3,
+            position: Vec2::new(0.0, 0.0),
+            pressure: 0.5,
+        });
+
+        state.log.clear();
+        ctx.tick(&mut state, frame_time()).unwrap();
+
+        assert_eq!(
+            state.log[0],
+            "TouchStarted { id: 3, position: Vec2 { x: 0.0, y: 0.0 }, pressure: 0.5 }"
+        );
+        assert_eq!(input::get_touch_pressure(&ctx, 3), Some(0.5));
+        assert!(!input::is_mouse_button_down(&ctx, MouseButton::Left));
+    }
+
+    #[test]
+    fn invalid_recordings_are_rejected() {
+        let mut ctx = headless_context();
+        let mut state = LoggingState::default();
+
+        input::start_recording(&mut ctx);
+        simulate_session(&mut ctx, &mut state);
+        let bytes = input::stop_recording(&mut ctx).unwrap().to_bytes();
+
+        assert!(InputRecording::from_bytes(&bytes).is_ok());
+
+        for len in 0..bytes.len() {
+            assert!(matches!(
+                InputRecording::from_bytes(&bytes[..len]),
+                Err(TetraError::InvalidInputRecording)
+            ));
+        }
+
+        assert!(matches!(
+            InputRecording::from_bytes(b"not a recording"),
+            Err(TetraError::InvalidInputRecording)
+        ));
+    }
+
+    #[test]
+    fn huge_gamepad_ids_are_rejected() {
+        let ctx = headless_context();
+
+        let recording = |id| InputRecording {
+            initial: InputSnapshot::capture(&ctx),
+            frames: vec![RecordedFrame {
+                elapsed: frame_time(),
+                events: vec![InputEvent::GamepadAdded { id }],
+            }],
+        };
+
+        assert!(InputRecording::from_bytes(&recording(MAX_GAMEPADS - 1).to_bytes()).is_ok());
+
+        for id in [MAX_GAMEPADS, usize::MAX / 2, usize::MAX] {
+            assert!(matches!(
+                InputRecording::from_bytes(&recording(id).to_bytes()),
+                Err(TetraError::InvalidInputRecording)
+            ));
+        }
+    }
+}
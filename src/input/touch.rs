@@ -0,0 +1,143 @@
+use crate::math::Vec2;
+use crate::Context;
+
+/// A finger (or stylus) that is currently touching the screen.
+///
+/// Touch IDs are assigned when a touch starts, and are unique across all touch
+/// devices, so touches on two different screens will never share an ID.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Touch {
+    /// The ID of the touch.
+    pub id: u64,
+
+    /// The position of the touch, in window co-ordinates.
+    pub position: Vec2<f32>,
+
+    /// How hard the screen is being pressed, from `0.0` to `1.0`.
+    ///
+    /// Devices that can't detect pressure will always report `1.0`.
+    pub pressure: f32,
+}
+
+/// Returns the touches that are currently active, in the order that they started.
+pub fn get_touches(ctx: &Context) -> &[Touch] {
+    &ctx.input.touches
+}
+
+/// Returns the touches that ended since the last update, with their final positions.
+pub fn get_ended_touches(ctx: &Context) -> &[Touch] {
+    &ctx.input.touches_ended
+}
+
+/// Returns the number of touches that are currently active.
+pub fn get_touch_count(ctx: &Context) -> usize {
+    ctx.input.touches.len()
+}
+
+/// Returns the touch with the specified ID, if it is currently active.
+pub fn get_touch(ctx: &Context, id: u64) -> Option<Touch> {
+    ctx.input.touches.iter().find(|t| t.id == id).copied()
+}
+
+/// Returns the primary touch, if there is one.
+///
+/// The primary touch is the first one that started while no other touches were
+/// active. If it ends while other touches are still active, there will be no
+/// primary touch until they have all ended.
+pub fn get_primary_touch(ctx: &Context) -> Option<Touch> {
+    ctx.input.primary_touch.and_then(|id| get_touch(ctx, id))
+}
+
+/// Returns true if the touch with the specified ID is currently active.
+pub fn is_touch_down(ctx: &Context, id: u64) -> bool {
+    get_touch(ctx, id).is_some()
+}
+
+/// Returns true if the touch with the specified ID started since the last update.
+pub fn is_touch_started(ctx: &Context, id: u64) -> bool {
+    ctx.input.touches_started.contains(&id)
+}
+
+/// Returns true if the touch with the specified ID ended since the last update.
+pub fn is_touch_ended(ctx: &Context, id: u64) -> bool {
+    ctx.input.touches_ended.iter().any(|t| t.id == id)
+}
+
+/// Returns true if any touch started since the last update.
+pub fn is_any_touch_started(ctx: &Context) -> bool {
+    !ctx.input.touches_started.is_empty()
+}
+
+/// Get the position of the touch with the specified ID, if it is currently active.
+pub fn get_touch_position(ctx: &Context, id: u64) -> Option<Vec2<f32>> {
+    get_touch(ctx, id).map(|t| t.position)
+}
+
+/// Get the pressure of the touch with the specified ID, if it is currently active.
+pub fn get_touch_pressure(ctx: &Context, id: u64) -> Option<f32> {
+    get_touch(ctx, id).map(|t| t.pressure)
+}
+
+/// Returns true if mouse events are being generated from the primary touch.
+pub fn is_touch_mouse_emulation_enabled(ctx: &Context) -> bool {
+    ctx.input.touch_mouse_emulation
+}
+
+/// Sets whether mouse events should be generated from the primary touch.
+///
+/// While this is enabled, the primary touch moves the mouse, and holds down the
+/// left mouse button. This allows games that only handle mouse input to be played
+/// on a touchscreen. Touch events will still be fired as normal.
+///
+/// This can also be set via [`ContextBuilder::touch_mouse_emulation`](crate::ContextBuilder::touch_mouse_emulation).
+pub fn set_touch_mouse_emulation(ctx: &mut Context, enabled: bool) {
+    ctx.input.touch_mouse_emulation = enabled;
+}
+
+/// Starts a touch. Returns true if it became the primary touch.
+pub(crate) fn start_touch(ctx: &mut Context, touch: Touch) -> bool {
+    let primary = ctx.input.touches.is_empty();
+
+    if primary {
+        ctx.input.primary_touch = Some(touch.id);
+    }
+
+    ctx.input.touches.retain(|t| t.id != touch.id);
+    ctx.input.touches.push(touch);
+    ctx.input.touches_started.insert(touch.id);
+
+    primary
+}
+
+/// Moves an active touch, returning how far it moved.
+pub(crate) fn move_touch(
+    ctx: &mut Context,
+    id: u64,
+    position: Vec2<f32>,
+    pressure: f32,
+) -> Option<Vec2<f32>> {
+    let touch = ctx.input.touches.iter_mut().find(|t| t.id == id)?;
+    let delta = position - touch.position;
+
+    touch.position = position;
+    touch.pressure = pressure;
+
+    Some(delta)
+}
+
+/// Ends an active touch, returning it.
+pub(crate) fn end_touch(ctx: &mut Context, id: u64, position: Vec2<f32>) -> Option<Touch> {
+    let index = ctx.input.touches.iter().position(|t| t.id == id)?;
+    let mut touch = ctx.input.touches.remove(index);
+
+    touch.position = position;
+
+    if ctx.input.primary_touch == Some(id) {
+        ctx.input.primary_touch = None;
+    }
+
+    ctx.input.touches_ended.push(touch);
+
+    Some(touch)
+}
@@ -73,13 +73,16 @@
 pub mod audio;
 mod context;
 pub mod error;
-mod fs;
+pub mod fs;
 pub mod graphics;
 pub mod input;
 mod lifecycle;
+pub mod loading;
 pub mod math;
 mod platform;
+pub mod scene;
 pub mod time;
+pub mod tween;
 pub mod window;
 
 pub use crate::context::{Context, ContextBuilder};
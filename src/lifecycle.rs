@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use crate::input::{GamepadAxis, GamepadButton, GamepadStick, Key, MouseButton};
 use crate::math::Vec2;
@@ -127,6 +128,42 @@ pub enum Event {
         amount: Vec2<i32>,
     },
 
+    /// A finger touched the screen.
+    TouchStarted {
+        /// The ID of the touch.
+        id: u64,
+
+        /// The position of the touch, in window co-ordinates.
+        position: Vec2<f32>,
+
+        /// How hard the screen is being pressed, from `0.0` to `1.0`.
+        pressure: f32,
+    },
+
+    /// A finger moved while touching the screen.
+    TouchMoved {
+        /// The ID of the touch.
+        id: u64,
+
+        /// The new position of the touch, in window co-ordinates.
+        position: Vec2<f32>,
+
+        /// The movement of the touch, relative to its previous position.
+        delta: Vec2<f32>,
+
+        /// How hard the screen is being pressed, from `0.0` to `1.0`.
+        pressure: f32,
+    },
+
+    /// A finger was lifted from the screen.
+    TouchEnded {
+        /// The ID of the touch.
+        id: u64,
+
+        /// The final position of the touch, in window co-ordinates.
+        position: Vec2<f32>,
+    },
+
     /// A gamepad was connected to the system.
     GamepadAdded {
         /// The ID that was assigned to the gamepad.
@@ -199,4 +236,36 @@ pub enum Event {
         /// The path of the file that was dropped.
         path: PathBuf,
     },
+
+    /// An asset was reloaded, as the file that it was loaded from changed.
+    ///
+    /// This event will only be fired if [`hot_reload`](crate::ContextBuilder::hot_reload)
+    /// is enabled.
+    AssetReloaded {
+        /// The path of the file that changed.
+        path: PathBuf,
+    },
+
+    /// An asset could not be reloaded after the file that it was loaded from changed.
+    /// The asset will keep its previous data.
+    ///
+    /// This event will only be fired if [`hot_reload`](crate::ContextBuilder::hot_reload)
+    /// is enabled.
+    AssetReloadFailed {
+        /// The path of the file that changed.
+        path: PathBuf,
+
+        /// The error that occurred while reloading the asset.
+        error: Arc<TetraError>,
+    },
+
+    /// The user asked for the game to close - for example, by clicking the window's
+    /// close button, or by pressing Escape while
+    /// [`quit_on_escape`](crate::ContextBuilder::quit_on_escape) is enabled.
+    ///
+    /// By default, the game will close at the end of the current iteration of the
+    /// game loop. This can be prevented by calling [`window::cancel_quit`](crate::window::cancel_quit)
+    /// while handling this event (for example, if you want to ask the player to save
+    /// their game first).
+    QuitRequested,
 }
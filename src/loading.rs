@@ -0,0 +1,442 @@
+//! Functions and types relating to loading assets in the background.
+//!
+//! Loading assets via functions like [`Texture::new`](crate::graphics::Texture::new) blocks the game until the file
+//! has been read and decoded, which can cause the window to freeze if you load a lot
+//! of assets at once. An [`AssetLoader`] instead reads and decodes files on a pool of
+//! worker threads, leaving only the final step (e.g. uploading a texture to the GPU)
+//! to be done on the main thread. This lets you keep drawing while assets are loading -
+//! for example, to display an animated loading screen.
+//!
+//! Assets are loaded via the same virtual filesystem as the rest of Tetra, which is
+//! shared with the worker threads. This means that [mounts](crate::fs::mount) made after
+//! the loader was created will also be used, as long as they are made before the worker
+//! reads the asset - to be sure that a mount applies, make it before requesting the load.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt::{self, Debug, Formatter};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use hashbrown::HashMap;
+
+#[cfg(feature = "audio")]
+use crate::audio::Sound;
+use crate::error::{Result, TetraError};
+use crate::graphics::text::{BmFontBuilder, Font};
+use crate::graphics::{ImageData, Texture};
+use crate::Context;
+
+#[cfg(feature = "font_ttf")]
+use crate::graphics::text::{self, VectorFontBuilder};
+
+/// The maximum number of worker threads that a loader will spawn.
+const MAX_WORKERS: usize = 4;
+
+type Decoded = Box<dyn Any + Send>;
+type Upload = Box<dyn FnOnce(&mut Context, Decoded) -> Result>;
+
+struct Job {
+    id: usize,
+    decode: Box<dyn FnOnce() -> Result<Decoded> + Send>,
+}
+
+/// Loads assets in the background.
+///
+/// Each of the loading methods returns an [`AssetHandle`] straight away, which will
+/// be filled in once the asset has finished loading. Finished assets are only made
+/// available when [`update`](AssetLoader::update) is called, so you should call it
+/// once per frame (usually from your [`State::update`](crate::State::update) method)
+/// until [`is_finished`](AssetLoader::is_finished) returns `true`.
+///
+/// Dropping the loader will cancel any assets that have not started loading yet.
+///
+/// # Examples
+///
+/// The [`loading_screen`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/loading_screen.rs)
+/// example demonstrates how to draw a progress bar while assets load in the background.
+pub struct AssetLoader {
+    jobs: Sender<Job>,
+    results: Receiver<(usize, Result<Decoded>)>,
+    cancelled: Arc<AtomicBool>,
+
+    uploads: HashMap<usize, Upload>,
+    next_id: usize,
+    total: usize,
+    completed: usize,
+}
+
+impl AssetLoader {
+    /// Creates a new loader, spawning its worker threads.
+    ///
+    /// # Errors
+    ///
+    /// * [`TetraError::PlatformError`] will be returned if the worker threads could
+    /// not be spawned.
+    pub fn new(ctx: &Context) -> Result<AssetLoader> {
+        let (job_sender, job_receiver) = mpsc::channel::<Job>();
+        let (result_sender, result_receiver) = mpsc::channel();
+
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let workers = thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(MAX_WORKERS);
+
+        for _ in 0..workers {
+            let fs = Arc::clone(&ctx.fs);
+            let jobs = Arc::clone(&job_receiver);
+            let results = result_sender.clone();
+            let cancelled = Arc::clone(&cancelled);
+
+            thread::Builder::new()
+                .name("tetra-asset-loader".into())
+                .spawn(move || {
+                    // Loading functions that don't take a Context look up the filesystem
+                    // via the current thread, so the workers need to share ours.
+                    fs.activate();
+
+                    loop {
+                        // The lock needs to be released before the job runs, so that
+                        // the other workers can pick up jobs in the meantime.
+                        let job = match jobs.lock().unwrap().recv() {
+                            Ok(job) => job,
+                            Err(_) => break,
+                        };
+
+                        if cancelled.load(Ordering::Relaxed) {
+                            break;
+                        }
+
+                        if results.send((job.id, (job.decode)())).is_err() {
+                            break;
+                        }
+                    }
+                })
+                .map_err(|e| TetraError::PlatformError(e.to_string()))?;
+        }
+
+        Ok(AssetLoader {
+            jobs: job_sender,
+            results: result_receiver,
+            cancelled,
+
+            uploads: HashMap::new(),
+            next_id: 0,
+            total: 0,
+            completed: 0,
+        })
+    }
+
+    /// Loads a texture from the given file.
+    ///
+    /// The image is decoded in the background, and then uploaded to the GPU by
+    /// [`update`](AssetLoader::update).
+    pub fn texture<P>(&mut self, path: P) -> AssetHandle<Texture>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_owned();
+
+        self.load(
+            {
+                let path = path.clone();
+                move || ImageData::new(path)
+            },
+            move |ctx, image| {
+                let texture = Texture::from_image_data(ctx, &image)?;
+                texture.watch(ctx, &path);
+
+                Ok(texture)
+            },
+        )
+    }
+
+    /// Loads a sound from the given file.
+    #[cfg(feature = "audio")]
+    pub fn sound<P>(&mut self, path: P) -> AssetHandle<Sound>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_owned();
+
+        self.load(move || Sound::new(path), |_, sound| Ok(sound))
+    }
+
+    /// Loads a vector font from the given file, with the given size.
+    ///
+    /// The font is parsed in the background, and then its GPU cache is created by
+    /// [`update`](AssetLoader::update).
+    #[cfg(feature = "font_ttf")]
+    pub fn vector_font<P>(&mut self, path: P, size: f32) -> AssetHandle<Font>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_owned();
+
+        self.load(
+            {
+                let path = path.clone();
+                move || text::load_font_vec(path)
+            },
+            move |ctx, font| {
+                let font = VectorFontBuilder::from_font_vec(font).with_size(ctx, size)?;
+                font.watch_vector(ctx, &path, size);
+
+                Ok(font)
+            },
+        )
+    }
+
+    /// Loads a font from the given AngelCode BMFont file.
+    ///
+    /// The font's images will be loaded relative to the font itself, as with
+    /// [`Font::bmfont`].
+    pub fn bmfont<P>(&mut self, path: P) -> AssetHandle<Font>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_owned();
+
+        self.load(
+            {
+                let path = path.clone();
+                move || {
+                    let builder = BmFontBuilder::new(path)?;
+                    let page_paths = builder.page_paths()?;
+
+                    Ok((builder.into_rasterizer()?, page_paths))
+                }
+            },
+            move |ctx, (rasterizer, page_paths)| {
+                let font = Font::from_rasterizer(ctx, Box::new(rasterizer))?;
+                font.watch_bmfont(ctx, &path, page_paths);
+
+                Ok(font)
+            },
+        )
+    }
+
+    /// Makes any assets that have finished loading available via their handles.
+    ///
+    /// # Errors
+    ///
+    /// If an asset failed to load, the error will be returned (for example,
+    /// [`TetraError::FailedToLoadAsset`] if the file could not be found). The failed asset
+    /// still counts towards the loader's progress, and any other assets will keep loading.
+    pub fn update(&mut self, ctx: &mut Context) -> Result {
+        while let Ok((id, decoded)) = self.results.try_recv() {
+            self.completed += 1;
+
+            let upload = self
+                .uploads
+                .remove(&id)
+                .expect("every job should have an upload");
+
+            upload(ctx, decoded?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the proportion of assets that have finished loading, from `0.0` to `1.0`.
+    ///
+    /// If no assets have been requested, this will return `1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.completed as f32 / self.total as f32
+        }
+    }
+
+    /// Returns `true` if every asset that has been requested has finished loading.
+    pub fn is_finished(&self) -> bool {
+        self.completed == self.total
+    }
+
+    fn load<T, D, F, U>(&mut self, decode: F, upload: U) -> AssetHandle<T>
+    where
+        T: 'static,
+        D: Send + 'static,
+        F: FnOnce() -> Result<D> + Send + 'static,
+        U: FnOnce(&mut Context, D) -> Result<T> + 'static,
+    {
+        let id = self.next_id;
+        let handle = AssetHandle {
+            asset: Rc::new(RefCell::new(None)),
+        };
+
+        let asset = Rc::clone(&handle.asset);
+
+        self.uploads.insert(
+            id,
+            Box::new(move |ctx, decoded| {
+                let decoded = decoded
+                    .downcast::<D>()
+                    .expect("decoded data should match the asset type");
+
+                *asset.borrow_mut() = Some(upload(ctx, *decoded)?);
+
+                Ok(())
+            }),
+        );
+
+        // The workers only stop once the loader has been dropped, so this can't fail.
+        let _ = self.jobs.send(Job {
+            id,
+            decode: Box::new(move || decode().map(|d| Box::new(d) as Decoded)),
+        });
+
+        self.next_id += 1;
+        self.total += 1;
+
+        handle
+    }
+}
+
+impl Drop for AssetLoader {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Debug for AssetLoader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssetLoader")
+            .field("total", &self.total)
+            .field("completed", &self.completed)
+            .finish()
+    }
+}
+
+/// A handle to an asset that is being loaded by an [`AssetLoader`].
+///
+/// You can clone a handle cheaply, as it is [reference-counted](https://doc.rust-lang.org/std/rc/struct.Rc.html)
+/// internally - all clones will see the asset once it has loaded.
+pub struct AssetHandle<T> {
+    asset: Rc<RefCell<Option<T>>>,
+}
+
+impl<T> AssetHandle<T> {
+    /// Returns `true` if the asset has finished loading.
+    pub fn is_ready(&self) -> bool {
+        self.asset.borrow().is_some()
+    }
+}
+
+impl<T> AssetHandle<T>
+where
+    T: Clone,
+{
+    /// Returns the asset, if it has finished loading.
+    pub fn get(&self) -> Option<T> {
+        self.asset.borrow().clone()
+    }
+}
+
+impl<T> Clone for AssetHandle<T> {
+    fn clone(&self) -> AssetHandle<T> {
+        AssetHandle {
+            asset: Rc::clone(&self.asset),
+        }
+    }
+}
+
+impl<T> Debug for AssetHandle<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssetHandle")
+            .field("asset", &self.asset.borrow())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::process;
+    use std::time::{Duration, Instant};
+
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+    use crate::ContextBuilder;
+
+    /// Updates the loader until everything has loaded, returning any errors.
+    fn finish(loader: &mut AssetLoader, ctx: &mut Context) -> Vec<TetraError> {
+        let start = Instant::now();
+        let mut errors = Vec::new();
+
+        while !loader.is_finished() {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "loading timed out"
+            );
+
+            if let Err(e) = loader.update(ctx) {
+                errors.push(e);
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        errors
+    }
+
+    #[test]
+    fn assets_load_in_the_background() {
+        let dir = std::env::temp_dir().join(format!("tetra-loading-{}", process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        RgbaImage::from_pixel(3, 2, Rgba([255, 255, 255, 255]))
+            .save(dir.join("texture.png"))
+            .unwrap();
+
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        // The workers should see the same mounts as the main thread.
+        crate::fs::mount(&mut ctx, &dir, 0).unwrap();
+
+        let mut loader = AssetLoader::new(&ctx).unwrap();
+        assert!(loader.is_finished());
+
+        let texture = loader.texture("texture.png");
+        let missing = loader.texture("missing.png");
+        let bmfont = loader.bmfont("./examples/resources/DejaVuSansMono.fnt");
+
+        #[cfg(feature = "font_ttf")]
+        let vector_font = loader.vector_font("./examples/resources/DejaVuSansMono.ttf", 16.0);
+
+        assert!(!texture.is_ready());
+        assert!(!loader.is_finished());
+
+        let errors = finish(&mut loader, &mut ctx);
+
+        assert!(matches!(
+            errors.as_slice(),
+            [TetraError::FailedToLoadAsset { path, .. }] if path.as_path() == Path::new("missing.png")
+        ));
+
+        assert_eq!(loader.progress(), 1.0);
+        assert_eq!(texture.get().unwrap().size(), (3, 2));
+        assert!(!missing.is_ready());
+        assert!(bmfont.is_ready());
+
+        #[cfg(feature = "font_ttf")]
+        assert!(vector_font.is_ready());
+    }
+}
@@ -1,8 +1,299 @@
 mod device_gl;
+mod device_null;
+mod window_null;
 mod window_sdl;
 
+use std::result;
+
+use glow::Context as GlowContext;
+
 pub use device_gl::{
     GraphicsDevice, RawCanvas, RawIndexBuffer, RawRenderbuffer, RawShader, RawTexture,
     RawVertexBuffer,
 };
-pub use window_sdl::{handle_events, Window};
+
+use crate::error::{Result, TetraError};
+use crate::graphics::ImageData;
+#[cfg(test)]
+use crate::input::InputEvent;
+use crate::input::{GamepadType, Key, KeyLabel};
+use crate::window::WindowPosition;
+use crate::{Context, ContextBuilder, State};
+
+/// The platform window that a `Context` is attached to.
+///
+/// Most games will use an SDL window, but headless contexts (e.g. for automated
+/// tests) use a null implementation which only keeps track of its own state.
+pub enum Window {
+    Sdl(window_sdl::Window),
+    Null(window_null::NullWindow),
+}
+
+macro_rules! dispatch {
+    ($self:expr, $window:ident => $body:expr) => {
+        match $self {
+            Window::Sdl($window) => $body,
+            Window::Null($window) => $body,
+        }
+    };
+}
+
+impl Window {
+    pub fn new(settings: &ContextBuilder) -> Result<(Window, GlowContext, i32, i32)> {
+        let (window, gl_context, window_width, window_height) = window_sdl::Window::new(settings)?;
+
+        Ok((Window::Sdl(window), gl_context, window_width, window_height))
+    }
+
+    pub fn new_null(settings: &ContextBuilder) -> (Window, i32, i32) {
+        let (window, window_width, window_height) = window_null::NullWindow::new(settings);
+
+        (Window::Null(window), window_width, window_height)
+    }
+
+    pub fn add_gamepad_mappings(&mut self, mappings: &str) -> Result<usize> {
+        dispatch!(self, w => w.add_gamepad_mappings(mappings))
+    }
+
+    pub fn maximize(&mut self) {
+        dispatch!(self, w => w.maximize())
+    }
+
+    pub fn minimize(&mut self) {
+        dispatch!(self, w => w.minimize())
+    }
+
+    pub fn restore(&mut self) {
+        dispatch!(self, w => w.restore())
+    }
+
+    pub fn focus(&mut self) {
+        dispatch!(self, w => w.focus())
+    }
+
+    pub fn is_focused(&self) -> bool {
+        dispatch!(self, w => w.is_focused())
+    }
+
+    pub fn is_minimized(&self) -> bool {
+        dispatch!(self, w => w.is_minimized())
+    }
+
+    pub fn get_refresh_rate(&self) -> Result<i32> {
+        dispatch!(self, w => w.get_refresh_rate())
+    }
+
+    pub fn get_window_title(&self) -> &str {
+        dispatch!(self, w => w.get_window_title())
+    }
+
+    pub fn set_window_title<S>(&mut self, title: S)
+    where
+        S: AsRef<str>,
+    {
+        dispatch!(self, w => w.set_window_title(title))
+    }
+
+    pub fn get_window_size(&self) -> (i32, i32) {
+        dispatch!(self, w => w.get_window_size())
+    }
+
+    pub fn get_physical_size(&self) -> (i32, i32) {
+        dispatch!(self, w => w.get_physical_size())
+    }
+
+    pub fn set_window_size(&mut self, width: i32, height: i32) -> Result {
+        dispatch!(self, w => w.set_window_size(width, height))
+    }
+
+    pub fn set_minimum_size(&mut self, width: i32, height: i32) -> Result {
+        dispatch!(self, w => w.set_minimum_size(width, height))
+    }
+
+    pub fn get_minimum_size(&self) -> (i32, i32) {
+        dispatch!(self, w => w.get_minimum_size())
+    }
+
+    pub fn set_maximum_size(&mut self, width: i32, height: i32) -> Result {
+        dispatch!(self, w => w.set_maximum_size(width, height))
+    }
+
+    pub fn get_maximum_size(&self) -> (i32, i32) {
+        dispatch!(self, w => w.get_maximum_size())
+    }
+
+    pub fn set_position(&mut self, x: WindowPosition, y: WindowPosition) {
+        dispatch!(self, w => w.set_position(x, y))
+    }
+
+    pub fn get_position(&self) -> (i32, i32) {
+        dispatch!(self, w => w.get_position())
+    }
+
+    pub fn set_decorated(&mut self, bordered: bool) {
+        dispatch!(self, w => w.set_decorated(bordered))
+    }
+
+    pub fn set_icon(&mut self, data: &mut ImageData) -> Result {
+        dispatch!(self, w => w.set_icon(data))
+    }
+
+    pub fn is_visible(&self) -> bool {
+        dispatch!(self, w => w.is_visible())
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        dispatch!(self, w => w.set_visible(visible))
+    }
+
+    pub fn get_dpi_scale(&self) -> f32 {
+        dispatch!(self, w => w.get_dpi_scale())
+    }
+
+    pub fn get_monitor_count(&self) -> Result<i32> {
+        dispatch!(self, w => w.get_monitor_count())
+    }
+
+    pub fn get_monitor_name(&self, monitor_index: i32) -> Result<String> {
+        dispatch!(self, w => w.get_monitor_name(monitor_index))
+    }
+
+    pub fn get_monitor_size(&self, monitor_index: i32) -> Result<(i32, i32)> {
+        dispatch!(self, w => w.get_monitor_size(monitor_index))
+    }
+
+    pub fn get_current_monitor(&self) -> Result<i32> {
+        dispatch!(self, w => w.get_current_monitor())
+    }
+
+    pub fn set_vsync(&mut self, vsync: bool) -> Result {
+        dispatch!(self, w => w.set_vsync(vsync))
+    }
+
+    pub fn is_vsync_enabled(&self) -> bool {
+        dispatch!(self, w => w.is_vsync_enabled())
+    }
+
+    pub fn set_fullscreen(&mut self, fullscreen: bool) -> Result {
+        dispatch!(self, w => w.set_fullscreen(fullscreen))
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        dispatch!(self, w => w.is_fullscreen())
+    }
+
+    pub fn set_mouse_visible(&mut self, mouse_visible: bool) -> Result {
+        dispatch!(self, w => w.set_mouse_visible(mouse_visible))
+    }
+
+    pub fn is_mouse_visible(&self) -> bool {
+        dispatch!(self, w => w.is_mouse_visible())
+    }
+
+    pub fn set_mouse_grabbed(&mut self, mouse_grabbed: bool) {
+        dispatch!(self, w => w.set_mouse_grabbed(mouse_grabbed))
+    }
+
+    pub fn is_mouse_grabbed(&self) -> bool {
+        dispatch!(self, w => w.is_mouse_grabbed())
+    }
+
+    pub fn set_relative_mouse_mode(&mut self, relative_mouse_mode: bool) {
+        dispatch!(self, w => w.set_relative_mouse_mode(relative_mouse_mode))
+    }
+
+    pub fn is_relative_mouse_mode(&self) -> bool {
+        dispatch!(self, w => w.is_relative_mouse_mode())
+    }
+
+    pub fn get_clipboard_text(&self) -> Result<String> {
+        dispatch!(self, w => w.get_clipboard_text())
+    }
+
+    pub fn set_clipboard_text(&self, text: &str) -> Result {
+        dispatch!(self, w => w.set_clipboard_text(text))
+    }
+
+    pub fn swap_buffers(&self) {
+        dispatch!(self, w => w.swap_buffers())
+    }
+
+    pub fn get_gamepad_name(&self, platform_id: u32) -> String {
+        dispatch!(self, w => w.get_gamepad_name(platform_id))
+    }
+
+    pub fn get_gamepad_type(&self, platform_id: u32) -> GamepadType {
+        dispatch!(self, w => w.get_gamepad_type(platform_id))
+    }
+
+    pub fn get_gamepad_guid(&self, platform_id: u32) -> Option<String> {
+        dispatch!(self, w => w.get_gamepad_guid(platform_id))
+    }
+
+    pub fn get_gamepad_vendor_id(&self, platform_id: u32) -> Option<u16> {
+        dispatch!(self, w => w.get_gamepad_vendor_id(platform_id))
+    }
+
+    pub fn get_gamepad_product_id(&self, platform_id: u32) -> Option<u16> {
+        dispatch!(self, w => w.get_gamepad_product_id(platform_id))
+    }
+
+    pub fn is_gamepad_vibration_supported(&self, platform_id: u32) -> bool {
+        dispatch!(self, w => w.is_gamepad_vibration_supported(platform_id))
+    }
+
+    pub fn set_gamepad_vibration(&mut self, platform_id: u32, strength: f32) {
+        dispatch!(self, w => w.set_gamepad_vibration(platform_id, strength))
+    }
+
+    pub fn start_gamepad_vibration(&mut self, platform_id: u32, strength: f32, duration: u32) {
+        dispatch!(self, w => w.start_gamepad_vibration(platform_id, strength, duration))
+    }
+
+    pub fn stop_gamepad_vibration(&mut self, platform_id: u32) {
+        dispatch!(self, w => w.stop_gamepad_vibration(platform_id))
+    }
+
+    pub fn set_screen_saver_enabled(&self, screen_saver_enabled: bool) {
+        dispatch!(self, w => w.set_screen_saver_enabled(screen_saver_enabled))
+    }
+
+    pub fn is_screen_saver_enabled(&self) -> bool {
+        dispatch!(self, w => w.is_screen_saver_enabled())
+    }
+
+    pub fn set_key_repeat_enabled(&mut self, key_repeat: bool) {
+        dispatch!(self, w => w.set_key_repeat_enabled(key_repeat))
+    }
+
+    pub fn is_key_repeat_enabled(&self) -> bool {
+        dispatch!(self, w => w.is_key_repeat_enabled())
+    }
+
+    pub fn get_key_with_label(&self, key_label: KeyLabel) -> Option<Key> {
+        dispatch!(self, w => w.get_key_with_label(key_label))
+    }
+
+    pub fn get_key_label(&self, key: Key) -> Option<KeyLabel> {
+        dispatch!(self, w => w.get_key_label(key))
+    }
+
+    #[cfg(test)]
+    pub fn push_input_event(&mut self, event: InputEvent) {
+        match self {
+            Window::Null(w) => w.push_input_event(event),
+            Window::Sdl(_) => panic!("input can only be simulated for headless contexts"),
+        }
+    }
+}
+
+pub fn handle_events<S, E>(ctx: &mut Context, state: &mut S) -> result::Result<(), E>
+where
+    S: State<E>,
+    E: From<TetraError>,
+{
+    match ctx.window {
+        Window::Sdl(_) => window_sdl::handle_events(ctx, state),
+        Window::Null(_) => window_null::handle_events(ctx, state),
+    }
+}
@@ -3,7 +3,10 @@ use std::mem;
 use std::rc::Rc;
 use std::slice;
 
-use glow::{Context as GlowContext, HasContext, PixelPackData, PixelUnpackData};
+use glow::{
+    Context as GlowContext, HasContext, NativeBuffer, NativeFramebuffer, NativeProgram,
+    NativeRenderbuffer, NativeTexture, NativeVertexArray, PixelPackData, PixelUnpackData,
+};
 
 use crate::error::{Result, TetraError};
 use crate::graphics::{
@@ -11,10 +14,11 @@ use crate::graphics::{
     StencilState, StencilTest,
 };
 use crate::graphics::{
-    BlendFactor, BlendOperation, BlendState, Color, FilterMode, GraphicsDeviceInfo, StencilAction,
-    TextureFormat,
+    BlendFactor, BlendOperation, BlendState, Color, DrawCall, FilterMode, GraphicsDeviceInfo,
+    StencilAction, TextureFormat,
 };
 use crate::math::{Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
+use crate::platform::device_null::NullDevice;
 
 type BufferId = <GlowContext as HasContext>::Buffer;
 type ProgramId = <GlowContext as HasContext>::Program;
@@ -24,9 +28,19 @@ type RenderbufferId = <GlowContext as HasContext>::Renderbuffer;
 type VertexArrayId = <GlowContext as HasContext>::VertexArray;
 type UniformLocation = <GlowContext as HasContext>::UniformLocation;
 
+/// The number of texture units reported by the null device - this matches the
+/// minimum that OpenGL 3.2 guarantees.
+const NULL_TEXTURE_UNITS: usize = 16;
+
+#[derive(Debug)]
+enum Backend {
+    OpenGl(Box<GlowContext>),
+    Null(NullDevice),
+}
+
 #[derive(Debug)]
 struct GraphicsState {
-    gl: GlowContext,
+    backend: Backend,
 
     current_vertex_buffer: Cell<Option<BufferId>>,
     current_index_buffer: Cell<Option<BufferId>>,
@@ -42,6 +56,22 @@ struct GraphicsState {
     max_samples: u8,
 }
 
+impl GraphicsState {
+    fn gl(&self) -> Option<&GlowContext> {
+        match &self.backend {
+            Backend::OpenGl(gl) => Some(gl),
+            Backend::Null(_) => None,
+        }
+    }
+
+    fn null(&self) -> Option<&NullDevice> {
+        match &self.backend {
+            Backend::OpenGl(_) => None,
+            Backend::Null(null) => Some(null),
+        }
+    }
+}
+
 pub struct GraphicsDevice {
     state: Rc<GraphicsState>,
 }
@@ -78,7 +108,7 @@ impl GraphicsDevice {
             let max_samples = gl.get_parameter_i32(glow::MAX_SAMPLES) as u8;
 
             let state = GraphicsState {
-                gl,
+                backend: Backend::OpenGl(Box::new(gl)),
 
                 current_vertex_buffer: Cell::new(None),
                 current_index_buffer: Cell::new(None),
@@ -100,92 +130,146 @@ impl GraphicsDevice {
         }
     }
 
+    /// Creates a device that records draw calls instead of sending them to the GPU.
+    pub fn new_null() -> GraphicsDevice {
+        let null = NullDevice::new();
+
+        let vertex_array = NativeVertexArray(null.create_id());
+        let resolve_framebuffer = NativeFramebuffer(null.create_id());
+
+        let state = GraphicsState {
+            backend: Backend::Null(null),
+
+            current_vertex_buffer: Cell::new(None),
+            current_index_buffer: Cell::new(None),
+            current_program: Cell::new(None),
+            current_textures: vec![Cell::new(None); NULL_TEXTURE_UNITS],
+            current_read_framebuffer: Cell::new(None),
+            current_draw_framebuffer: Cell::new(None),
+            current_renderbuffer: Cell::new(None),
+
+            vertex_array,
+            resolve_framebuffer,
+
+            // Multisampling is meaningless when nothing is being rasterized.
+            max_samples: 0,
+        };
+
+        GraphicsDevice {
+            state: Rc::new(state),
+        }
+    }
+
     pub fn get_info(&self) -> GraphicsDeviceInfo {
-        unsafe {
-            GraphicsDeviceInfo {
-                vendor: self.state.gl.get_parameter_string(glow::VENDOR),
-                renderer: self.state.gl.get_parameter_string(glow::RENDERER),
-                opengl_version: self.state.gl.get_parameter_string(glow::VERSION),
-                glsl_version: self
-                    .state
-                    .gl
-                    .get_parameter_string(glow::SHADING_LANGUAGE_VERSION),
-            }
+        match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe {
+                GraphicsDeviceInfo {
+                    vendor: gl.get_parameter_string(glow::VENDOR),
+                    renderer: gl.get_parameter_string(glow::RENDERER),
+                    opengl_version: gl.get_parameter_string(glow::VERSION),
+                    glsl_version: gl.get_parameter_string(glow::SHADING_LANGUAGE_VERSION),
+                }
+            },
+
+            Backend::Null(_) => GraphicsDeviceInfo {
+                vendor: "Tetra".into(),
+                renderer: "Null Device".into(),
+                opengl_version: "N/A".into(),
+                glsl_version: "N/A".into(),
+            },
         }
     }
 
-    pub fn clear(&mut self, color: Color) {
-        unsafe {
-            self.state
-                .gl
-                .clear_color(color.r, color.g, color.b, color.a);
+    pub fn take_draw_calls(&self) -> Vec<DrawCall> {
+        self.state
+            .null()
+            .map(NullDevice::take_draw_calls)
+            .unwrap_or_default()
+    }
 
-            self.state.gl.clear(glow::COLOR_BUFFER_BIT);
+    pub fn clear(&mut self, color: Color) {
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.clear_color(color.r, color.g, color.b, color.a);
+                gl.clear(glow::COLOR_BUFFER_BIT);
+            }
         }
     }
 
     pub fn front_face(&mut self, front_face: VertexWinding) {
-        unsafe {
-            self.state.gl.front_face(front_face.to_gl_enum());
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.front_face(front_face.to_gl_enum());
+            }
         }
     }
 
     pub fn cull_face(&mut self, cull_face: bool) {
-        unsafe {
-            if cull_face {
-                self.state.gl.enable(glow::CULL_FACE);
-            } else {
-                self.state.gl.disable(glow::CULL_FACE);
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                if cull_face {
+                    gl.enable(glow::CULL_FACE);
+                } else {
+                    gl.disable(glow::CULL_FACE);
+                }
             }
         }
     }
 
     pub fn scissor(&mut self, x: i32, y: i32, width: i32, height: i32) {
-        unsafe { self.state.gl.scissor(x, y, width, height) }
+        if let Some(gl) = self.state.gl() {
+            unsafe { gl.scissor(x, y, width, height) }
+        }
     }
 
     pub fn scissor_test(&mut self, scissor_test: bool) {
-        unsafe {
-            if scissor_test {
-                self.state.gl.enable(glow::SCISSOR_TEST);
-            } else {
-                self.state.gl.disable(glow::SCISSOR_TEST);
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                if scissor_test {
+                    gl.enable(glow::SCISSOR_TEST);
+                } else {
+                    gl.disable(glow::SCISSOR_TEST);
+                }
             }
         }
     }
 
     pub fn set_stencil_state(&mut self, state: StencilState) {
-        unsafe {
-            if state.enabled {
-                self.state.gl.enable(glow::STENCIL_TEST);
-            } else {
-                self.state.gl.disable(glow::STENCIL_TEST);
-            }
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                if state.enabled {
+                    gl.enable(glow::STENCIL_TEST);
+                } else {
+                    gl.disable(glow::STENCIL_TEST);
+                }
 
-            self.state
-                .gl
-                .stencil_op(glow::KEEP, glow::KEEP, state.action.to_gl_enum());
+                gl.stencil_op(glow::KEEP, glow::KEEP, state.action.to_gl_enum());
 
-            self.state.gl.stencil_func(
-                state.test.to_gl_enum(),
-                state.reference_value.into(),
-                state.read_mask.into(),
-            );
+                gl.stencil_func(
+                    state.test.to_gl_enum(),
+                    state.reference_value.into(),
+                    state.read_mask.into(),
+                );
 
-            self.state.gl.stencil_mask(state.write_mask.into());
+                gl.stencil_mask(state.write_mask.into());
+            }
         }
     }
 
     pub fn clear_stencil(&mut self, value: u8) {
-        unsafe {
-            self.state.gl.clear_stencil(value.into());
-            self.state.gl.clear(glow::STENCIL_BUFFER_BIT);
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.clear_stencil(value.into());
+                gl.clear(glow::STENCIL_BUFFER_BIT);
+            }
         }
     }
 
     pub fn set_color_mask(&mut self, red: bool, green: bool, blue: bool, alpha: bool) {
-        unsafe {
-            self.state.gl.color_mask(red, green, blue, alpha);
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.color_mask(red, green, blue, alpha);
+            }
         }
     }
 
@@ -194,28 +278,27 @@ impl GraphicsDevice {
         count: usize,
         usage: BufferUsage,
     ) -> Result<RawVertexBuffer> {
-        unsafe {
-            let id = self
-                .state
-                .gl
-                .create_buffer()
-                .map_err(TetraError::PlatformError)?;
-
-            let buffer = RawVertexBuffer {
-                state: Rc::clone(&self.state),
-                id,
-                count,
-            };
+        let id = match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe {
+                gl.create_buffer().map_err(TetraError::PlatformError)?
+            },
+            Backend::Null(null) => NativeBuffer(null.create_vertex_buffer(count)),
+        };
+
+        let buffer = RawVertexBuffer {
+            state: Rc::clone(&self.state),
+            id,
+            count,
+        };
 
-            self.bind_vertex_buffer(Some(buffer.id));
+        self.bind_vertex_buffer(Some(buffer.id));
 
+        if let Some(gl) = self.state.gl() {
             self.clear_errors();
 
-            self.state.gl.buffer_data_size(
-                glow::ARRAY_BUFFER,
-                buffer.size() as i32,
-                usage.to_gl_enum(),
-            );
+            unsafe {
+                gl.buffer_data_size(glow::ARRAY_BUFFER, buffer.size() as i32, usage.to_gl_enum());
+            }
 
             if let Some(e) = self.get_error() {
                 return Err(TetraError::PlatformError(format_gl_error(
@@ -223,9 +306,9 @@ impl GraphicsDevice {
                     e,
                 )));
             }
-
-            Ok(buffer)
         }
+
+        Ok(buffer)
     }
 
     pub fn set_vertex_buffer_data(
@@ -241,79 +324,65 @@ impl GraphicsDevice {
             "tried to write out of bounds buffer data"
         );
 
-        unsafe {
-            // TODO: What if we want to discard what's already there?
+        match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe {
+                // TODO: What if we want to discard what's already there?
 
-            self.state.gl.buffer_sub_data_u8_slice(
-                glow::ARRAY_BUFFER,
-                (buffer.stride() * offset) as i32,
-                bytemuck::cast_slice(data),
-            );
+                gl.buffer_sub_data_u8_slice(
+                    glow::ARRAY_BUFFER,
+                    (buffer.stride() * offset) as i32,
+                    bytemuck::cast_slice(data),
+                );
+            },
+
+            Backend::Null(null) => null.set_vertex_buffer_data(buffer.id.0, data, offset),
         }
     }
 
-    fn set_vertex_attributes(&mut self, buffer: &RawVertexBuffer) {
+    fn set_vertex_attributes(&self, buffer: &RawVertexBuffer) {
         // TODO: This only works because we don't let the user set custom
         // attribute bindings - will need a rethink at that point!
-        unsafe {
-            self.bind_vertex_buffer(Some(buffer.id));
-
-            self.state.gl.vertex_attrib_pointer_f32(
-                0,
-                2,
-                glow::FLOAT,
-                false,
-                buffer.stride() as i32,
-                0,
-            );
+        self.bind_vertex_buffer(Some(buffer.id));
 
-            self.state.gl.vertex_attrib_pointer_f32(
-                1,
-                2,
-                glow::FLOAT,
-                false,
-                buffer.stride() as i32,
-                8,
-            );
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.vertex_attrib_pointer_f32(0, 2, glow::FLOAT, false, buffer.stride() as i32, 0);
+                gl.vertex_attrib_pointer_f32(1, 2, glow::FLOAT, false, buffer.stride() as i32, 8);
+                gl.vertex_attrib_pointer_f32(2, 4, glow::FLOAT, false, buffer.stride() as i32, 16);
 
-            self.state.gl.vertex_attrib_pointer_f32(
-                2,
-                4,
-                glow::FLOAT,
-                false,
-                buffer.stride() as i32,
-                16,
-            );
-
-            self.state.gl.enable_vertex_attrib_array(0);
-            self.state.gl.enable_vertex_attrib_array(1);
-            self.state.gl.enable_vertex_attrib_array(2);
+                gl.enable_vertex_attrib_array(0);
+                gl.enable_vertex_attrib_array(1);
+                gl.enable_vertex_attrib_array(2);
+            }
         }
     }
 
     pub fn new_index_buffer(&mut self, count: usize, usage: BufferUsage) -> Result<RawIndexBuffer> {
-        unsafe {
-            let id = self
-                .state
-                .gl
-                .create_buffer()
-                .map_err(TetraError::PlatformError)?;
-
-            let buffer = RawIndexBuffer {
-                state: Rc::clone(&self.state),
-                id,
-                count,
-            };
+        let id = match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe {
+                gl.create_buffer().map_err(TetraError::PlatformError)?
+            },
+            Backend::Null(null) => NativeBuffer(null.create_index_buffer(count)),
+        };
+
+        let buffer = RawIndexBuffer {
+            state: Rc::clone(&self.state),
+            id,
+            count,
+        };
 
-            self.bind_index_buffer(Some(buffer.id));
+        self.bind_index_buffer(Some(buffer.id));
 
+        if let Some(gl) = self.state.gl() {
             self.clear_errors();
 
-            self.state.gl.buffer_data_size(
-                glow::ELEMENT_ARRAY_BUFFER,
-                buffer.size() as i32,
-                usage.to_gl_enum(),
-            );
+            unsafe {
+                gl.buffer_data_size(
+                    glow::ELEMENT_ARRAY_BUFFER,
+                    buffer.size() as i32,
+                    usage.to_gl_enum(),
+                );
+            }
 
             if let Some(e) = self.get_error() {
                 return Err(TetraError::PlatformError(format_gl_error(
@@ -321,9 +390,9 @@ impl GraphicsDevice {
                     e,
                 )));
             }
-
-            Ok(buffer)
         }
+
+        Ok(buffer)
     }
 
     pub fn set_index_buffer_data(&mut self, buffer: &RawIndexBuffer, data: &[u32], offset: usize) {
@@ -334,89 +403,75 @@ impl GraphicsDevice {
             "tried to write out of bounds buffer data"
         );
 
-        unsafe {
-            // TODO: What if we want to discard what's already there?
+        match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe {
+                // TODO: What if we want to discard what's already there?
 
-            self.state.gl.buffer_sub_data_u8_slice(
-                glow::ELEMENT_ARRAY_BUFFER,
-                (buffer.stride() * offset) as i32,
-                bytemuck::cast_slice(data),
-            );
+                gl.buffer_sub_data_u8_slice(
+                    glow::ELEMENT_ARRAY_BUFFER,
+                    (buffer.stride() * offset) as i32,
+                    bytemuck::cast_slice(data),
+                );
+            },
+
+            Backend::Null(null) => null.set_index_buffer_data(buffer.id.0, data, offset),
         }
     }
 
     pub fn new_shader(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<RawShader> {
-        unsafe {
-            let program_id = self
-                .state
-                .gl
-                .create_program()
-                .map_err(TetraError::PlatformError)?;
-
-            // TODO: IDK if this should be applied to *all* shaders...
-            self.state
-                .gl
-                .bind_attrib_location(program_id, 0, "a_position");
-            self.state.gl.bind_attrib_location(program_id, 1, "a_uv");
-            self.state.gl.bind_attrib_location(program_id, 2, "a_color");
-
-            let vertex_id = self
-                .state
-                .gl
-                .create_shader(glow::VERTEX_SHADER)
-                .map_err(TetraError::PlatformError)?;
-
-            self.state.gl.shader_source(vertex_id, vertex_shader);
-            self.state.gl.compile_shader(vertex_id);
-            self.state.gl.attach_shader(program_id, vertex_id);
+        let program_id = match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe { compile_program(gl, vertex_shader, fragment_shader)? },
 
-            if !self.state.gl.get_shader_compile_status(vertex_id) {
-                return Err(TetraError::InvalidShader(
-                    self.state.gl.get_shader_info_log(vertex_id),
-                ));
-            }
+            // The null device can't validate GLSL, so any source is accepted.
+            Backend::Null(null) => NativeProgram(null.create_id()),
+        };
 
-            let fragment_id = self
-                .state
-                .gl
-                .create_shader(glow::FRAGMENT_SHADER)
-                .map_err(TetraError::PlatformError)?;
+        let shader = RawShader {
+            state: Rc::clone(&self.state),
+            id: Cell::new(program_id),
+        };
 
-            self.state.gl.shader_source(fragment_id, fragment_shader);
-            self.state.gl.compile_shader(fragment_id);
-            self.state.gl.attach_shader(program_id, fragment_id);
+        let sampler_location = self.get_uniform_location(&shader, "u_texture");
+        self.set_uniform_i32(&shader, sampler_location.as_ref(), &[0]);
 
-            if !self.state.gl.get_shader_compile_status(fragment_id) {
-                return Err(TetraError::InvalidShader(
-                    self.state.gl.get_shader_info_log(fragment_id),
-                ));
-            }
+        Ok(shader)
+    }
 
-            self.state.gl.link_program(program_id);
+    /// Compiles a new program from the given source and swaps it into an existing shader.
+    ///
+    /// If compilation fails, the shader is left untouched. Note that the new program will
+    /// not inherit any uniform values from the old one.
+    pub fn replace_shader(
+        &mut self,
+        shader: &RawShader,
+        vertex_shader: &str,
+        fragment_shader: &str,
+    ) -> Result {
+        let program_id = match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe { compile_program(gl, vertex_shader, fragment_shader)? },
+            Backend::Null(null) => NativeProgram(null.create_id()),
+        };
 
-            if !self.state.gl.get_program_link_status(program_id) {
-                return Err(TetraError::InvalidShader(
-                    self.state.gl.get_program_info_log(program_id),
-                ));
-            }
+        let old_id = shader.id.replace(program_id);
 
-            self.state.gl.delete_shader(vertex_id);
-            self.state.gl.delete_shader(fragment_id);
+        if self.state.current_program.get() == Some(old_id) {
+            self.state.current_program.set(None);
+        }
 
-            let shader = RawShader {
-                state: Rc::clone(&self.state),
-                id: program_id,
-            };
+        if let Some(gl) = self.state.gl() {
+            unsafe { gl.delete_program(old_id) }
+        }
 
-            let sampler_location = self.get_uniform_location(&shader, "u_texture");
-            self.set_uniform_i32(&shader, sampler_location.as_ref(), &[0]);
+        let sampler_location = self.get_uniform_location(shader, "u_texture");
+        self.set_uniform_i32(shader, sampler_location.as_ref(), &[0]);
 
-            Ok(shader)
-        }
+        Ok(())
     }
 
     pub fn get_uniform_location(&self, shader: &RawShader, name: &str) -> Option<UniformLocation> {
-        unsafe { self.state.gl.get_uniform_location(shader.id, name) }
+        self.state
+            .gl()
+            .and_then(|gl| unsafe { gl.get_uniform_location(shader.id.get(), name) })
     }
 
     pub fn set_uniform_i32(
@@ -425,10 +480,12 @@ impl GraphicsDevice {
         location: Option<&UniformLocation>,
         values: &[i32],
     ) {
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
 
-        unsafe {
-            self.state.gl.uniform_1_i32_slice(location, values);
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.uniform_1_i32_slice(location, values);
+            }
         }
     }
 
@@ -438,10 +495,12 @@ impl GraphicsDevice {
         location: Option<&UniformLocation>,
         values: &[u32],
     ) {
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
 
-        unsafe {
-            self.state.gl.uniform_1_u32_slice(location, values);
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.uniform_1_u32_slice(location, values);
+            }
         }
     }
 
@@ -451,10 +510,12 @@ impl GraphicsDevice {
         location: Option<&UniformLocation>,
         values: &[f32],
     ) {
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
 
-        unsafe {
-            self.state.gl.uniform_1_f32_slice(location, values);
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.uniform_1_f32_slice(location, values);
+            }
         }
     }
 
@@ -464,13 +525,13 @@ impl GraphicsDevice {
         location: Option<&UniformLocation>,
         values: &[Vec2<f32>],
     ) {
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
 
-        unsafe {
-            // SAFETY: Type is aligned and has no padding.
-            self.state
-                .gl
-                .uniform_2_f32_slice(location, cast_slice_assume_aligned(values));
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                // SAFETY: Type is aligned and has no padding.
+                gl.uniform_2_f32_slice(location, cast_slice_assume_aligned(values));
+            }
         }
     }
 
@@ -480,13 +541,13 @@ impl GraphicsDevice {
         location: Option<&UniformLocation>,
         values: &[Vec3<f32>],
     ) {
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
 
-        unsafe {
-            // SAFETY: Type is aligned and has no padding.
-            self.state
-                .gl
-                .uniform_3_f32_slice(location, cast_slice_assume_aligned(values));
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                // SAFETY: Type is aligned and has no padding.
+                gl.uniform_3_f32_slice(location, cast_slice_assume_aligned(values));
+            }
         }
     }
 
@@ -496,13 +557,13 @@ impl GraphicsDevice {
         location: Option<&UniformLocation>,
         values: &[Vec4<f32>],
     ) {
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
 
-        unsafe {
-            // SAFETY: Type is aligned and has no padding.
-            self.state
-                .gl
-                .uniform_4_f32_slice(location, cast_slice_assume_aligned(values));
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                // SAFETY: Type is aligned and has no padding.
+                gl.uniform_4_f32_slice(location, cast_slice_assume_aligned(values));
+            }
         }
     }
 
@@ -512,13 +573,13 @@ impl GraphicsDevice {
         location: Option<&UniformLocation>,
         values: &[Color],
     ) {
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
 
-        unsafe {
-            // SAFETY: Type is aligned and has no padding.
-            self.state
-                .gl
-                .uniform_4_f32_slice(location, cast_slice_assume_aligned(values));
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                // SAFETY: Type is aligned and has no padding.
+                gl.uniform_4_f32_slice(location, cast_slice_assume_aligned(values));
+            }
         }
     }
 
@@ -528,20 +589,22 @@ impl GraphicsDevice {
         location: Option<&UniformLocation>,
         values: &[Mat2<f32>],
     ) {
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
 
         // This is probably overkill as Vek's repr_c matrices are always packed,
         // but they explicitly don't guarentee this won't change, so let's be
         // safe.
         assert!(values.iter().all(Mat2::is_packed));
 
-        unsafe {
-            // SAFETY: Type is aligned and has no padding.
-            self.state.gl.uniform_matrix_2_f32_slice(
-                location,
-                Mat2::<f32>::GL_SHOULD_TRANSPOSE,
-                cast_slice_assume_aligned(values),
-            );
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                // SAFETY: Type is aligned and has no padding.
+                gl.uniform_matrix_2_f32_slice(
+                    location,
+                    Mat2::<f32>::GL_SHOULD_TRANSPOSE,
+                    cast_slice_assume_aligned(values),
+                );
+            }
         }
     }
 
@@ -551,20 +614,22 @@ impl GraphicsDevice {
         location: Option<&UniformLocation>,
         values: &[Mat3<f32>],
     ) {
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
 
         // This is probably overkill as Vek's repr_c matrices are always packed,
         // but they explicitly don't guarentee this won't change, so let's be
         // safe.
         assert!(values.iter().all(Mat3::is_packed));
 
-        unsafe {
-            // SAFETY: Type is aligned and has no padding.
-            self.state.gl.uniform_matrix_3_f32_slice(
-                location,
-                Mat3::<f32>::GL_SHOULD_TRANSPOSE,
-                cast_slice_assume_aligned(values),
-            );
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                // SAFETY: Type is aligned and has no padding.
+                gl.uniform_matrix_3_f32_slice(
+                    location,
+                    Mat3::<f32>::GL_SHOULD_TRANSPOSE,
+                    cast_slice_assume_aligned(values),
+                );
+            }
         }
     }
 
@@ -574,36 +639,40 @@ impl GraphicsDevice {
         location: Option<&UniformLocation>,
         values: &[Mat4<f32>],
     ) {
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
 
         // This is probably overkill as Vek's repr_c matrices are always packed,
         // but they explicitly don't guarentee this won't change, so let's be
         // safe.
         assert!(values.iter().all(Mat4::is_packed));
 
-        unsafe {
-            // SAFETY: Type is aligned and has no padding.
-            self.state.gl.uniform_matrix_4_f32_slice(
-                location,
-                Mat4::<f32>::GL_SHOULD_TRANSPOSE,
-                cast_slice_assume_aligned(values),
-            );
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                // SAFETY: Type is aligned and has no padding.
+                gl.uniform_matrix_4_f32_slice(
+                    location,
+                    Mat4::<f32>::GL_SHOULD_TRANSPOSE,
+                    cast_slice_assume_aligned(values),
+                );
+            }
         }
     }
 
     pub fn set_blend_state(&mut self, blend_state: BlendState) {
-        unsafe {
-            self.state.gl.blend_equation_separate(
-                blend_state.color_operation.to_gl_enum(),
-                blend_state.alpha_operation.to_gl_enum(),
-            );
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.blend_equation_separate(
+                    blend_state.color_operation.to_gl_enum(),
+                    blend_state.alpha_operation.to_gl_enum(),
+                );
 
-            self.state.gl.blend_func_separate(
-                blend_state.color_src.to_gl_enum(),
-                blend_state.color_dst.to_gl_enum(),
-                blend_state.alpha_src.to_gl_enum(),
-                blend_state.alpha_dst.to_gl_enum(),
-            );
+                gl.blend_func_separate(
+                    blend_state.color_src.to_gl_enum(),
+                    blend_state.color_dst.to_gl_enum(),
+                    blend_state.alpha_src.to_gl_enum(),
+                    blend_state.alpha_dst.to_gl_enum(),
+                );
+            }
         }
     }
 
@@ -614,70 +683,72 @@ impl GraphicsDevice {
         format: TextureFormat,
         filter_mode: FilterMode,
     ) -> Result<RawTexture> {
-        // TODO: I don't think we need mipmaps?
-        unsafe {
-            let id = self
-                .state
-                .gl
-                .create_texture()
-                .map_err(TetraError::PlatformError)?;
+        let id = match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe {
+                gl.create_texture().map_err(TetraError::PlatformError)?
+            },
 
-            let texture = RawTexture {
-                state: Rc::clone(&self.state),
+            Backend::Null(null) => NativeTexture(
+                null.create_texture(width as usize * height as usize * format.stride()),
+            ),
+        };
 
-                id,
-                width,
-                height,
-                format,
-            };
+        let texture = RawTexture {
+            state: Rc::clone(&self.state),
 
-            self.bind_default_texture(Some(texture.id));
+            id,
+            width: Cell::new(width),
+            height: Cell::new(height),
+            format,
+        };
 
-            self.state.gl.tex_parameter_i32(
-                glow::TEXTURE_2D,
-                glow::TEXTURE_MIN_FILTER,
-                filter_mode.to_gl_enum() as i32,
-            );
+        self.bind_default_texture(Some(texture.id));
 
-            self.state.gl.tex_parameter_i32(
-                glow::TEXTURE_2D,
-                glow::TEXTURE_MAG_FILTER,
-                filter_mode.to_gl_enum() as i32,
-            );
+        // TODO: I don't think we need mipmaps?
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_MIN_FILTER,
+                    filter_mode.to_gl_enum() as i32,
+                );
 
-            self.state.gl.tex_parameter_i32(
-                glow::TEXTURE_2D,
-                glow::TEXTURE_WRAP_S,
-                glow::CLAMP_TO_EDGE as i32,
-            );
+                gl.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_MAG_FILTER,
+                    filter_mode.to_gl_enum() as i32,
+                );
 
-            self.state.gl.tex_parameter_i32(
-                glow::TEXTURE_2D,
-                glow::TEXTURE_WRAP_T,
-                glow::CLAMP_TO_EDGE as i32,
-            );
+                gl.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_WRAP_S,
+                    glow::CLAMP_TO_EDGE as i32,
+                );
 
-            self.state
-                .gl
-                .tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_BASE_LEVEL, 0);
+                gl.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_WRAP_T,
+                    glow::CLAMP_TO_EDGE as i32,
+                );
 
-            self.state
-                .gl
-                .tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAX_LEVEL, 0);
+                gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_BASE_LEVEL, 0);
 
-            self.clear_errors();
+                gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAX_LEVEL, 0);
 
-            self.state.gl.tex_image_2d(
-                glow::TEXTURE_2D,
-                0,
-                format.to_gl_internal_format() as i32,
-                width,
-                height,
-                0,
-                format.to_gl_format(),
-                format.to_gl_data_type(),
-                None,
-            );
+                self.clear_errors();
+
+                gl.tex_image_2d(
+                    glow::TEXTURE_2D,
+                    0,
+                    format.to_gl_internal_format() as i32,
+                    width,
+                    height,
+                    0,
+                    format.to_gl_format(),
+                    format.to_gl_data_type(),
+                    None,
+                );
+            }
 
             if let Some(e) = self.get_error() {
                 return Err(TetraError::PlatformError(format_gl_error(
@@ -685,9 +756,51 @@ impl GraphicsDevice {
                     e,
                 )));
             }
+        }
+
+        Ok(texture)
+    }
+
+    /// Reallocates the storage for an existing texture, discarding its contents.
+    pub fn resize_texture(&mut self, texture: &RawTexture, width: i32, height: i32) -> Result {
+        self.bind_default_texture(Some(texture.id));
+
+        match &self.state.backend {
+            Backend::OpenGl(gl) => {
+                unsafe {
+                    self.clear_errors();
+
+                    gl.tex_image_2d(
+                        glow::TEXTURE_2D,
+                        0,
+                        texture.format.to_gl_internal_format() as i32,
+                        width,
+                        height,
+                        0,
+                        texture.format.to_gl_format(),
+                        texture.format.to_gl_data_type(),
+                        None,
+                    );
+                }
+
+                if let Some(e) = self.get_error() {
+                    return Err(TetraError::PlatformError(format_gl_error(
+                        "failed to resize texture",
+                        e,
+                    )));
+                }
+            }
 
-            Ok(texture)
+            Backend::Null(null) => null.resize_texture(
+                texture.id.0,
+                width as usize * height as usize * texture.format.stride(),
+            ),
         }
+
+        texture.width.set(width);
+        texture.height.set(height);
+
+        Ok(())
     }
 
     pub fn set_texture_data(
@@ -700,7 +813,10 @@ impl GraphicsDevice {
         height: i32,
     ) -> Result {
         assert!(
-            x >= 0 && y >= 0 && x + width <= texture.width && y + height <= texture.height,
+            x >= 0
+                && y >= 0
+                && x + width <= texture.width.get()
+                && y + height <= texture.height.get(),
             "tried to write outside of texture bounds"
         );
 
@@ -713,31 +829,44 @@ impl GraphicsDevice {
 
         self.bind_default_texture(Some(texture.id));
 
-        let alignment = texture.format.to_gl_alignment();
+        match &self.state.backend {
+            Backend::OpenGl(gl) => {
+                let alignment = texture.format.to_gl_alignment();
 
-        unsafe {
-            if alignment != 4 {
-                self.state
-                    .gl
-                    .pixel_store_i32(glow::UNPACK_ALIGNMENT, alignment)
-            }
-
-            self.state.gl.tex_sub_image_2d(
-                glow::TEXTURE_2D,
-                0,
-                x,
-                y,
-                width,
-                height,
-                texture.format.to_gl_format(),
-                texture.format.to_gl_data_type(),
-                PixelUnpackData::Slice(data),
-            );
+                unsafe {
+                    if alignment != 4 {
+                        gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, alignment)
+                    }
 
-            // Revert back to a sensible default.
-            if alignment != 4 {
-                self.state.gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 4)
+                    gl.tex_sub_image_2d(
+                        glow::TEXTURE_2D,
+                        0,
+                        x,
+                        y,
+                        width,
+                        height,
+                        texture.format.to_gl_format(),
+                        texture.format.to_gl_data_type(),
+                        PixelUnpackData::Slice(data),
+                    );
+
+                    // Revert back to a sensible default.
+                    if alignment != 4 {
+                        gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 4)
+                    }
+                }
             }
+
+            Backend::Null(null) => null.set_texture_data(
+                texture.id.0,
+                texture.width.get() as usize,
+                texture.format.stride(),
+                data,
+                x as usize,
+                y as usize,
+                width as usize,
+                height as usize,
+            ),
         }
 
         Ok(())
@@ -746,17 +875,63 @@ impl GraphicsDevice {
     pub fn get_texture_data(&mut self, texture: &RawTexture) -> Vec<u8> {
         self.bind_default_texture(Some(texture.id));
 
-        let mut buffer =
-            vec![0; (texture.width * texture.height) as usize * texture.format.stride()];
+        match &self.state.backend {
+            Backend::OpenGl(gl) => {
+                let mut buffer = vec![
+                    0;
+                    (texture.width.get() * texture.height.get()) as usize
+                        * texture.format.stride()
+                ];
+
+                unsafe {
+                    gl.get_tex_image(
+                        glow::TEXTURE_2D,
+                        0,
+                        texture.format.to_gl_format(),
+                        texture.format.to_gl_data_type(),
+                        PixelPackData::Slice(&mut buffer),
+                    );
+                }
 
-        unsafe {
-            self.state.gl.get_tex_image(
-                glow::TEXTURE_2D,
-                0,
-                texture.format.to_gl_format(),
-                texture.format.to_gl_data_type(),
-                PixelPackData::Slice(&mut buffer),
-            );
+                buffer
+            }
+
+            Backend::Null(null) => null.get_texture_data(texture.id.0),
+        }
+    }
+
+    /// Reads the RGBA8 contents of the default framebuffer, with the top row first.
+    pub fn get_screen_data(&mut self, width: i32, height: i32) -> Vec<u8> {
+        let row_size = width as usize * 4;
+        let mut buffer = vec![0; row_size * height as usize];
+
+        // The null device doesn't render anything, so the screen is always blank.
+        if let Some(gl) = self.state.gl() {
+            let previous_read = self.state.current_read_framebuffer.get();
+
+            self.bind_read_framebuffer(None);
+
+            unsafe {
+                gl.read_pixels(
+                    0,
+                    0,
+                    width,
+                    height,
+                    glow::RGBA,
+                    glow::UNSIGNED_BYTE,
+                    PixelPackData::Slice(&mut buffer),
+                );
+            }
+
+            self.bind_read_framebuffer(previous_read);
+
+            // OpenGL returns the bottom row first, so the rows need to be flipped.
+            let height = height as usize;
+
+            for y in 0..height / 2 {
+                let (upper, lower) = buffer.split_at_mut((height - y - 1) * row_size);
+                upper[y * row_size..(y + 1) * row_size].swap_with_slice(&mut lower[..row_size]);
+            }
         }
 
         buffer
@@ -765,18 +940,20 @@ impl GraphicsDevice {
     pub fn set_texture_filter_mode(&mut self, texture: &RawTexture, filter_mode: FilterMode) {
         self.bind_default_texture(Some(texture.id));
 
-        unsafe {
-            self.state.gl.tex_parameter_i32(
-                glow::TEXTURE_2D,
-                glow::TEXTURE_MIN_FILTER,
-                filter_mode.to_gl_enum() as i32,
-            );
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_MIN_FILTER,
+                    filter_mode.to_gl_enum() as i32,
+                );
 
-            self.state.gl.tex_parameter_i32(
-                glow::TEXTURE_2D,
-                glow::TEXTURE_MAG_FILTER,
-                filter_mode.to_gl_enum() as i32,
-            );
+                gl.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_MAG_FILTER,
+                    filter_mode.to_gl_enum() as i32,
+                );
+            }
         }
     }
 
@@ -793,95 +970,110 @@ impl GraphicsDevice {
         samples: u8,
         with_stencil_buffer: bool,
     ) -> Result<RawCanvasWithAttachments> {
-        unsafe {
-            let previous_read = self.state.current_read_framebuffer.get();
-            let previous_draw = self.state.current_draw_framebuffer.get();
+        let previous_read = self.state.current_read_framebuffer.get();
+        let previous_draw = self.state.current_draw_framebuffer.get();
 
-            let id = self
-                .state
-                .gl
-                .create_framebuffer()
-                .map_err(TetraError::PlatformError)?;
-
-            let canvas = RawCanvas {
-                state: Rc::clone(&self.state),
-                id,
-            };
+        let id = match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe {
+                gl.create_framebuffer().map_err(TetraError::PlatformError)?
+            },
 
-            self.bind_framebuffer(Some(canvas.id));
+            Backend::Null(null) => NativeFramebuffer(null.create_id()),
+        };
 
-            let color = self.new_texture(width, height, format, filter_mode)?;
+        let canvas = RawCanvas {
+            state: Rc::clone(&self.state),
+            id,
+        };
 
-            self.state.gl.framebuffer_texture_2d(
-                glow::FRAMEBUFFER,
-                glow::COLOR_ATTACHMENT0,
-                glow::TEXTURE_2D,
-                Some(color.id),
-                0,
-            );
-
-            self.clear(Color::rgba(0.0, 0.0, 0.0, 0.0));
+        self.bind_framebuffer(Some(canvas.id));
 
-            let actual_samples = u8::min(samples, self.state.max_samples);
+        let color = self.new_texture(width, height, format, filter_mode)?;
 
-            let multisample_color = if actual_samples > 0 {
-                let renderbuffer =
-                    self.new_color_renderbuffer(width, height, format, actual_samples)?;
-
-                self.state.gl.framebuffer_renderbuffer(
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.framebuffer_texture_2d(
                     glow::FRAMEBUFFER,
                     glow::COLOR_ATTACHMENT0,
-                    glow::RENDERBUFFER,
-                    Some(renderbuffer.id),
+                    glow::TEXTURE_2D,
+                    Some(color.id),
+                    0,
                 );
+            }
+        }
 
-                self.clear(Color::rgba(0.0, 0.0, 0.0, 0.0));
+        self.clear(Color::rgba(0.0, 0.0, 0.0, 0.0));
 
-                Some(renderbuffer)
-            } else {
-                None
-            };
+        let actual_samples = u8::min(samples, self.state.max_samples);
 
-            let depth_stencil = if with_stencil_buffer {
-                let renderbuffer =
-                    self.new_depth_stencil_renderbuffer(width, height, actual_samples)?;
+        let multisample_color = if actual_samples > 0 {
+            let renderbuffer =
+                self.new_color_renderbuffer(width, height, format, actual_samples)?;
 
-                self.state.gl.framebuffer_renderbuffer(
-                    glow::FRAMEBUFFER,
-                    glow::DEPTH_STENCIL_ATTACHMENT,
-                    glow::RENDERBUFFER,
-                    Some(renderbuffer.id),
-                );
+            if let Some(gl) = self.state.gl() {
+                unsafe {
+                    gl.framebuffer_renderbuffer(
+                        glow::FRAMEBUFFER,
+                        glow::COLOR_ATTACHMENT0,
+                        glow::RENDERBUFFER,
+                        Some(renderbuffer.id),
+                    );
+                }
+            }
 
-                self.clear_stencil(0);
-                // TODO: Clear the depth buffer, if we start using it
+            self.clear(Color::rgba(0.0, 0.0, 0.0, 0.0));
 
-                Some(renderbuffer)
-            } else {
-                None
-            };
+            Some(renderbuffer)
+        } else {
+            None
+        };
+
+        let depth_stencil = if with_stencil_buffer {
+            let renderbuffer =
+                self.new_depth_stencil_renderbuffer(width, height, actual_samples)?;
+
+            if let Some(gl) = self.state.gl() {
+                unsafe {
+                    gl.framebuffer_renderbuffer(
+                        glow::FRAMEBUFFER,
+                        glow::DEPTH_STENCIL_ATTACHMENT,
+                        glow::RENDERBUFFER,
+                        Some(renderbuffer.id),
+                    );
+                }
+            }
 
-            let status = self.state.gl.check_framebuffer_status(glow::FRAMEBUFFER);
+            self.clear_stencil(0);
+            // TODO: Clear the depth buffer, if we start using it
 
-            // Revert the bindings before checking the status, so we don't end up
-            // in a weird state if there's an error:
-            self.bind_read_framebuffer(previous_read);
-            self.bind_draw_framebuffer(previous_draw);
+            Some(renderbuffer)
+        } else {
+            None
+        };
 
-            if status != glow::FRAMEBUFFER_COMPLETE {
-                return Err(TetraError::PlatformError(format_gl_framebuffer_status(
-                    "failed to create canvas",
-                    status,
-                )));
-            }
+        let status = match self.state.gl() {
+            Some(gl) => unsafe { gl.check_framebuffer_status(glow::FRAMEBUFFER) },
+            None => glow::FRAMEBUFFER_COMPLETE,
+        };
 
-            Ok(RawCanvasWithAttachments {
-                canvas,
-                color,
-                multisample_color,
-                depth_stencil,
-            })
+        // Revert the bindings before checking the status, so we don't end up
+        // in a weird state if there's an error:
+        self.bind_read_framebuffer(previous_read);
+        self.bind_draw_framebuffer(previous_draw);
+
+        if status != glow::FRAMEBUFFER_COMPLETE {
+            return Err(TetraError::PlatformError(format_gl_framebuffer_status(
+                "failed to create canvas",
+                status,
+            )));
         }
+
+        Ok(RawCanvasWithAttachments {
+            canvas,
+            color,
+            multisample_color,
+            depth_stencil,
+        })
     }
 
     pub fn set_canvas(&mut self, canvas: Option<&RawCanvas>) {
@@ -889,33 +1081,35 @@ impl GraphicsDevice {
     }
 
     pub fn resolve(&mut self, canvas: &RawCanvas, texture: &RawTexture) {
-        unsafe {
+        if let Some(gl) = self.state.gl() {
             let previous_read = self.state.current_read_framebuffer.get();
             let previous_draw = self.state.current_draw_framebuffer.get();
 
             self.bind_read_framebuffer(Some(canvas.id));
             self.bind_draw_framebuffer(Some(self.state.resolve_framebuffer));
 
-            self.state.gl.framebuffer_texture_2d(
-                glow::DRAW_FRAMEBUFFER,
-                glow::COLOR_ATTACHMENT0,
-                glow::TEXTURE_2D,
-                Some(texture.id),
-                0,
-            );
+            unsafe {
+                gl.framebuffer_texture_2d(
+                    glow::DRAW_FRAMEBUFFER,
+                    glow::COLOR_ATTACHMENT0,
+                    glow::TEXTURE_2D,
+                    Some(texture.id),
+                    0,
+                );
 
-            self.state.gl.blit_framebuffer(
-                0,
-                0,
-                texture.width,
-                texture.height,
-                0,
-                0,
-                texture.width,
-                texture.height,
-                glow::COLOR_BUFFER_BIT,
-                glow::NEAREST,
-            );
+                gl.blit_framebuffer(
+                    0,
+                    0,
+                    texture.width.get(),
+                    texture.height.get(),
+                    0,
+                    0,
+                    texture.width.get(),
+                    texture.height.get(),
+                    glow::COLOR_BUFFER_BIT,
+                    glow::NEAREST,
+                );
+            }
 
             self.bind_read_framebuffer(previous_read);
             self.bind_draw_framebuffer(previous_draw);
@@ -948,41 +1142,46 @@ impl GraphicsDevice {
         format: u32,
         samples: u8,
     ) -> Result<RawRenderbuffer> {
-        unsafe {
-            let id = self
-                .state
-                .gl
-                .create_renderbuffer()
-                .map_err(TetraError::PlatformError)?;
-
-            let renderbuffer = RawRenderbuffer {
-                state: Rc::clone(&self.state),
-                id,
-            };
-
-            self.bind_renderbuffer(Some(renderbuffer.id));
-
-            if samples > 0 {
-                self.state.gl.renderbuffer_storage_multisample(
-                    glow::RENDERBUFFER,
-                    samples.into(),
-                    format,
-                    width,
-                    height,
-                );
-            } else {
-                self.state
-                    .gl
-                    .renderbuffer_storage(glow::RENDERBUFFER, format, width, height);
+        let id = match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe {
+                gl.create_renderbuffer()
+                    .map_err(TetraError::PlatformError)?
+            },
+
+            Backend::Null(null) => NativeRenderbuffer(null.create_id()),
+        };
+
+        let renderbuffer = RawRenderbuffer {
+            state: Rc::clone(&self.state),
+            id,
+        };
+
+        self.bind_renderbuffer(Some(renderbuffer.id));
+
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                if samples > 0 {
+                    gl.renderbuffer_storage_multisample(
+                        glow::RENDERBUFFER,
+                        samples.into(),
+                        format,
+                        width,
+                        height,
+                    );
+                } else {
+                    gl.renderbuffer_storage(glow::RENDERBUFFER, format, width, height);
+                }
             }
-
-            Ok(renderbuffer)
         }
+
+        Ok(renderbuffer)
     }
 
     pub fn viewport(&mut self, x: i32, y: i32, width: i32, height: i32) {
-        unsafe {
-            self.state.gl.viewport(x, y, width, height);
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.viewport(x, y, width, height);
+            }
         }
     }
 
@@ -1019,159 +1218,180 @@ impl GraphicsDevice {
     ) {
         self.bind_vertex_buffer(Some(vertex_buffer.id));
         self.bind_default_texture(Some(texture.id));
-        self.bind_program(Some(shader.id));
+        self.bind_program(Some(shader.id.get()));
         self.set_vertex_attributes(vertex_buffer);
 
-        match index_buffer {
-            Some(index_buffer) => {
-                self.bind_index_buffer(Some(index_buffer.id));
-
-                let max_count = index_buffer.count();
-
-                let offset = usize::min(offset, max_count.saturating_sub(1));
-                let count = usize::min(count, max_count.saturating_sub(offset));
-
-                if instances > 1 {
-                    unsafe {
-                        self.state.gl.draw_elements_instanced(
-                            glow::TRIANGLES,
-                            count as i32,
-                            glow::UNSIGNED_INT,
-                            (index_buffer.stride() * offset) as i32,
-                            instances as i32,
-                        );
-                    }
-                } else {
-                    unsafe {
-                        self.state.gl.draw_elements(
-                            glow::TRIANGLES,
-                            count as i32,
-                            glow::UNSIGNED_INT,
-                            (index_buffer.stride() * offset) as i32,
-                        );
+        if let Some(index_buffer) = index_buffer {
+            self.bind_index_buffer(Some(index_buffer.id));
+        }
+
+        let max_count = match index_buffer {
+            Some(index_buffer) => index_buffer.count(),
+            None => vertex_buffer.count(),
+        };
+
+        let offset = usize::min(offset, max_count.saturating_sub(1));
+        let count = usize::min(count, max_count.saturating_sub(offset));
+
+        match &self.state.backend {
+            Backend::OpenGl(gl) => match index_buffer {
+                Some(index_buffer) => {
+                    if instances > 1 {
+                        unsafe {
+                            gl.draw_elements_instanced(
+                                glow::TRIANGLES,
+                                count as i32,
+                                glow::UNSIGNED_INT,
+                                (index_buffer.stride() * offset) as i32,
+                                instances as i32,
+                            );
+                        }
+                    } else {
+                        unsafe {
+                            gl.draw_elements(
+                                glow::TRIANGLES,
+                                count as i32,
+                                glow::UNSIGNED_INT,
+                                (index_buffer.stride() * offset) as i32,
+                            );
+                        }
                     }
                 }
-            }
-
-            None => {
-                let max_count = vertex_buffer.count();
 
-                let offset = usize::min(offset, max_count.saturating_sub(1));
-                let count = usize::min(count, max_count.saturating_sub(offset));
-
-                if instances > 1 {
-                    unsafe {
-                        self.state.gl.draw_arrays_instanced(
-                            glow::TRIANGLES,
-                            offset as i32,
-                            count as i32,
-                            instances as i32,
-                        );
-                    }
-                } else {
-                    unsafe {
-                        self.state
-                            .gl
-                            .draw_arrays(glow::TRIANGLES, offset as i32, count as i32);
+                None => {
+                    if instances > 1 {
+                        unsafe {
+                            gl.draw_arrays_instanced(
+                                glow::TRIANGLES,
+                                offset as i32,
+                                count as i32,
+                                instances as i32,
+                            );
+                        }
+                    } else {
+                        unsafe {
+                            gl.draw_arrays(glow::TRIANGLES, offset as i32, count as i32);
+                        }
                     }
                 }
-            }
+            },
+
+            Backend::Null(null) => null.record_draw(
+                vertex_buffer.id.0,
+                index_buffer.map(|b| b.id.0),
+                offset,
+                count,
+                instances,
+                (texture.width.get(), texture.height.get()),
+                self.state.current_draw_framebuffer.get().is_some(),
+            ),
         }
     }
 
-    fn bind_vertex_buffer(&mut self, id: Option<BufferId>) {
-        unsafe {
-            if self.state.current_vertex_buffer.get() != id {
-                self.state.gl.bind_buffer(glow::ARRAY_BUFFER, id);
-                self.state.current_vertex_buffer.set(id);
+    fn bind_vertex_buffer(&self, id: Option<BufferId>) {
+        if self.state.current_vertex_buffer.get() != id {
+            if let Some(gl) = self.state.gl() {
+                unsafe { gl.bind_buffer(glow::ARRAY_BUFFER, id) }
             }
+
+            self.state.current_vertex_buffer.set(id);
         }
     }
 
-    fn bind_index_buffer(&mut self, id: Option<BufferId>) {
-        unsafe {
-            if self.state.current_index_buffer.get() != id {
-                self.state.gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, id);
-                self.state.current_index_buffer.set(id);
+    fn bind_index_buffer(&self, id: Option<BufferId>) {
+        if self.state.current_index_buffer.get() != id {
+            if let Some(gl) = self.state.gl() {
+                unsafe { gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, id) }
             }
+
+            self.state.current_index_buffer.set(id);
         }
     }
 
-    fn bind_program(&mut self, id: Option<ProgramId>) {
-        unsafe {
-            if self.state.current_program.get() != id {
-                self.state.gl.use_program(id);
-                self.state.current_program.set(id);
+    fn bind_program(&self, id: Option<ProgramId>) {
+        if self.state.current_program.get() != id {
+            if let Some(gl) = self.state.gl() {
+                unsafe { gl.use_program(id) }
             }
+
+            self.state.current_program.set(id);
         }
     }
 
-    fn bind_texture(&mut self, id: Option<TextureId>, unit: u32) -> Result {
-        unsafe {
-            let current = &self
-                .state
-                .current_textures
-                .get(unit as usize)
-                .ok_or_else(|| TetraError::PlatformError("invalid texture unit".into()))?;
-
-            if current.get() != id {
-                self.state.gl.active_texture(glow::TEXTURE0 + unit);
-                self.state.gl.bind_texture(glow::TEXTURE_2D, id);
-                current.set(id);
+    fn bind_texture(&self, id: Option<TextureId>, unit: u32) -> Result {
+        let current = &self
+            .state
+            .current_textures
+            .get(unit as usize)
+            .ok_or_else(|| TetraError::PlatformError("invalid texture unit".into()))?;
+
+        if current.get() != id {
+            if let Some(gl) = self.state.gl() {
+                unsafe {
+                    gl.active_texture(glow::TEXTURE0 + unit);
+                    gl.bind_texture(glow::TEXTURE_2D, id);
+                }
             }
+
+            current.set(id);
         }
 
         Ok(())
     }
 
-    fn bind_default_texture(&mut self, id: Option<TextureId>) {
+    fn bind_default_texture(&self, id: Option<TextureId>) {
         self.bind_texture(id, 0)
             .expect("texture unit 0 should always be available");
     }
 
-    fn bind_framebuffer(&mut self, id: Option<FramebufferId>) {
-        unsafe {
-            if self.state.current_read_framebuffer.get() != id
-                || self.state.current_draw_framebuffer.get() != id
-            {
-                self.state.gl.bind_framebuffer(glow::FRAMEBUFFER, id);
-                self.state.current_read_framebuffer.set(id);
-                self.state.current_draw_framebuffer.set(id);
+    fn bind_framebuffer(&self, id: Option<FramebufferId>) {
+        if self.state.current_read_framebuffer.get() != id
+            || self.state.current_draw_framebuffer.get() != id
+        {
+            if let Some(gl) = self.state.gl() {
+                unsafe { gl.bind_framebuffer(glow::FRAMEBUFFER, id) }
             }
+
+            self.state.current_read_framebuffer.set(id);
+            self.state.current_draw_framebuffer.set(id);
         }
     }
 
-    fn bind_read_framebuffer(&mut self, id: Option<FramebufferId>) {
-        unsafe {
-            if self.state.current_read_framebuffer.get() != id {
-                self.state.gl.bind_framebuffer(glow::READ_FRAMEBUFFER, id);
-                self.state.current_read_framebuffer.set(id);
+    fn bind_read_framebuffer(&self, id: Option<FramebufferId>) {
+        if self.state.current_read_framebuffer.get() != id {
+            if let Some(gl) = self.state.gl() {
+                unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, id) }
             }
+
+            self.state.current_read_framebuffer.set(id);
         }
     }
 
-    fn bind_draw_framebuffer(&mut self, id: Option<FramebufferId>) {
-        unsafe {
-            if self.state.current_draw_framebuffer.get() != id {
-                self.state.gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, id);
-
-                self.state.current_draw_framebuffer.set(id);
+    fn bind_draw_framebuffer(&self, id: Option<FramebufferId>) {
+        if self.state.current_draw_framebuffer.get() != id {
+            if let Some(gl) = self.state.gl() {
+                unsafe { gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, id) }
             }
+
+            self.state.current_draw_framebuffer.set(id);
         }
     }
 
-    fn bind_renderbuffer(&mut self, id: Option<RenderbufferId>) {
-        unsafe {
-            if self.state.current_renderbuffer.get() != id {
-                self.state.gl.bind_renderbuffer(glow::RENDERBUFFER, id);
-                self.state.current_renderbuffer.set(id);
+    fn bind_renderbuffer(&self, id: Option<RenderbufferId>) {
+        if self.state.current_renderbuffer.get() != id {
+            if let Some(gl) = self.state.gl() {
+                unsafe { gl.bind_renderbuffer(glow::RENDERBUFFER, id) }
             }
+
+            self.state.current_renderbuffer.set(id);
         }
     }
 
-    fn get_error(&mut self) -> Option<u32> {
+    fn get_error(&self) -> Option<u32> {
+        let gl = self.state.gl()?;
+
         unsafe {
-            let error = self.state.gl.get_error();
+            let error = gl.get_error();
 
             if error != glow::NO_ERROR {
                 Some(error)
@@ -1181,19 +1401,72 @@ impl GraphicsDevice {
         }
     }
 
-    fn clear_errors(&mut self) {
-        unsafe { while self.state.gl.get_error() != glow::NO_ERROR {} }
+    fn clear_errors(&self) {
+        if let Some(gl) = self.state.gl() {
+            unsafe { while gl.get_error() != glow::NO_ERROR {} }
+        }
     }
 }
 
+unsafe fn compile_program(
+    gl: &GlowContext,
+    vertex_shader: &str,
+    fragment_shader: &str,
+) -> Result<ProgramId> {
+    let program_id = gl.create_program().map_err(TetraError::PlatformError)?;
+
+    // TODO: IDK if this should be applied to *all* shaders...
+    gl.bind_attrib_location(program_id, 0, "a_position");
+    gl.bind_attrib_location(program_id, 1, "a_uv");
+    gl.bind_attrib_location(program_id, 2, "a_color");
+
+    let vertex_id = gl
+        .create_shader(glow::VERTEX_SHADER)
+        .map_err(TetraError::PlatformError)?;
+
+    gl.shader_source(vertex_id, vertex_shader);
+    gl.compile_shader(vertex_id);
+    gl.attach_shader(program_id, vertex_id);
+
+    if !gl.get_shader_compile_status(vertex_id) {
+        return Err(TetraError::InvalidShader(gl.get_shader_info_log(vertex_id)));
+    }
+
+    let fragment_id = gl
+        .create_shader(glow::FRAGMENT_SHADER)
+        .map_err(TetraError::PlatformError)?;
+
+    gl.shader_source(fragment_id, fragment_shader);
+    gl.compile_shader(fragment_id);
+    gl.attach_shader(program_id, fragment_id);
+
+    if !gl.get_shader_compile_status(fragment_id) {
+        return Err(TetraError::InvalidShader(
+            gl.get_shader_info_log(fragment_id),
+        ));
+    }
+
+    gl.link_program(program_id);
+
+    if !gl.get_program_link_status(program_id) {
+        return Err(TetraError::InvalidShader(
+            gl.get_program_info_log(program_id),
+        ));
+    }
+
+    gl.delete_shader(vertex_id);
+    gl.delete_shader(fragment_id);
+
+    Ok(program_id)
+}
+
 impl Drop for GraphicsDevice {
     fn drop(&mut self) {
-        unsafe {
-            self.state
-                .gl
-                .delete_framebuffer(self.state.resolve_framebuffer);
-
-            self.state.gl.delete_vertex_array(self.state.vertex_array);
+        if let Some(gl) = self.state.gl() {
+            unsafe {
+                gl.delete_framebuffer(self.state.resolve_framebuffer);
+                gl.delete_vertex_array(self.state.vertex_array);
+            }
         }
     }
 }
@@ -1367,12 +1640,13 @@ impl PartialEq for RawVertexBuffer {
 
 impl Drop for RawVertexBuffer {
     fn drop(&mut self) {
-        unsafe {
-            if self.state.current_vertex_buffer.get() == Some(self.id) {
-                self.state.current_vertex_buffer.set(None);
-            }
+        if self.state.current_vertex_buffer.get() == Some(self.id) {
+            self.state.current_vertex_buffer.set(None);
+        }
 
-            self.state.gl.delete_buffer(self.id);
+        match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe { gl.delete_buffer(self.id) },
+            Backend::Null(null) => null.delete_buffer(self.id.0),
         }
     }
 }
@@ -1410,12 +1684,13 @@ impl PartialEq for RawIndexBuffer {
 
 impl Drop for RawIndexBuffer {
     fn drop(&mut self) {
-        unsafe {
-            if self.state.current_index_buffer.get() == Some(self.id) {
-                self.state.current_index_buffer.set(None);
-            }
+        if self.state.current_index_buffer.get() == Some(self.id) {
+            self.state.current_index_buffer.set(None);
+        }
 
-            self.state.gl.delete_buffer(self.id);
+        match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe { gl.delete_buffer(self.id) },
+            Backend::Null(null) => null.delete_buffer(self.id.0),
         }
     }
 }
@@ -1423,23 +1698,25 @@ impl Drop for RawIndexBuffer {
 #[derive(Debug)]
 pub struct RawShader {
     state: Rc<GraphicsState>,
-    id: ProgramId,
+
+    // This can change if the shader is recompiled.
+    id: Cell<ProgramId>,
 }
 
 impl PartialEq for RawShader {
     fn eq(&self, other: &RawShader) -> bool {
-        self.id == other.id
+        self.id.get() == other.id.get()
     }
 }
 
 impl Drop for RawShader {
     fn drop(&mut self) {
-        unsafe {
-            if self.state.current_program.get() == Some(self.id) {
-                self.state.current_program.set(None);
-            }
+        if self.state.current_program.get() == Some(self.id.get()) {
+            self.state.current_program.set(None);
+        }
 
-            self.state.gl.delete_program(self.id);
+        if let Some(gl) = self.state.gl() {
+            unsafe { gl.delete_program(self.id.get()) }
         }
     }
 }
@@ -1449,18 +1726,19 @@ pub struct RawTexture {
     state: Rc<GraphicsState>,
     id: TextureId,
 
-    width: i32,
-    height: i32,
+    // These can change if the texture is resized.
+    width: Cell<i32>,
+    height: Cell<i32>,
     format: TextureFormat,
 }
 
 impl RawTexture {
     pub fn width(&self) -> i32 {
-        self.width
+        self.width.get()
     }
 
     pub fn height(&self) -> i32 {
-        self.height
+        self.height.get()
     }
 
     pub fn format(&self) -> TextureFormat {
@@ -1476,14 +1754,15 @@ impl PartialEq for RawTexture {
 
 impl Drop for RawTexture {
     fn drop(&mut self) {
-        unsafe {
-            for bound in &self.state.current_textures {
-                if bound.get() == Some(self.id) {
-                    bound.set(None);
-                }
+        for bound in &self.state.current_textures {
+            if bound.get() == Some(self.id) {
+                bound.set(None);
             }
+        }
 
-            self.state.gl.delete_texture(self.id);
+        match &self.state.backend {
+            Backend::OpenGl(gl) => unsafe { gl.delete_texture(self.id) },
+            Backend::Null(null) => null.delete_texture(self.id.0),
         }
     }
 }
@@ -1502,16 +1781,16 @@ impl PartialEq for RawCanvas {
 
 impl Drop for RawCanvas {
     fn drop(&mut self) {
-        unsafe {
-            if self.state.current_read_framebuffer.get() == Some(self.id) {
-                self.state.current_read_framebuffer.set(None);
-            }
+        if self.state.current_read_framebuffer.get() == Some(self.id) {
+            self.state.current_read_framebuffer.set(None);
+        }
 
-            if self.state.current_draw_framebuffer.get() == Some(self.id) {
-                self.state.current_draw_framebuffer.set(None);
-            }
+        if self.state.current_draw_framebuffer.get() == Some(self.id) {
+            self.state.current_draw_framebuffer.set(None);
+        }
 
-            self.state.gl.delete_framebuffer(self.id);
+        if let Some(gl) = self.state.gl() {
+            unsafe { gl.delete_framebuffer(self.id) }
         }
     }
 }
@@ -1537,12 +1816,12 @@ impl PartialEq for RawRenderbuffer {
 
 impl Drop for RawRenderbuffer {
     fn drop(&mut self) {
-        unsafe {
-            if self.state.current_renderbuffer.get() == Some(self.id) {
-                self.state.current_renderbuffer.set(None);
-            }
+        if self.state.current_renderbuffer.get() == Some(self.id) {
+            self.state.current_renderbuffer.set(None);
+        }
 
-            self.state.gl.delete_renderbuffer(self.id);
+        if let Some(gl) = self.state.gl() {
+            unsafe { gl.delete_renderbuffer(self.id) }
         }
     }
 }
@@ -0,0 +1,167 @@
+//! A graphics backend that doesn't talk to the GPU at all.
+//!
+//! This is used by headless contexts - rather than executing commands, it keeps
+//! just enough state around (buffer/texture contents) for reads to work, and
+//! records the draw calls that would have been made so they can be inspected.
+
+use std::cell::{Cell, RefCell};
+use std::num::NonZeroU32;
+
+use hashbrown::HashMap;
+
+use crate::graphics::mesh::Vertex;
+use crate::graphics::DrawCall;
+
+#[derive(Debug)]
+pub struct NullDevice {
+    next_id: Cell<u32>,
+
+    vertex_buffers: RefCell<HashMap<NonZeroU32, Vec<Vertex>>>,
+    index_buffers: RefCell<HashMap<NonZeroU32, Vec<u32>>>,
+    textures: RefCell<HashMap<NonZeroU32, Vec<u8>>>,
+
+    draw_calls: RefCell<Vec<DrawCall>>,
+}
+
+impl NullDevice {
+    pub fn new() -> NullDevice {
+        NullDevice {
+            next_id: Cell::new(1),
+
+            vertex_buffers: RefCell::new(HashMap::new()),
+            index_buffers: RefCell::new(HashMap::new()),
+            textures: RefCell::new(HashMap::new()),
+
+            draw_calls: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn create_id(&self) -> NonZeroU32 {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        NonZeroU32::new(id).expect("ids should start at 1")
+    }
+
+    pub fn create_vertex_buffer(&self, count: usize) -> NonZeroU32 {
+        let id = self.create_id();
+
+        self.vertex_buffers
+            .borrow_mut()
+            .insert(id, vec![Vertex::default(); count]);
+
+        id
+    }
+
+    pub fn set_vertex_buffer_data(&self, id: NonZeroU32, data: &[Vertex], offset: usize) {
+        if let Some(buffer) = self.vertex_buffers.borrow_mut().get_mut(&id) {
+            buffer[offset..offset + data.len()].copy_from_slice(data);
+        }
+    }
+
+    pub fn create_index_buffer(&self, count: usize) -> NonZeroU32 {
+        let id = self.create_id();
+
+        self.index_buffers.borrow_mut().insert(id, vec![0; count]);
+
+        id
+    }
+
+    pub fn set_index_buffer_data(&self, id: NonZeroU32, data: &[u32], offset: usize) {
+        if let Some(buffer) = self.index_buffers.borrow_mut().get_mut(&id) {
+            buffer[offset..offset + data.len()].copy_from_slice(data);
+        }
+    }
+
+    pub fn delete_buffer(&self, id: NonZeroU32) {
+        self.vertex_buffers.borrow_mut().remove(&id);
+        self.index_buffers.borrow_mut().remove(&id);
+    }
+
+    pub fn create_texture(&self, size: usize) -> NonZeroU32 {
+        let id = self.create_id();
+
+        self.textures.borrow_mut().insert(id, vec![0; size]);
+
+        id
+    }
+
+    pub fn resize_texture(&self, id: NonZeroU32, size: usize) {
+        if let Some(texture) = self.textures.borrow_mut().get_mut(&id) {
+            *texture = vec![0; size];
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_texture_data(
+        &self,
+        id: NonZeroU32,
+        texture_width: usize,
+        stride: usize,
+        data: &[u8],
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) {
+        if let Some(texture) = self.textures.borrow_mut().get_mut(&id) {
+            let row_size = width * stride;
+
+            for row in 0..height {
+                let src = row * row_size;
+                let dst = ((y + row) * texture_width + x) * stride;
+
+                texture[dst..dst + row_size].copy_from_slice(&data[src..src + row_size]);
+            }
+        }
+    }
+
+    pub fn get_texture_data(&self, id: NonZeroU32) -> Vec<u8> {
+        self.textures.borrow().get(&id).cloned().unwrap_or_default()
+    }
+
+    pub fn delete_texture(&self, id: NonZeroU32) {
+        self.textures.borrow_mut().remove(&id);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_draw(
+        &self,
+        vertex_buffer: NonZeroU32,
+        index_buffer: Option<NonZeroU32>,
+        offset: usize,
+        count: usize,
+        instances: usize,
+        texture_size: (i32, i32),
+        to_canvas: bool,
+    ) {
+        let vertex_buffers = self.vertex_buffers.borrow();
+        let index_buffers = self.index_buffers.borrow();
+
+        let vertex_data = match vertex_buffers.get(&vertex_buffer) {
+            Some(data) => data,
+            None => return,
+        };
+
+        let vertices = match index_buffer.and_then(|id| index_buffers.get(&id)) {
+            Some(indices) => indices[offset..offset + count]
+                .iter()
+                .filter_map(|i| vertex_data.get(*i as usize))
+                .copied()
+                .collect(),
+
+            None => vertex_data[offset..offset + count].to_vec(),
+        };
+
+        self.draw_calls.borrow_mut().push(DrawCall {
+            vertices,
+            instances,
+            texture_size,
+            to_canvas,
+        });
+    }
+
+    pub fn take_draw_calls(&self) -> Vec<DrawCall> {
+        self.draw_calls.take()
+    }
+}
@@ -0,0 +1,352 @@
+//! A window that doesn't actually exist.
+//!
+//! This is used by headless contexts - it keeps track of the state that the
+//! game has requested (size, title, mouse settings, etc), and reports a single
+//! virtual monitor that matches the size of the window.
+
+use std::cell::{Cell, RefCell};
+use std::mem;
+use std::result;
+
+use crate::error::{Result, TetraError};
+use crate::graphics::{self, ImageData};
+use crate::input::{self, GamepadType, InputEvent, Key, KeyLabel};
+use crate::window::WindowPosition;
+use crate::{Context, ContextBuilder, Event, State};
+
+const MONITOR_NAME: &str = "Null Monitor";
+const REFRESH_RATE: i32 = 60;
+
+pub struct NullWindow {
+    title: String,
+    size: (i32, i32),
+    minimum_size: (i32, i32),
+    maximum_size: (i32, i32),
+    position: (i32, i32),
+
+    visible: bool,
+    fullscreen: bool,
+    vsync: bool,
+    minimized: bool,
+
+    mouse_visible: bool,
+    mouse_grabbed: bool,
+    relative_mouse_mode: bool,
+
+    clipboard: RefCell<String>,
+    screen_saver_enabled: Cell<bool>,
+    key_repeat: bool,
+
+    pending_resize: Option<(i32, i32)>,
+    pending_input: Vec<InputEvent>,
+}
+
+impl NullWindow {
+    pub fn new(settings: &ContextBuilder) -> (NullWindow, i32, i32) {
+        let window = NullWindow {
+            title: settings.title.clone(),
+            size: (settings.window_width, settings.window_height),
+            minimum_size: (0, 0),
+            maximum_size: (0, 0),
+            position: (0, 0),
+
+            visible: false,
+            fullscreen: settings.fullscreen,
+            vsync: settings.vsync,
+            minimized: settings.minimized,
+
+            mouse_visible: settings.show_mouse,
+            mouse_grabbed: settings.grab_mouse,
+            relative_mouse_mode: settings.relative_mouse_mode,
+
+            clipboard: RefCell::new(String::new()),
+            screen_saver_enabled: Cell::new(settings.screen_saver_enabled),
+            key_repeat: settings.key_repeat,
+
+            pending_resize: None,
+            pending_input: Vec::new(),
+        };
+
+        (window, settings.window_width, settings.window_height)
+    }
+
+    pub fn maximize(&mut self) {
+        self.minimized = false;
+    }
+
+    pub fn minimize(&mut self) {
+        self.minimized = true;
+    }
+
+    pub fn restore(&mut self) {
+        self.minimized = false;
+    }
+
+    pub fn focus(&mut self) {}
+
+    pub fn is_focused(&self) -> bool {
+        // There's no other window that could take focus away from this one.
+        !self.minimized
+    }
+
+    pub fn is_minimized(&self) -> bool {
+        self.minimized
+    }
+
+    pub fn get_refresh_rate(&self) -> Result<i32> {
+        Ok(REFRESH_RATE)
+    }
+
+    pub fn get_window_title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn set_window_title<S>(&mut self, title: S)
+    where
+        S: AsRef<str>,
+    {
+        self.title = title.as_ref().to_owned();
+    }
+
+    pub fn get_window_size(&self) -> (i32, i32) {
+        self.size
+    }
+
+    pub fn get_physical_size(&self) -> (i32, i32) {
+        self.size
+    }
+
+    pub fn set_window_size(&mut self, width: i32, height: i32) -> Result {
+        if self.size != (width, height) {
+            self.size = (width, height);
+            self.pending_resize = Some(self.size);
+        }
+
+        Ok(())
+    }
+
+    pub fn set_minimum_size(&mut self, width: i32, height: i32) -> Result {
+        self.minimum_size = (width, height);
+        Ok(())
+    }
+
+    pub fn get_minimum_size(&self) -> (i32, i32) {
+        self.minimum_size
+    }
+
+    pub fn set_maximum_size(&mut self, width: i32, height: i32) -> Result {
+        self.maximum_size = (width, height);
+        Ok(())
+    }
+
+    pub fn get_maximum_size(&self) -> (i32, i32) {
+        self.maximum_size
+    }
+
+    pub fn set_position(&mut self, x: WindowPosition, y: WindowPosition) {
+        // The virtual monitor is always exactly the size of the window, so
+        // centering it puts it at the origin.
+        let resolve = |position| match position {
+            WindowPosition::Centered(_) => 0,
+            WindowPosition::Positioned(value) => value,
+        };
+
+        self.position = (resolve(x), resolve(y));
+    }
+
+    pub fn get_position(&self) -> (i32, i32) {
+        self.position
+    }
+
+    pub fn set_decorated(&mut self, _bordered: bool) {}
+
+    pub fn set_icon(&mut self, _data: &mut ImageData) -> Result {
+        Ok(())
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn get_dpi_scale(&self) -> f32 {
+        1.0
+    }
+
+    pub fn get_monitor_count(&self) -> Result<i32> {
+        Ok(1)
+    }
+
+    pub fn get_monitor_name(&self, monitor_index: i32) -> Result<String> {
+        self.check_monitor_index(monitor_index)?;
+        Ok(MONITOR_NAME.to_owned())
+    }
+
+    pub fn get_monitor_size(&self, monitor_index: i32) -> Result<(i32, i32)> {
+        self.check_monitor_index(monitor_index)?;
+        Ok(self.size)
+    }
+
+    pub fn get_current_monitor(&self) -> Result<i32> {
+        Ok(0)
+    }
+
+    fn check_monitor_index(&self, monitor_index: i32) -> Result {
+        if monitor_index == 0 {
+            Ok(())
+        } else {
+            Err(TetraError::PlatformError(format!(
+                "invalid monitor index: {}",
+                monitor_index
+            )))
+        }
+    }
+
+    pub fn set_vsync(&mut self, vsync: bool) -> Result {
+        self.vsync = vsync;
+        Ok(())
+    }
+
+    pub fn is_vsync_enabled(&self) -> bool {
+        self.vsync
+    }
+
+    pub fn set_fullscreen(&mut self, fullscreen: bool) -> Result {
+        self.fullscreen = fullscreen;
+        Ok(())
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    pub fn set_mouse_visible(&mut self, mouse_visible: bool) -> Result {
+        self.mouse_visible = mouse_visible;
+        Ok(())
+    }
+
+    pub fn is_mouse_visible(&self) -> bool {
+        self.mouse_visible
+    }
+
+    pub fn set_mouse_grabbed(&mut self, mouse_grabbed: bool) {
+        self.mouse_grabbed = mouse_grabbed;
+    }
+
+    pub fn is_mouse_grabbed(&self) -> bool {
+        self.mouse_grabbed
+    }
+
+    pub fn set_relative_mouse_mode(&mut self, relative_mouse_mode: bool) {
+        self.relative_mouse_mode = relative_mouse_mode;
+    }
+
+    pub fn is_relative_mouse_mode(&self) -> bool {
+        self.relative_mouse_mode
+    }
+
+    pub fn get_clipboard_text(&self) -> Result<String> {
+        Ok(self.clipboard.borrow().clone())
+    }
+
+    pub fn set_clipboard_text(&self, text: &str) -> Result {
+        *self.clipboard.borrow_mut() = text.to_owned();
+        Ok(())
+    }
+
+    pub fn swap_buffers(&self) {}
+
+    pub fn get_gamepad_name(&self, _platform_id: u32) -> String {
+        String::new()
+    }
+
+    pub fn add_gamepad_mappings(&mut self, _mappings: &str) -> Result<usize> {
+        Ok(0)
+    }
+
+    pub fn get_gamepad_type(&self, _platform_id: u32) -> GamepadType {
+        GamepadType::Generic
+    }
+
+    pub fn get_gamepad_guid(&self, platform_id: u32) -> Option<String> {
+        // There are no real devices to identify, so the platform ID is used instead.
+        Some(format!("{:032x}", platform_id))
+    }
+
+    pub fn get_gamepad_vendor_id(&self, _platform_id: u32) -> Option<u16> {
+        None
+    }
+
+    pub fn get_gamepad_product_id(&self, _platform_id: u32) -> Option<u16> {
+        None
+    }
+
+    pub fn is_gamepad_vibration_supported(&self, _platform_id: u32) -> bool {
+        false
+    }
+
+    pub fn set_gamepad_vibration(&mut self, _platform_id: u32, _strength: f32) {}
+
+    pub fn start_gamepad_vibration(&mut self, _platform_id: u32, _strength: f32, _duration: u32) {}
+
+    pub fn stop_gamepad_vibration(&mut self, _platform_id: u32) {}
+
+    pub fn set_screen_saver_enabled(&self, screen_saver_enabled: bool) {
+        self.screen_saver_enabled.set(screen_saver_enabled);
+    }
+
+    pub fn is_screen_saver_enabled(&self) -> bool {
+        self.screen_saver_enabled.get()
+    }
+
+    pub fn set_key_repeat_enabled(&mut self, key_repeat: bool) {
+        self.key_repeat = key_repeat;
+    }
+
+    pub fn is_key_repeat_enabled(&self) -> bool {
+        self.key_repeat
+    }
+
+    pub fn get_key_with_label(&self, _key_label: KeyLabel) -> Option<Key> {
+        None
+    }
+
+    pub fn get_key_label(&self, _key: Key) -> Option<KeyLabel> {
+        None
+    }
+
+    /// Queues up an input event, to be delivered the next time events are handled.
+    ///
+    /// This allows tests to simulate the player's input.
+    #[cfg(test)]
+    pub fn push_input_event(&mut self, event: InputEvent) {
+        self.pending_input.push(event);
+    }
+}
+
+fn null_window(ctx: &mut Context) -> &mut NullWindow {
+    match &mut ctx.window {
+        super::Window::Null(window) => window,
+        super::Window::Sdl(_) => unreachable!("null events should only be polled for null windows"),
+    }
+}
+
+pub fn handle_events<S, E>(ctx: &mut Context, state: &mut S) -> result::Result<(), E>
+where
+    S: State<E>,
+    E: From<TetraError>,
+{
+    if let Some((width, height)) = null_window(ctx).pending_resize.take() {
+        graphics::set_viewport_size(ctx);
+        state.event(ctx, Event::Resized { width, height })?;
+    }
+
+    for event in mem::take(&mut null_window(ctx).pending_input) {
+        input::handle_input_event(ctx, state, event)?;
+    }
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 // TODO: This file is getting way too huge.
+use std::mem;
+use std::os::raw::c_int;
 use std::path::PathBuf;
 use std::result;
 
@@ -20,16 +22,25 @@ use sdl2::{EventPump, GameControllerSubsystem, JoystickSubsystem, Sdl, VideoSubs
 use crate::error::{Result, TetraError};
 use crate::graphics::{self, ImageData};
 use crate::input::{
-    self, GamepadAxis, GamepadButton, GamepadStick, Key, KeyLabel, KeyModifierState, MouseButton,
+    self, GamepadAxis, GamepadButton, GamepadType, InputEvent, Key, KeyLabel, KeyModifierState,
+    MouseButton,
 };
 use crate::math::Vec2;
-use crate::window::WindowPosition;
+use crate::window::{self, WindowPosition};
 use crate::{Context, ContextBuilder, Event, State};
 
 struct SdlController {
     controller: GameController,
-    slot: usize,
+
+    /// The gamepad ID that the controller was given. This is `None` if the
+    /// controller was connected while input was being played back.
+    slot: Option<usize>,
+
     supports_rumble: bool,
+    gamepad_type: GamepadType,
+    vendor_id: Option<u16>,
+    product_id: Option<u16>,
+    guid: Option<String>,
 }
 
 pub struct Window {
@@ -39,11 +50,20 @@ pub struct Window {
     event_pump: EventPump,
     video_sys: VideoSubsystem,
     controller_sys: GameControllerSubsystem,
-    _joystick_sys: JoystickSubsystem,
+    joystick_sys: JoystickSubsystem,
     _gl_sys: SdlGlContext,
 
     controllers: HashMap<u32, SdlController>,
 
+    /// Gamepads that were disconnected while input was being played back, and
+    /// so still need to be removed from their slots.
+    removed_gamepads: Vec<(u32, usize)>,
+
+    /// SDL's finger IDs are only unique per touch device, so each active finger
+    /// is given its own ID instead.
+    touch_ids: HashMap<(i64, i64), u64>,
+    next_touch_id: u64,
+
     window_visible: bool,
 
     key_repeat: bool,
@@ -59,6 +79,17 @@ impl Window {
 
         sdl2::hint::set("SDL_JOYSTICK_ALLOW_BACKGROUND_EVENTS", "1");
 
+        // Tetra generates its own mouse events from touches, so that they can be
+        // turned on and off at runtime.
+        sdl2::hint::set("SDL_TOUCH_MOUSE_EVENTS", "0");
+
+        // Mappings that are added before the first events are processed will apply to
+        // gamepads that were already plugged in, as SDL will report them as newly added.
+        #[cfg(feature = "embedded_gamepad_mappings")]
+        controller_sys
+            .load_mappings_from_read(&mut input::DEFAULT_GAMEPAD_MAPPINGS.as_bytes())
+            .map_err(|e| TetraError::PlatformError(e.to_string()))?;
+
         let gl_attr = video_sys.gl_attr();
 
         gl_attr.set_context_profile(GLProfile::Core);
@@ -170,10 +201,14 @@ impl Window {
             event_pump,
             video_sys,
             controller_sys,
-            _joystick_sys: joystick_sys,
+            joystick_sys,
             _gl_sys: gl_sys,
 
             controllers: HashMap::new(),
+            removed_gamepads: Vec::new(),
+
+            touch_ids: HashMap::new(),
+            next_touch_id: 0,
 
             window_visible: false,
 
@@ -199,6 +234,14 @@ impl Window {
         self.sdl_window.raise()
     }
 
+    pub fn is_focused(&self) -> bool {
+        self.sdl_window.has_input_focus()
+    }
+
+    pub fn is_minimized(&self) -> bool {
+        self.sdl_window.is_minimized()
+    }
+
     pub fn get_refresh_rate(&self) -> Result<i32> {
         self.sdl_window
             .display_mode()
@@ -422,7 +465,42 @@ impl Window {
     }
 
     pub fn get_gamepad_name(&self, platform_id: u32) -> String {
-        self.controllers[&platform_id].controller.name()
+        self.controllers
+            .get(&platform_id)
+            .map(|c| c.controller.name())
+            .unwrap_or_default()
+    }
+
+    pub fn add_gamepad_mappings(&mut self, mappings: &str) -> Result<usize> {
+        let count = self
+            .controller_sys
+            .load_mappings_from_read(&mut mappings.as_bytes())
+            .map_err(|e| TetraError::PlatformError(e.to_string()))?;
+
+        Ok(count as usize)
+    }
+
+    pub fn get_gamepad_type(&self, platform_id: u32) -> GamepadType {
+        self.controllers
+            .get(&platform_id)
+            .map(|c| c.gamepad_type)
+            .unwrap_or_default()
+    }
+
+    pub fn get_gamepad_guid(&self, platform_id: u32) -> Option<String> {
+        self.controllers
+            .get(&platform_id)
+            .and_then(|c| c.guid.clone())
+    }
+
+    pub fn get_gamepad_vendor_id(&self, platform_id: u32) -> Option<u16> {
+        self.controllers.get(&platform_id).and_then(|c| c.vendor_id)
+    }
+
+    pub fn get_gamepad_product_id(&self, platform_id: u32) -> Option<u16> {
+        self.controllers
+            .get(&platform_id)
+            .and_then(|c| c.product_id)
     }
 
     pub fn is_gamepad_vibration_supported(&self, platform_id: u32) -> bool {
@@ -491,14 +569,63 @@ impl Window {
     }
 }
 
+fn sdl_window(ctx: &mut Context) -> &mut Window {
+    match &mut ctx.window {
+        super::Window::Sdl(window) => window,
+        super::Window::Null(_) => unreachable!("SDL events should only be polled for SDL windows"),
+    }
+}
+
+/// Applies any gamepad connections and disconnections that were held back
+/// while input was being played back.
+fn sync_gamepad_slots<S, E>(ctx: &mut Context, state: &mut S) -> result::Result<(), E>
+where
+    S: State<E>,
+    E: From<TetraError>,
+{
+    if input::is_playing_back(ctx) {
+        return Ok(());
+    }
+
+    for (platform_id, slot) in mem::take(&mut sdl_window(ctx).removed_gamepads) {
+        if input::get_gamepad(ctx, slot).map(|pad| pad.platform_id) == Some(platform_id) {
+            input::remove_gamepad(ctx, slot);
+            input::handle_input_event(ctx, state, InputEvent::GamepadRemoved { id: slot })?;
+        }
+    }
+
+    let mut unassigned: Vec<u32> = sdl_window(ctx)
+        .controllers
+        .iter()
+        .filter(|(_, controller)| controller.slot.is_none())
+        .map(|(&id, _)| id)
+        .collect();
+
+    unassigned.sort_unstable();
+
+    for id in unassigned {
+        let slot = input::add_gamepad(ctx, id);
+
+        if let Some(controller) = sdl_window(ctx).controllers.get_mut(&id) {
+            controller.slot = Some(slot);
+        }
+
+        input::handle_input_event(ctx, state, InputEvent::GamepadAdded { id: slot })?;
+    }
+
+    Ok(())
+}
+
 pub fn handle_events<S, E>(ctx: &mut Context, state: &mut S) -> result::Result<(), E>
 where
     S: State<E>,
     E: From<TetraError>,
 {
-    while let Some(event) = ctx.window.event_pump.poll_event() {
+    sync_gamepad_slots(ctx, state)?;
+
+    while let Some(event) = sdl_window(ctx).event_pump.poll_event() {
         match event {
-            SdlEvent::Quit { .. } => ctx.running = false, // TODO: Add a way to override this
+            SdlEvent::Quit { .. } => window::request_quit(ctx, state)?,
 
             SdlEvent::Window { win_event, .. } => match win_event {
                 WindowEvent::SizeChanged(width, height) => {
@@ -536,17 +663,22 @@ where
                 ..
             } => {
                 if !repeat || ctx.window.is_key_repeat_enabled() {
-                    input::set_key_modifier_state(ctx, from_sdl_keymod(keymod));
+                    let modifiers = from_sdl_keymod(keymod);
 
                     if let Scancode::Escape = scancode {
                         if ctx.quit_on_escape {
-                            ctx.running = false;
+                            window::request_quit(ctx, state)?;
                         }
                     }
 
-                    if let Some(key) = from_sdl_scancode(scancode) {
-                        input::set_key_down(ctx, key);
-                        state.event(ctx, Event::KeyPressed { key })?;
+                    match from_sdl_scancode(scancode) {
+                        Some(key) => input::handle_input_event(
+                            ctx,
+                            state,
+                            InputEvent::KeyPressed { key, modifiers },
+                        )?,
+
+                        None => input::set_key_modifier_state(ctx, modifiers),
                     }
                 }
             }
@@ -556,38 +688,57 @@ where
                 keymod,
                 ..
             } => {
-                input::set_key_modifier_state(ctx, from_sdl_keymod(keymod));
+                let modifiers = from_sdl_keymod(keymod);
 
-                if let Some(key) = from_sdl_scancode(scancode) {
+                match from_sdl_scancode(scancode) {
                     // TODO: This can cause some inputs to be missed at low tick rates.
                     // Could consider buffering input releases like Otter2D does?
-                    input::set_key_up(ctx, key);
-                    state.event(ctx, Event::KeyReleased { key })?;
+                    Some(key) => input::handle_input_event(
+                        ctx,
+                        state,
+                        InputEvent::KeyReleased { key, modifiers },
+                    )?,
+
+                    None => input::set_key_modifier_state(ctx, modifiers),
                 }
             }
 
-            SdlEvent::MouseButtonDown { mouse_btn, .. } => {
+            SdlEvent::MouseButtonDown {
+                which, mouse_btn, ..
+            } if which != TOUCH_MOUSE_ID => {
                 if let Some(button) = into_mouse_button(mouse_btn) {
-                    input::set_mouse_button_down(ctx, button);
-                    state.event(ctx, Event::MouseButtonPressed { button })?;
+                    input::handle_input_event(
+                        ctx,
+                        state,
+                        InputEvent::MouseButtonPressed { button },
+                    )?;
                 }
             }
 
-            SdlEvent::MouseButtonUp { mouse_btn, .. } => {
+            SdlEvent::MouseButtonUp {
+                which, mouse_btn, ..
+            } if which != TOUCH_MOUSE_ID => {
                 if let Some(button) = into_mouse_button(mouse_btn) {
-                    input::set_mouse_button_up(ctx, button);
-                    state.event(ctx, Event::MouseButtonReleased { button })?;
+                    input::handle_input_event(
+                        ctx,
+                        state,
+                        InputEvent::MouseButtonReleased { button },
+                    )?;
                 }
             }
 
             SdlEvent::MouseMotion {
-                x, y, xrel, yrel, ..
-            } => {
+                which,
+                x,
+                y,
+                xrel,
+                yrel,
+                ..
+            } if which != TOUCH_MOUSE_ID => {
                 let position = Vec2::new(x as f32, y as f32);
                 let delta = Vec2::new(xrel as f32, yrel as f32);
 
-                input::set_mouse_position(ctx, position);
-                state.event(ctx, Event::MouseMoved { position, delta })?;
+                input::handle_input_event(ctx, state, InputEvent::MouseMoved { position, delta })?;
             }
 
             SdlEvent::MouseWheel {
@@ -598,13 +749,75 @@ where
                     _ => Vec2::new(x, y),
                 };
 
-                input::apply_mouse_wheel_movement(ctx, amount);
-                state.event(ctx, Event::MouseWheelMoved { amount })?
+                input::handle_input_event(ctx, state, InputEvent::MouseWheelMoved { amount })?;
+            }
+
+            SdlEvent::FingerDown {
+                touch_id,
+                finger_id,
+                x,
+                y,
+                pressure,
+                ..
+            } => {
+                let window = sdl_window(ctx);
+                let id = window.next_touch_id;
+
+                window.next_touch_id += 1;
+                window.touch_ids.insert((touch_id, finger_id), id);
+
+                let position = touch_position(ctx, x, y);
+
+                input::handle_input_event(
+                    ctx,
+                    state,
+                    InputEvent::TouchStarted {
+                        id,
+                        position,
+                        pressure,
+                    },
+                )?;
+            }
+
+            SdlEvent::FingerMotion {
+                touch_id,
+                finger_id,
+                x,
+                y,
+                pressure,
+                ..
+            } => {
+                if let Some(&id) = sdl_window(ctx).touch_ids.get(&(touch_id, finger_id)) {
+                    let position = touch_position(ctx, x, y);
+
+                    input::handle_input_event(
+                        ctx,
+                        state,
+                        InputEvent::TouchMoved {
+                            id,
+                            position,
+                            pressure,
+                        },
+                    )?;
+                }
+            }
+
+            SdlEvent::FingerUp {
+                touch_id,
+                finger_id,
+                x,
+                y,
+                ..
+            } => {
+                if let Some(id) = sdl_window(ctx).touch_ids.remove(&(touch_id, finger_id)) {
+                    let position = touch_position(ctx, x, y);
+
+                    input::handle_input_event(ctx, state, InputEvent::TouchEnded { id, position })?;
+                }
             }
 
             SdlEvent::TextInput { text, .. } => {
-                input::push_text_input(ctx, &text);
-                state.event(ctx, Event::TextInput { text })?;
+                input::handle_input_event(ctx, state, InputEvent::TextInput { text })?;
             }
 
             SdlEvent::DropFile { filename, .. } => {
@@ -617,61 +830,87 @@ where
             }
 
             SdlEvent::ControllerDeviceAdded { which, .. } => {
-                let mut controller = ctx
-                    .window
+                let mut controller = sdl_window(ctx)
                     .controller_sys
                     .open(which)
                     .map_err(|e| TetraError::PlatformError(e.to_string()))?;
 
                 let id = controller.instance_id();
-                let slot = input::add_gamepad(ctx, id);
+
+                // The gamepad slots belong to the recording during playback, so the
+                // controller will be given a slot once playback has finished.
+                let slot = if input::is_playing_back(ctx) {
+                    None
+                } else {
+                    Some(input::add_gamepad(ctx, id))
+                };
 
                 let supports_rumble = controller.set_rumble(0, 0, 0).is_ok();
+                let (gamepad_type, vendor_id, product_id) = get_controller_info(which);
+
+                let guid = sdl_window(ctx)
+                    .joystick_sys
+                    .device_guid(which)
+                    .ok()
+                    .filter(|guid| !guid.is_zero())
+                    .map(|guid| guid.string());
 
-                ctx.window.controllers.insert(
+                sdl_window(ctx).controllers.insert(
                     id,
                     SdlController {
                         controller,
                         slot,
                         supports_rumble,
+                        gamepad_type,
+                        vendor_id,
+                        product_id,
+                        guid,
                     },
                 );
 
-                state.event(ctx, Event::GamepadAdded { id: slot })?;
+                if let Some(slot) = slot {
+                    input::handle_input_event(ctx, state, InputEvent::GamepadAdded { id: slot })?;
+                }
             }
 
             SdlEvent::ControllerDeviceRemoved { which, .. } => {
-                let controller = ctx.window.controllers.remove(&which).unwrap();
-                input::remove_gamepad(ctx, controller.slot);
+                let controller = sdl_window(ctx).controllers.remove(&which).unwrap();
 
-                state.event(
-                    ctx,
-                    Event::GamepadRemoved {
-                        id: controller.slot,
-                    },
-                )?;
+                if let Some(slot) = controller.slot {
+                    if input::is_playing_back(ctx) {
+                        sdl_window(ctx).removed_gamepads.push((which, slot));
+                    } else {
+                        input::remove_gamepad(ctx, slot);
+
+                        input::handle_input_event(
+                            ctx,
+                            state,
+                            InputEvent::GamepadRemoved { id: slot },
+                        )?;
+                    }
+                }
             }
 
             SdlEvent::ControllerButtonDown { which, button, .. } => {
-                if let Some(slot) = ctx.window.controllers.get(&which).map(|c| c.slot) {
-                    if let Some(pad) = input::get_gamepad_mut(ctx, slot) {
-                        if let Some(button) = into_gamepad_button(button) {
-                            pad.set_button_down(button);
-                            state.event(ctx, Event::GamepadButtonPressed { id: slot, button })?;
-                        }
+                if let Some(slot) = sdl_window(ctx).controllers.get(&which).and_then(|c| c.slot) {
+                    if let Some(button) = into_gamepad_button(button) {
+                        input::handle_input_event(
+                            ctx,
+                            state,
+                            InputEvent::GamepadButtonPressed { id: slot, button },
+                        )?;
                     }
                 }
             }
 
             SdlEvent::ControllerButtonUp { which, button, .. } => {
-                if let Some(slot) = ctx.window.controllers.get(&which).map(|c| c.slot) {
-                    if let Some(pad) = input::get_gamepad_mut(ctx, slot) {
-                        if let Some(button) = into_gamepad_button(button) {
-                            // TODO: This can cause some inputs to be missed at low tick rates.
-                            // Could consider buffering input releases like Otter2D does?
-                            pad.set_button_up(button);
-                            state.event(ctx, Event::GamepadButtonReleased { id: slot, button })?;
-                        }
+                if let Some(slot) = sdl_window(ctx).controllers.get(&which).and_then(|c| c.slot) {
+                    if let Some(button) = into_gamepad_button(button) {
+                        input::handle_input_event(
+                            ctx,
+                            state,
+                            InputEvent::GamepadButtonReleased { id: slot, button },
+                        )?;
                     }
                 }
             }
@@ -679,76 +918,22 @@ where
             SdlEvent::ControllerAxisMotion {
                 which, axis, value, ..
             } => {
-                if let Some(slot) = ctx.window.controllers.get(&which).map(|c| c.slot) {
-                    if let Some(pad) = input::get_gamepad_mut(ctx, slot) {
-                        let axis = axis.into();
-
-                        let mapped_value = if value > 0 {
-                            f32::from(value) / 32767.0
-                        } else {
-                            f32::from(value) / 32768.0
-                        };
-
-                        pad.set_axis_position(axis, mapped_value);
-
-                        let button = match axis {
-                            GamepadAxis::LeftTrigger => Some(GamepadButton::LeftTrigger),
-                            GamepadAxis::RightTrigger => Some(GamepadButton::RightTrigger),
-                            _ => None,
-                        };
-
-                        if let Some(button) = button {
-                            if value > 0 {
-                                let pressed = pad.set_button_down(button);
-
-                                if pressed {
-                                    state.event(
-                                        ctx,
-                                        Event::GamepadButtonPressed { id: slot, button },
-                                    )?;
-                                }
-                            } else {
-                                let released = pad.set_button_up(button);
-
-                                if released {
-                                    state.event(
-                                        ctx,
-                                        Event::GamepadButtonReleased { id: slot, button },
-                                    )?;
-                                }
-                            }
-                        }
-
-                        state.event(
-                            ctx,
-                            Event::GamepadAxisMoved {
-                                id: slot,
-                                axis,
-                                position: mapped_value,
-                            },
-                        )?;
-
-                        let stick = match axis {
-                            GamepadAxis::LeftStickX | GamepadAxis::LeftStickY => {
-                                Some(GamepadStick::LeftStick)
-                            }
-                            GamepadAxis::RightStickX | GamepadAxis::RightStickY => {
-                                Some(GamepadStick::RightStick)
-                            }
-                            _ => None,
-                        };
-
-                        if let Some(stick) = stick {
-                            state.event(
-                                ctx,
-                                Event::GamepadStickMoved {
-                                    id: slot,
-                                    stick,
-                                    position: input::get_gamepad_stick_position(ctx, slot, stick),
-                                },
-                            )?;
-                        }
-                    }
+                if let Some(slot) = sdl_window(ctx).controllers.get(&which).and_then(|c| c.slot) {
+                    let position = if value > 0 {
+                        f32::from(value) / 32767.0
+                    } else {
+                        f32::from(value) / 32768.0
+                    };
+
+                    input::handle_input_event(
+                        ctx,
+                        state,
+                        InputEvent::GamepadAxisMoved {
+                            id: slot,
+                            axis: axis.into(),
+                            position,
+                        },
+                    )?;
                 }
             }
 
@@ -759,6 +944,16 @@ where
     Ok(())
 }
 
+/// The mouse ID that SDL uses for mouse events that were generated from touches.
+const TOUCH_MOUSE_ID: u32 = u32::MAX;
+
+/// Converts a touch position from SDL's normalized co-ordinates to window co-ordinates.
+fn touch_position(ctx: &mut Context, x: f32, y: f32) -> Vec2<f32> {
+    let (width, height) = sdl_window(ctx).get_window_size();
+
+    Vec2::new(x * width as f32, y * height as f32)
+}
+
 fn into_mouse_button(button: SdlMouseButton) -> Option<MouseButton> {
     match button {
         SdlMouseButton::Left => Some(MouseButton::Left),
@@ -995,6 +1190,63 @@ fn from_sdl_keymod(keymod: Mod) -> KeyModifierState {
     }
 }
 
+extern "C" {
+    // The bindings in `sdl2-sys` declare this as returning an enum, which is only
+    // sound if SDL never returns a type that was added after the bindings were
+    // generated. Returning an int avoids that.
+    fn SDL_GameControllerTypeForIndex(joystick_index: c_int) -> c_int;
+}
+
+// Values of `SDL_GameControllerType`. The Joy-Con types are newer than the bindings,
+// so all of them are defined here for consistency.
+const SDL_CONTROLLER_TYPE_XBOX360: c_int = 1;
+const SDL_CONTROLLER_TYPE_XBOXONE: c_int = 2;
+const SDL_CONTROLLER_TYPE_PS3: c_int = 3;
+const SDL_CONTROLLER_TYPE_PS4: c_int = 4;
+const SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_PRO: c_int = 5;
+const SDL_CONTROLLER_TYPE_PS5: c_int = 7;
+const SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_JOYCON_LEFT: c_int = 11;
+const SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_JOYCON_RIGHT: c_int = 12;
+const SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_JOYCON_PAIR: c_int = 13;
+
+const NINTENDO_VENDOR_ID: u16 = 0x057e;
+const JOY_CON_LEFT_PRODUCT_ID: u16 = 0x2006;
+const JOY_CON_RIGHT_PRODUCT_ID: u16 = 0x2007;
+
+/// Returns the type, vendor ID and product ID of the controller at the given device index.
+fn get_controller_info(device_index: u32) -> (GamepadType, Option<u16>, Option<u16>) {
+    let device_index = device_index as c_int;
+
+    let (controller_type, vendor_id, product_id) = unsafe {
+        (
+            SDL_GameControllerTypeForIndex(device_index),
+            sdl2::sys::SDL_JoystickGetDeviceVendor(device_index),
+            sdl2::sys::SDL_JoystickGetDeviceProduct(device_index),
+        )
+    };
+
+    let gamepad_type = match (controller_type, vendor_id, product_id) {
+        (SDL_CONTROLLER_TYPE_XBOX360, _, _) => GamepadType::Xbox360,
+        (SDL_CONTROLLER_TYPE_XBOXONE, _, _) => GamepadType::XboxOne,
+        (SDL_CONTROLLER_TYPE_PS3, _, _) => GamepadType::PlayStation3,
+        (SDL_CONTROLLER_TYPE_PS4, _, _) => GamepadType::PlayStation4,
+        (SDL_CONTROLLER_TYPE_PS5, _, _) => GamepadType::PlayStation5,
+        (SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_JOYCON_LEFT, _, _)
+        | (_, NINTENDO_VENDOR_ID, JOY_CON_LEFT_PRODUCT_ID) => GamepadType::JoyConLeft,
+        (SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_JOYCON_RIGHT, _, _)
+        | (_, NINTENDO_VENDOR_ID, JOY_CON_RIGHT_PRODUCT_ID) => GamepadType::JoyConRight,
+        (SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_JOYCON_PAIR, _, _) => GamepadType::JoyConPair,
+        (SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_PRO, _, _) => GamepadType::SwitchPro,
+        _ => GamepadType::Generic,
+    };
+
+    // SDL returns zero if the IDs are not available.
+    let vendor_id = Some(vendor_id).filter(|&id| id != 0);
+    let product_id = Some(product_id).filter(|&id| id != 0);
+
+    (gamepad_type, vendor_id, product_id)
+}
+
 fn into_gamepad_button(button: SdlGamepadButton) -> Option<GamepadButton> {
     match button {
         SdlGamepadButton::A => Some(GamepadButton::A),
@@ -0,0 +1,375 @@
+//! Functions and types relating to managing a stack of scenes.
+//!
+//! Most games are made up of several distinct screens (e.g. a title screen, the
+//! gameplay itself, and a pause menu), some of which sit on top of others. The
+//! [`SceneStack`] type implements this pattern for you - each screen is represented
+//! by a [`Scene`], and scenes can push, pop or replace each other by returning a
+//! [`Transition`].
+//!
+//! `SceneStack` implements [`State`], so it can be passed straight to
+//! [`Context::run`](crate::Context::run).
+
+use crate::window;
+use crate::{Context, Event, State, TetraError};
+
+/// A change that should be made to a [`SceneStack`].
+///
+/// This is returned from the [`update`](Scene::update) and [`event`](Scene::event)
+/// methods of a [`Scene`].
+#[non_exhaustive]
+pub enum Transition<E = TetraError> {
+    /// The stack should not be changed.
+    None,
+
+    /// The given scene should be pushed onto the top of the stack.
+    Push(Box<dyn Scene<E>>),
+
+    /// The scene at the top of the stack should be removed.
+    ///
+    /// If this leaves the stack empty, the game will quit.
+    Pop,
+
+    /// The scene at the top of the stack should be replaced with the given scene.
+    Replace(Box<dyn Scene<E>>),
+
+    /// The game should quit.
+    Quit,
+}
+
+/// Implemented by types that represent a single scene within a [`SceneStack`].
+///
+/// This is very similar to the [`State`] trait, but the [`update`](Scene::update)
+/// and [`event`](Scene::event) methods return a [`Transition`], which allows the
+/// scene to modify the stack that it is contained in.
+///
+/// # Pass-through
+///
+/// By default, only the scene at the top of the stack is updated and drawn. A scene
+/// can opt into letting the scene underneath it be updated or drawn by overriding
+/// [`update_previous`](Scene::update_previous) and [`draw_previous`](Scene::draw_previous)
+/// respectively. For example, a pause menu would usually return `true` from
+/// `draw_previous` (so the game is still visible behind it) and `false` from
+/// `update_previous` (so that the game is frozen).
+///
+/// Pass-through applies recursively - if the scene underneath also allows pass-through,
+/// the scene beneath that will be updated/drawn as well, and so on.
+#[allow(unused_variables)]
+pub trait Scene<E = TetraError> {
+    /// Called when it is time for the scene to update.
+    fn update(&mut self, ctx: &mut Context) -> Result<Transition<E>, E> {
+        Ok(Transition::None)
+    }
+
+    /// Called when it is time for the scene to be drawn.
+    fn draw(&mut self, ctx: &mut Context) -> Result<(), E> {
+        Ok(())
+    }
+
+    /// Called when a window or input event occurs.
+    ///
+    /// Events are delivered to the same scenes that are being updated - i.e. the scene
+    /// at the top of the stack, plus any scenes that are being updated via pass-through.
+    fn event(&mut self, ctx: &mut Context, event: Event) -> Result<Transition<E>, E> {
+        Ok(Transition::None)
+    }
+
+    /// Returns whether or not the scene underneath this one should be updated
+    /// (and receive events).
+    ///
+    /// Defaults to `false`.
+    fn update_previous(&self) -> bool {
+        false
+    }
+
+    /// Returns whether or not the scene underneath this one should be drawn.
+    ///
+    /// Defaults to `false`.
+    fn draw_previous(&self) -> bool {
+        false
+    }
+}
+
+/// A stack of [`Scene`]s, which can be run as a [`State`].
+///
+/// Scenes are updated and drawn from the bottom of the stack upwards, so the scene
+/// at the top of the stack is always drawn last.
+///
+/// Transitions are applied once every scene has been updated (or has received
+/// the current event), in the order that they were returned. They always act on
+/// the top of the stack, no matter which scene returned them.
+///
+/// # Examples
+///
+/// The [`scenes`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/scenes.rs)
+/// example demonstrates how to use a `SceneStack` to implement a title screen and
+/// a pause menu.
+pub struct SceneStack<E = TetraError> {
+    scenes: Vec<Box<dyn Scene<E>>>,
+}
+
+impl<E> SceneStack<E> {
+    /// Creates a new `SceneStack`, containing a single initial scene.
+    pub fn new<S>(initial: S) -> SceneStack<E>
+    where
+        S: Scene<E> + 'static,
+    {
+        SceneStack {
+            scenes: vec![Box::new(initial)],
+        }
+    }
+
+    /// Pushes a scene onto the top of the stack.
+    pub fn push<S>(&mut self, scene: S)
+    where
+        S: Scene<E> + 'static,
+    {
+        self.scenes.push(Box::new(scene));
+    }
+
+    /// Removes the scene at the top of the stack, returning it.
+    ///
+    /// Unlike [`Transition::Pop`], this will not quit the game if the stack is
+    /// left empty.
+    pub fn pop(&mut self) -> Option<Box<dyn Scene<E>>> {
+        self.scenes.pop()
+    }
+
+    /// Returns the number of scenes in the stack.
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// Returns `true` if the stack contains no scenes.
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// Returns the index of the lowest scene that should be updated.
+    fn update_start(&self) -> usize {
+        self.pass_through_start(|scene| scene.update_previous())
+    }
+
+    /// Returns the index of the lowest scene that should be drawn.
+    fn draw_start(&self) -> usize {
+        self.pass_through_start(|scene| scene.draw_previous())
+    }
+
+    fn pass_through_start<F>(&self, pass_through: F) -> usize
+    where
+        F: Fn(&dyn Scene<E>) -> bool,
+    {
+        let mut start = self.scenes.len().saturating_sub(1);
+
+        while start > 0 && pass_through(self.scenes[start].as_ref()) {
+            start -= 1;
+        }
+
+        start
+    }
+
+    fn apply(&mut self, ctx: &mut Context, transitions: Vec<Transition<E>>) {
+        for transition in transitions {
+            match transition {
+                Transition::None => {}
+
+                Transition::Push(scene) => self.scenes.push(scene),
+
+                Transition::Pop => {
+                    self.scenes.pop();
+
+                    if self.scenes.is_empty() {
+                        window::quit(ctx);
+                    }
+                }
+
+                Transition::Replace(scene) => {
+                    self.scenes.pop();
+                    self.scenes.push(scene);
+                }
+
+                Transition::Quit => window::quit(ctx),
+            }
+        }
+    }
+}
+
+impl<E> State<E> for SceneStack<E>
+where
+    E: From<TetraError>,
+{
+    fn update(&mut self, ctx: &mut Context) -> Result<(), E> {
+        let start = self.update_start();
+        let mut transitions = Vec::new();
+
+        for scene in self.scenes.iter_mut().skip(start) {
+            transitions.push(scene.update(ctx)?);
+        }
+
+        self.apply(ctx, transitions);
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> Result<(), E> {
+        let start = self.draw_start();
+
+        for scene in self.scenes.iter_mut().skip(start) {
+            scene.draw(ctx)?;
+        }
+
+        Ok(())
+    }
+
+    fn event(&mut self, ctx: &mut Context, event: Event) -> Result<(), E> {
+        let start = self.update_start();
+        let mut transitions = Vec::new();
+
+        for scene in self.scenes.iter_mut().skip(start) {
+            transitions.push(scene.event(ctx, event.clone())?);
+        }
+
+        self.apply(ctx, transitions);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ContextBuilder;
+
+    type Log = Rc<RefCell<Vec<String>>>;
+    type NextScene = Box<dyn FnMut(&Log) -> Transition>;
+
+    struct TestScene {
+        name: &'static str,
+        log: Log,
+        update_previous: bool,
+        draw_previous: bool,
+        next: Option<NextScene>,
+    }
+
+    impl TestScene {
+        fn new(name: &'static str, log: &Log) -> TestScene {
+            TestScene {
+                name,
+                log: Rc::clone(log),
+                update_previous: false,
+                draw_previous: false,
+                next: None,
+            }
+        }
+    }
+
+    impl Scene for TestScene {
+        fn update(&mut self, _: &mut Context) -> crate::Result<Transition> {
+            self.log.borrow_mut().push(format!("update {}", self.name));
+
+            Ok(match &mut self.next {
+                Some(next) => next(&self.log),
+                None => Transition::None,
+            })
+        }
+
+        fn draw(&mut self, _: &mut Context) -> crate::Result {
+            self.log.borrow_mut().push(format!("draw {}", self.name));
+            Ok(())
+        }
+
+        fn update_previous(&self) -> bool {
+            self.update_previous
+        }
+
+        fn draw_previous(&self) -> bool {
+            self.draw_previous
+        }
+    }
+
+    fn tick(ctx: &mut Context, stack: &mut SceneStack) {
+        ctx.tick(stack, Duration::from_secs_f64(1.0 / 60.0))
+            .unwrap();
+    }
+
+    fn take(log: &Log) -> Vec<String> {
+        log.borrow_mut().drain(..).collect()
+    }
+
+    #[test]
+    fn only_top_scene_runs_by_default() {
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        let log = Log::default();
+        let mut stack = SceneStack::new(TestScene::new("game", &log));
+        stack.push(TestScene::new("pause", &log));
+
+        tick(&mut ctx, &mut stack);
+
+        assert_eq!(take(&log), ["update pause", "draw pause"]);
+    }
+
+    #[test]
+    fn pass_through() {
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        let log = Log::default();
+        let mut stack = SceneStack::new(TestScene::new("game", &log));
+
+        let mut pause = TestScene::new("pause", &log);
+        pause.draw_previous = true;
+        stack.push(pause);
+
+        tick(&mut ctx, &mut stack);
+
+        assert_eq!(take(&log), ["update pause", "draw game", "draw pause"]);
+    }
+
+    #[test]
+    fn transitions() {
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        let log = Log::default();
+
+        let mut title = TestScene::new("title", &log);
+        title.next = Some(Box::new(|log| {
+            Transition::Replace(Box::new(TestScene::new("game", log)))
+        }));
+
+        let mut stack = SceneStack::new(title);
+
+        tick(&mut ctx, &mut stack);
+        assert_eq!(take(&log), ["update title", "draw game"]);
+        assert_eq!(stack.len(), 1);
+
+        let mut menu = TestScene::new("menu", &log);
+        menu.next = Some(Box::new(|_| Transition::Pop));
+        stack.push(menu);
+
+        tick(&mut ctx, &mut stack);
+        assert_eq!(take(&log), ["update menu", "draw game"]);
+        assert_eq!(stack.len(), 1);
+        assert!(ctx.is_running());
+
+        stack.pop();
+        let mut last = TestScene::new("last", &log);
+        last.next = Some(Box::new(|_| Transition::Pop));
+        stack.push(last);
+
+        tick(&mut ctx, &mut stack);
+        assert!(stack.is_empty());
+        assert!(!ctx.is_running());
+    }
+}
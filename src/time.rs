@@ -1,10 +1,16 @@
 //! Functions and types relating to measuring and manipulating time.
 
+mod scheduler;
+
 use std::collections::VecDeque;
+use std::hint;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use std::time::Duration;
+use crate::window;
+use crate::{Context, ContextBuilder};
 
-use crate::Context;
+pub use scheduler::*;
 
 /// The different timestep modes that a game can have.
 ///
@@ -48,7 +54,7 @@ pub enum Timestep {
 }
 
 pub(crate) struct FpsTracker {
-    buffer: VecDeque<f64>,
+    buffer: VecDeque<Duration>,
 }
 
 impl FpsTracker {
@@ -63,24 +69,111 @@ impl FpsTracker {
             self.buffer.pop_front();
         }
 
-        self.buffer.push_back(frame_time.as_secs_f64());
+        self.buffer.push_back(frame_time);
     }
 
     fn get_fps(&self) -> f64 {
-        1.0 / (self.buffer.iter().sum::<f64>() / self.buffer.len() as f64)
+        1.0 / self.get_average_secs()
+    }
+
+    fn get_average_secs(&self) -> f64 {
+        self.buffer.iter().map(Duration::as_secs_f64).sum::<f64>() / self.buffer.len() as f64
     }
 }
 
+/// How long each part of a single frame took.
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct FrameTimings {
+    pub(crate) events: Duration,
+    pub(crate) update: Duration,
+    pub(crate) draw: Duration,
+    pub(crate) present: Duration,
+    pub(crate) updates: u32,
+    pub(crate) dropped: Duration,
+}
+
+/// Statistics about how long the game's frames are taking.
+///
+/// The frame time statistics are measured over the last 200 frames (the same as
+/// [`get_fps`]), while the rest of the fields describe the most recent frame.
+///
+/// This can be used to detect hitches that an average frame rate would hide - for
+/// example, a game that usually runs at 60 FPS but occasionally stalls for 100ms will
+/// still report an FPS of about 59, but its `max_frame_time` will be 100ms.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct FrameStats {
+    /// The shortest frame time over the last 200 frames.
+    pub min_frame_time: Duration,
+
+    /// The longest frame time over the last 200 frames.
+    pub max_frame_time: Duration,
+
+    /// The mean frame time over the last 200 frames.
+    pub average_frame_time: Duration,
+
+    /// The median frame time over the last 200 frames.
+    pub median_frame_time: Duration,
+
+    /// The 95th percentile frame time over the last 200 frames - i.e. 95% of frames
+    /// were at least this fast.
+    pub p95_frame_time: Duration,
+
+    /// The 99th percentile frame time over the last 200 frames - i.e. 99% of frames
+    /// were at least this fast.
+    pub p99_frame_time: Duration,
+
+    /// How long it took to handle events during the most recent frame.
+    pub event_time: Duration,
+
+    /// How long the [`update`](crate::State::update) calls took during the most
+    /// recent frame.
+    pub update_time: Duration,
+
+    /// How long the [`draw`](crate::State::draw) call took during the most recent
+    /// frame.
+    pub draw_time: Duration,
+
+    /// How long it took to present the most recent frame to the screen. If vsync
+    /// is enabled, this includes the time spent waiting for it.
+    pub present_time: Duration,
+
+    /// The number of times that [`update`](crate::State::update) was called during
+    /// the most recent frame.
+    ///
+    /// When using a fixed timestep, this can be zero (if the game is rendering
+    /// faster than it is updating) or more than one (if the game is falling behind).
+    pub updates: u32,
+
+    /// The amount of time that was discarded during the most recent frame, because
+    /// the game had fallen too far behind to catch up.
+    ///
+    /// When using a fixed timestep, the game will run at most eight updates in a single
+    /// frame - any time beyond that is dropped, and the game will appear to slow down.
+    /// This will always be zero when using a variable timestep.
+    pub dropped_time: Duration,
+}
+
 pub(crate) struct TimeContext {
     pub(crate) fps_tracker: FpsTracker,
+    pub(crate) last_frame: FrameTimings,
     pub(crate) ticks_per_second: Option<f64>,
     pub(crate) tick_rate: Option<Duration>,
     pub(crate) delta_time: Duration,
+    pub(crate) unscaled_delta_time: Duration,
+    pub(crate) unscaled_time: Duration,
     pub(crate) accumulator: Duration,
+    pub(crate) last_time: Option<Instant>,
+    pub(crate) time_scale: f64,
+    pub(crate) paused: bool,
+    pub(crate) target_fps: Option<f64>,
+    pub(crate) background_fps: Option<f64>,
 }
 
 impl TimeContext {
-    pub(crate) fn new(timestep: Timestep) -> TimeContext {
+    pub(crate) fn new(settings: &ContextBuilder) -> TimeContext {
+        let timestep = settings.timestep;
+
         let ticks_per_second = match timestep {
             Timestep::Fixed(tps) => Some(tps),
             Timestep::Variable => None,
@@ -93,17 +186,27 @@ impl TimeContext {
 
         TimeContext {
             fps_tracker: FpsTracker::new(),
+            last_frame: FrameTimings::default(),
             ticks_per_second,
             tick_rate,
             delta_time: Duration::from_secs(0),
+            unscaled_delta_time: Duration::from_secs(0),
+            unscaled_time: Duration::from_secs(0),
             accumulator: Duration::from_secs(0),
+            last_time: None,
+            time_scale: 1.0,
+            paused: false,
+            target_fps: settings.target_fps,
+            background_fps: settings.background_fps,
         }
     }
 }
 
 pub(crate) fn reset(ctx: &mut Context) {
     ctx.time.delta_time = Duration::from_secs(0);
+    ctx.time.unscaled_delta_time = Duration::from_secs(0);
     ctx.time.accumulator = Duration::from_secs(0);
+    ctx.time.last_time = None;
 }
 
 /// Returns the amount of time that has passed since the last update or draw.
@@ -116,10 +219,21 @@ pub(crate) fn reset(ctx: &mut Context) {
 /// When using a fixed time step, calling this function during an update will always
 /// return the configured update rate. This is to prevent floating point error/non-determinism
 /// from creeping into your game's calculations!
+///
+/// Outside of updates, the returned value is affected by the [time scale](set_time_scale),
+/// and will be zero while the game is [paused](set_paused). If you need the real amount
+/// of time that has passed (e.g. for animating a pause menu), use
+/// [`get_unscaled_delta_time`] instead.
 pub fn get_delta_time(ctx: &Context) -> Duration {
     ctx.time.delta_time
 }
 
+/// Returns the real amount of time that the current frame took, ignoring the
+/// [time scale](set_time_scale) and whether the game is [paused](set_paused).
+pub fn get_unscaled_delta_time(ctx: &Context) -> Duration {
+    ctx.time.unscaled_delta_time
+}
+
 /// Returns the amount of time that has accumulated between updates.
 ///
 /// When using a fixed time step, as time passes, this value will increase;
@@ -191,3 +305,220 @@ pub fn set_timestep(ctx: &mut Context, timestep: Timestep) {
 pub fn get_fps(ctx: &Context) -> f64 {
     ctx.time.fps_tracker.get_fps()
 }
+
+/// Returns detailed statistics about how long the game's frames are taking.
+///
+/// If no frames have been run yet, all of the fields will be zero.
+pub fn get_frame_stats(ctx: &Context) -> FrameStats {
+    let frame = &ctx.time.last_frame;
+
+    let mut stats = FrameStats {
+        event_time: frame.events,
+        update_time: frame.update,
+        draw_time: frame.draw,
+        present_time: frame.present,
+        updates: frame.updates,
+        dropped_time: frame.dropped,
+        ..FrameStats::default()
+    };
+
+    let tracker = &ctx.time.fps_tracker;
+
+    if !tracker.buffer.is_empty() {
+        let mut sorted: Vec<Duration> = tracker.buffer.iter().copied().collect();
+        sorted.sort_unstable();
+
+        stats.min_frame_time = sorted[0];
+        stats.max_frame_time = sorted[sorted.len() - 1];
+        stats.average_frame_time = Duration::from_secs_f64(tracker.get_average_secs());
+        stats.median_frame_time = percentile(&sorted, 0.5);
+        stats.p95_frame_time = percentile(&sorted, 0.95);
+        stats.p99_frame_time = percentile(&sorted, 0.99);
+    }
+
+    stats
+}
+
+/// Returns the given percentile of a sorted, non-empty list, using the
+/// nearest-rank method.
+fn percentile(sorted: &[Duration], percentile: f64) -> Duration {
+    let rank = (percentile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// The maximum value that the [time scale](set_time_scale) can be set to.
+pub const MAX_TIME_SCALE: f64 = 1000.0;
+
+/// Gets the speed at which game time passes, relative to real time.
+pub fn get_time_scale(ctx: &Context) -> f64 {
+    ctx.time.time_scale
+}
+
+/// Sets the speed at which game time passes, relative to real time.
+///
+/// For example, `0.5` will make the game run at half speed, and `2.0` will make it
+/// run at double speed. This can be used for slow motion effects.
+///
+/// When using a fixed time step, this changes how often updates happen, rather than
+/// the [delta time](get_delta_time) that each update sees - this keeps your game's
+/// updates deterministic. When using a variable time step, the delta time is scaled
+/// instead.
+///
+/// Values will be clamped between `0.0` and [`MAX_TIME_SCALE`]. `NaN` will be ignored.
+///
+/// Defaults to `1.0`.
+pub fn set_time_scale(ctx: &mut Context, time_scale: f64) {
+    if time_scale.is_nan() {
+        return;
+    }
+
+    ctx.time.time_scale = time_scale.clamp(0.0, MAX_TIME_SCALE);
+}
+
+/// Returns whether or not the game is paused.
+pub fn is_paused(ctx: &Context) -> bool {
+    ctx.time.paused
+}
+
+/// Sets whether or not the game is paused.
+///
+/// While the game is paused, [`State::update`](crate::State::update) will not be
+/// called, but the game will still be drawn and receive events. This can be used for
+/// pause menus, or for 'hit-stop' effects, where the action freezes for a few frames.
+///
+/// Time that passes while the game is paused is discarded, so the game will not try
+/// to catch up on the updates that it missed when it is unpaused.
+///
+/// Defaults to `false`.
+pub fn set_paused(ctx: &mut Context, paused: bool) {
+    ctx.time.paused = paused;
+}
+
+/// Returns the maximum number of frames that will be run per second.
+pub fn get_target_fps(ctx: &Context) -> Option<f64> {
+    ctx.time.target_fps
+}
+
+/// Sets the maximum number of frames that will be run per second.
+///
+/// See [`ContextBuilder::target_fps`] for more details.
+pub fn set_target_fps(ctx: &mut Context, target_fps: Option<f64>) {
+    ctx.time.target_fps = target_fps;
+}
+
+/// Returns the maximum number of frames that will be run per second while the
+/// window is unfocused or minimized.
+pub fn get_background_fps(ctx: &Context) -> Option<f64> {
+    ctx.time.background_fps
+}
+
+/// Sets the maximum number of frames that will be run per second while the
+/// window is unfocused or minimized.
+///
+/// See [`ContextBuilder::background_fps`] for more details.
+pub fn set_background_fps(ctx: &mut Context, background_fps: Option<f64>) {
+    ctx.time.background_fps = background_fps;
+}
+
+/// Returns the minimum amount of time that the current frame should take, based on
+/// the target FPS settings and the state of the window.
+pub(crate) fn get_frame_target(ctx: &Context) -> Option<Duration> {
+    let in_background = !window::is_focused(ctx) || window::is_minimized(ctx);
+
+    let fps = match ctx.time.background_fps {
+        Some(background_fps) if in_background => Some(background_fps),
+        _ => ctx.time.target_fps,
+    };
+
+    fps.filter(|fps| *fps > 0.0)
+        .map(|fps| Duration::from_secs_f64(1.0 / fps))
+}
+
+/// Blocks the current thread until the given point in time.
+///
+/// Sleeping usually overshoots by a millisecond or so (and sometimes much more on
+/// Windows), so this sleeps until shortly before the deadline, and then spins for
+/// the rest of the time.
+pub(crate) fn wait_until(deadline: Instant) {
+    const SPIN_TIME: Duration = Duration::from_millis(2);
+
+    loop {
+        let now = Instant::now();
+
+        if now >= deadline {
+            break;
+        }
+
+        let remaining = deadline - now;
+
+        if remaining > SPIN_TIME {
+            thread::sleep(remaining - SPIN_TIME);
+        } else {
+            hint::spin_loop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ContextBuilder;
+
+    struct Empty;
+
+    impl crate::State for Empty {}
+
+    #[test]
+    fn frame_target_uses_background_fps() {
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .target_fps(Some(50.0))
+            .background_fps(Some(10.0))
+            .build()
+            .unwrap();
+
+        assert_eq!(get_frame_target(&ctx), Some(Duration::from_millis(20)));
+
+        window::minimize(&mut ctx);
+        assert_eq!(get_frame_target(&ctx), Some(Duration::from_millis(100)));
+
+        set_background_fps(&mut ctx, None);
+        assert_eq!(get_frame_target(&ctx), Some(Duration::from_millis(20)));
+
+        window::restore(&mut ctx);
+        set_target_fps(&mut ctx, None);
+        assert_eq!(get_frame_target(&ctx), None);
+    }
+
+    #[test]
+    fn time_scale_is_clamped() {
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .build()
+            .unwrap();
+
+        set_time_scale(&mut ctx, -1.0);
+        assert_eq!(get_time_scale(&ctx), 0.0);
+
+        set_time_scale(&mut ctx, f64::INFINITY);
+        assert_eq!(get_time_scale(&ctx), MAX_TIME_SCALE);
+
+        set_time_scale(&mut ctx, f64::NAN);
+        assert_eq!(get_time_scale(&ctx), MAX_TIME_SCALE);
+
+        set_time_scale(&mut ctx, 1e300);
+        assert_eq!(get_time_scale(&ctx), MAX_TIME_SCALE);
+
+        // This would panic if the time scale wasn't clamped.
+        ctx.tick(&mut Empty, Duration::from_secs(60)).unwrap();
+    }
+
+    #[test]
+    fn wait_until_reaches_deadline() {
+        let deadline = Instant::now() + Duration::from_millis(5);
+
+        wait_until(deadline);
+
+        assert!(Instant::now() >= deadline);
+    }
+}
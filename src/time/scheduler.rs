@@ -0,0 +1,462 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::time;
+use crate::Context;
+
+/// A handle to a timer that has been added to a [`Scheduler`].
+///
+/// This can be used to check on the timer, or to cancel it before it fires.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+/// Keeps track of a set of timers, and reports when they fire.
+///
+/// Each timer carries a value of type `T`, which is returned from [`update`](Scheduler::update)
+/// when the timer fires. This is usually an enum describing what should happen (e.g.
+/// `SpawnEnemy` or `CooldownFinished`), but it can be anything - for example, a function
+/// pointer that takes your game state as a parameter.
+///
+/// A scheduler is not attached to the [`Context`] - you should store it in your game
+/// state (or in each scene, if different parts of your game need their own timers),
+/// and call [`update`](Scheduler::update) from your [`State::update`](crate::State::update)
+/// method. This means that timers measure game time rather than real time: they
+/// advance by exactly one tick per update when using [`Timestep::Fixed`](time::Timestep::Fixed),
+/// and they stop when your game stops updating it (e.g. when a pause menu is open).
+///
+/// # Examples
+///
+/// The [`timers`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/timers.rs)
+/// example demonstrates how to use a `Scheduler` to spawn objects over time.
+#[derive(Debug)]
+pub struct Scheduler<T> {
+    timers: Vec<Timer<T>>,
+    next_id: u64,
+    time_scale: f64,
+}
+
+#[derive(Debug)]
+struct Timer<T> {
+    id: TimerId,
+    remaining: Duration,
+    kind: TimerKind<T>,
+}
+
+#[derive(Debug)]
+enum TimerKind<T> {
+    Once(Option<T>),
+
+    Repeat {
+        interval: Duration,
+        count: Option<usize>,
+        value: T,
+
+        // Storing the clone function means that `Scheduler` only requires
+        // `T: Clone` for repeating timers.
+        clone: fn(&T) -> T,
+    },
+
+    Sequence(VecDeque<(Duration, T)>),
+}
+
+impl<T> Timer<T> {
+    /// Fires the timer, returning the value it carries and (if it has not finished)
+    /// the amount of time until it should fire again.
+    fn fire(&mut self) -> (Option<T>, Option<Duration>) {
+        match &mut self.kind {
+            TimerKind::Once(value) => (value.take(), None),
+
+            TimerKind::Repeat {
+                interval,
+                count,
+                value,
+                clone,
+            } => match count {
+                Some(1) => (Some(clone(value)), None),
+
+                Some(count) => {
+                    *count -= 1;
+                    (Some(clone(value)), Some(*interval))
+                }
+
+                None => (Some(clone(value)), Some(*interval)),
+            },
+
+            TimerKind::Sequence(steps) => {
+                let value = steps.pop_front().map(|(_, value)| value);
+                let next = steps.front().map(|(delay, _)| *delay);
+
+                (value, next)
+            }
+        }
+    }
+}
+
+impl<T> Scheduler<T> {
+    /// Creates a new, empty `Scheduler`.
+    pub fn new() -> Scheduler<T> {
+        Scheduler {
+            timers: Vec::new(),
+            next_id: 0,
+            time_scale: 1.0,
+        }
+    }
+
+    /// Adds a timer that will fire once, after the given delay.
+    pub fn after(&mut self, delay: Duration, value: T) -> TimerId {
+        self.add(delay, TimerKind::Once(Some(value)))
+    }
+
+    /// Adds a timer that will fire repeatedly, with the given interval between each
+    /// time that it fires. The first time will be one interval from now.
+    ///
+    /// The timer will keep firing until it is [cancelled](Scheduler::cancel).
+    ///
+    /// If the interval is zero, the timer will fire once per update.
+    pub fn every(&mut self, interval: Duration, value: T) -> TimerId
+    where
+        T: Clone,
+    {
+        self.add(
+            interval,
+            TimerKind::Repeat {
+                interval,
+                count: None,
+                value,
+                clone: T::clone,
+            },
+        )
+    }
+
+    /// Adds a timer that will fire the given number of times, with the given interval
+    /// between each time that it fires. The first time will be one interval from now.
+    ///
+    /// If the interval is zero, the timer will fire once per update.
+    pub fn repeat(&mut self, interval: Duration, count: usize, value: T) -> TimerId
+    where
+        T: Clone,
+    {
+        if count == 0 {
+            return self.next_id();
+        }
+
+        self.add(
+            interval,
+            TimerKind::Repeat {
+                interval,
+                count: Some(count),
+                value,
+                clone: T::clone,
+            },
+        )
+    }
+
+    /// Adds a timer that will work through a sequence of steps, waiting for each step's
+    /// delay and then firing its value.
+    ///
+    /// This can be used to script a series of events, like a coroutine - for example,
+    /// a boss's attack pattern, or the beats of a cutscene. The delays are relative to
+    /// the previous step, so a delay of zero will fire a value in the same update as
+    /// the previous one.
+    ///
+    /// Cancelling the returned [`TimerId`] will skip any steps that have not fired yet.
+    pub fn sequence<I>(&mut self, steps: I) -> TimerId
+    where
+        I: IntoIterator<Item = (Duration, T)>,
+    {
+        let steps: VecDeque<_> = steps.into_iter().collect();
+
+        match steps.front() {
+            Some((delay, _)) => self.add(*delay, TimerKind::Sequence(steps)),
+            None => self.next_id(),
+        }
+    }
+
+    /// Cancels a timer, so that it will not fire again.
+    ///
+    /// Returns `true` if the timer was still active.
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != len
+    }
+
+    /// Cancels all of the timers.
+    pub fn clear(&mut self) {
+        self.timers.clear();
+    }
+
+    /// Returns `true` if the timer has not yet finished or been cancelled.
+    pub fn is_active(&self, id: TimerId) -> bool {
+        self.timers.iter().any(|timer| timer.id == id)
+    }
+
+    /// Returns the amount of time until the timer next fires, or `None` if it is
+    /// no longer active.
+    ///
+    /// This is measured in the scheduler's time, so it does not take the
+    /// [time scale](Scheduler::set_time_scale) into account.
+    pub fn time_remaining(&self, id: TimerId) -> Option<Duration> {
+        self.timers
+            .iter()
+            .find(|timer| timer.id == id)
+            .map(|timer| timer.remaining)
+    }
+
+    /// Returns the number of active timers.
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Returns `true` if there are no active timers.
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Returns the speed at which the scheduler's timers advance.
+    pub fn get_time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    /// Sets the speed at which the scheduler's timers advance, relative to the game's
+    /// delta time. For example, `0.5` will make every timer take twice as long to fire,
+    /// and `0.0` will stop them entirely.
+    ///
+    /// Values will be clamped between `0.0` and [`MAX_TIME_SCALE`](time::MAX_TIME_SCALE).
+    /// `NaN` will be ignored.
+    ///
+    /// Defaults to `1.0`.
+    pub fn set_time_scale(&mut self, time_scale: f64) {
+        if time_scale.is_nan() {
+            return;
+        }
+
+        self.time_scale = time_scale.clamp(0.0, time::MAX_TIME_SCALE);
+    }
+
+    /// Advances the timers by the current [delta time](time::get_delta_time), returning
+    /// the values of any timers that fired.
+    ///
+    /// This should usually be called once per update. If you call it from
+    /// [`State::draw`](crate::State::draw) instead, the timers will be affected by
+    /// the frame rate.
+    pub fn update(&mut self, ctx: &Context) -> Vec<T> {
+        self.advance(time::get_delta_time(ctx))
+    }
+
+    /// Advances the timers by the given amount of time, returning the values of any
+    /// timers that fired.
+    ///
+    /// If a timer is due to fire more than once within the given time, it will do so.
+    /// The values are returned in the order that the timers were due, with timers that
+    /// were due at the same time being returned in the order they were added.
+    pub fn advance(&mut self, delta: Duration) -> Vec<T> {
+        let delta = delta.mul_f64(self.time_scale);
+        let mut fired = Vec::new();
+
+        self.timers.retain_mut(|timer| {
+            // The point within `delta` at which the timer's current countdown started.
+            let mut offset = Duration::ZERO;
+
+            loop {
+                let until_due = delta - offset;
+
+                if timer.remaining > until_due {
+                    timer.remaining -= until_due;
+                    return true;
+                }
+
+                offset += timer.remaining;
+
+                let (value, next) = timer.fire();
+
+                if let Some(value) = value {
+                    fired.push((offset, timer.id, value));
+                }
+
+                match next {
+                    // A repeating timer with no interval would never stop firing, so
+                    // it gets picked up again on the next update instead.
+                    Some(Duration::ZERO) if matches!(timer.kind, TimerKind::Repeat { .. }) => {
+                        timer.remaining = Duration::ZERO;
+                        return true;
+                    }
+
+                    Some(next) => timer.remaining = next,
+                    None => return false,
+                }
+            }
+        });
+
+        fired.sort_by_key(|(offset, id, _)| (*offset, id.0));
+        fired.into_iter().map(|(_, _, value)| value).collect()
+    }
+
+    fn add(&mut self, delay: Duration, kind: TimerKind<T>) -> TimerId {
+        let id = self.next_id();
+
+        self.timers.push(Timer {
+            id,
+            remaining: delay,
+            kind,
+        });
+
+        id
+    }
+
+    fn next_id(&mut self) -> TimerId {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+}
+
+impl<T> Default for Scheduler<T> {
+    fn default() -> Scheduler<T> {
+        Scheduler::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContextBuilder, State};
+
+    fn secs(secs: f64) -> Duration {
+        Duration::from_secs_f64(secs)
+    }
+
+    #[test]
+    fn one_shot_timers() {
+        let mut scheduler = Scheduler::new();
+        let id = scheduler.after(secs(1.0), "a");
+
+        assert!(scheduler.advance(secs(0.5)).is_empty());
+        assert_eq!(scheduler.time_remaining(id), Some(secs(0.5)));
+
+        assert_eq!(scheduler.advance(secs(0.5)), ["a"]);
+        assert!(!scheduler.is_active(id));
+        assert!(scheduler.advance(secs(10.0)).is_empty());
+    }
+
+    #[test]
+    fn repeating_timers_catch_up_in_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.every(secs(1.0), "every");
+        scheduler.repeat(secs(1.5), 2, "repeat");
+
+        assert_eq!(
+            scheduler.advance(secs(3.5)),
+            ["every", "repeat", "every", "every", "repeat"]
+        );
+
+        assert_eq!(scheduler.len(), 1);
+        assert_eq!(scheduler.advance(secs(0.5)), ["every"]);
+    }
+
+    #[test]
+    fn zero_interval_fires_once_per_update() {
+        let mut scheduler = Scheduler::new();
+        scheduler.every(Duration::ZERO, ());
+
+        assert_eq!(scheduler.advance(secs(1.0)).len(), 1);
+        assert_eq!(scheduler.advance(Duration::ZERO).len(), 1);
+    }
+
+    #[test]
+    fn cancellation() {
+        let mut scheduler = Scheduler::new();
+        let a = scheduler.every(secs(1.0), "a");
+        scheduler.after(secs(1.0), "b");
+
+        assert!(scheduler.cancel(a));
+        assert!(!scheduler.cancel(a));
+
+        assert_eq!(scheduler.advance(secs(1.0)), ["b"]);
+    }
+
+    #[test]
+    fn sequences() {
+        let mut scheduler = Scheduler::new();
+
+        let id = scheduler.sequence([
+            (secs(1.0), "windup"),
+            (Duration::ZERO, "flash"),
+            (secs(2.0), "attack"),
+            (secs(1.0), "recover"),
+        ]);
+
+        assert_eq!(scheduler.advance(secs(1.0)), ["windup", "flash"]);
+        assert_eq!(scheduler.advance(secs(2.0)), ["attack"]);
+
+        scheduler.cancel(id);
+        assert!(scheduler.advance(secs(1.0)).is_empty());
+    }
+
+    #[test]
+    fn time_scale() {
+        let mut scheduler = Scheduler::new();
+        scheduler.after(secs(1.0), "a");
+        scheduler.set_time_scale(0.5);
+
+        assert!(scheduler.advance(secs(1.0)).is_empty());
+        assert_eq!(scheduler.advance(secs(1.0)), ["a"]);
+    }
+
+    #[test]
+    fn time_scale_is_clamped() {
+        let mut scheduler = Scheduler::<()>::new();
+
+        scheduler.set_time_scale(-1.0);
+        assert_eq!(scheduler.get_time_scale(), 0.0);
+
+        scheduler.set_time_scale(f64::INFINITY);
+        assert_eq!(scheduler.get_time_scale(), time::MAX_TIME_SCALE);
+
+        scheduler.set_time_scale(f64::NAN);
+        assert_eq!(scheduler.get_time_scale(), time::MAX_TIME_SCALE);
+
+        scheduler.set_time_scale(1e300);
+        assert_eq!(scheduler.get_time_scale(), time::MAX_TIME_SCALE);
+
+        // This would panic if the time scale wasn't clamped.
+        scheduler.advance(secs(60.0));
+    }
+
+    #[test]
+    fn update_uses_fixed_timestep() {
+        struct GameState {
+            scheduler: Scheduler<u32>,
+            fired: Vec<u32>,
+        }
+
+        impl State for GameState {
+            fn update(&mut self, ctx: &mut Context) -> crate::Result {
+                self.fired.extend(self.scheduler.update(ctx));
+                Ok(())
+            }
+        }
+
+        let mut ctx = ContextBuilder::new("Test", 64, 64)
+            .headless(true)
+            .timestep(time::Timestep::Fixed(10.0))
+            .build()
+            .unwrap();
+
+        let mut state = GameState {
+            scheduler: Scheduler::new(),
+            fired: Vec::new(),
+        };
+
+        state.scheduler.every(secs(0.2), 1);
+
+        // A long frame runs several fixed updates, each of which advances the
+        // timer by exactly one tick.
+        ctx.tick(&mut state, secs(0.45)).unwrap();
+        assert_eq!(state.fired, [1, 1]);
+
+        ctx.tick(&mut state, secs(0.05)).unwrap();
+        ctx.tick(&mut state, secs(0.1)).unwrap();
+        assert_eq!(state.fired, [1, 1, 1]);
+    }
+}
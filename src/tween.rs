@@ -0,0 +1,625 @@
+//! Functions and types relating to tweening (animating values over time).
+//!
+//! A [`Tween`] describes how a single value should change from one point to another,
+//! following an [`Easing`] curve. Tweens can be chained together into a [`Sequence`],
+//! or run alongside each other via [`Parallel`]. All of these implement the [`Timeline`]
+//! trait, which means they can be sampled at any point in time.
+//!
+//! To actually play back a timeline, wrap it in a [`Tweener`], which keeps track of
+//! how much time has passed and handles looping. Tweeners are advanced by a
+//! [`Duration`], so they can be driven by [`time::get_delta_time`](crate::time::get_delta_time)
+//! (via [`Tweener::update`]), or by any other clock.
+//!
+//! # Examples
+//!
+//! The [`tweening`](https://github.com/17cupsofcoffee/tetra/blob/main/examples/tweening.rs)
+//! example demonstrates how to use the different types of tween.
+
+mod easing;
+
+use std::time::Duration;
+
+use crate::graphics::{Color, DrawParams, Rectangle};
+use crate::math::Vec2;
+use crate::time;
+use crate::Context;
+
+pub use easing::*;
+
+/// Implemented by types that can be tweened.
+///
+/// Tetra implements this trait for [`f32`], [`f64`], [`Vec2<f32>`], [`Color`],
+/// [`Rectangle<f32>`] and [`DrawParams`]. You can implement it for your own
+/// types as well.
+pub trait Tweenable: Clone {
+    /// Returns a value between `self` and `target`.
+    ///
+    /// When `t` is `0.0`, this should return `self`, and when `t` is `1.0`, this should
+    /// return `target`. Note that some [`Easing`] curves overshoot, so `t` may be
+    /// outside of the range `0.0..=1.0`.
+    fn interpolate(&self, target: &Self, t: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn interpolate(&self, target: &f32, t: f32) -> f32 {
+        self + (target - self) * t
+    }
+}
+
+impl Tweenable for f64 {
+    fn interpolate(&self, target: &f64, t: f32) -> f64 {
+        self + (target - self) * f64::from(t)
+    }
+}
+
+impl Tweenable for Vec2<f32> {
+    fn interpolate(&self, target: &Vec2<f32>, t: f32) -> Vec2<f32> {
+        *self + (*target - *self) * t
+    }
+}
+
+impl Tweenable for Color {
+    fn interpolate(&self, target: &Color, t: f32) -> Color {
+        Color::rgba(
+            self.r.interpolate(&target.r, t),
+            self.g.interpolate(&target.g, t),
+            self.b.interpolate(&target.b, t),
+            self.a.interpolate(&target.a, t),
+        )
+    }
+}
+
+impl Tweenable for Rectangle<f32> {
+    fn interpolate(&self, target: &Rectangle<f32>, t: f32) -> Rectangle<f32> {
+        Rectangle::new(
+            self.x.interpolate(&target.x, t),
+            self.y.interpolate(&target.y, t),
+            self.width.interpolate(&target.width, t),
+            self.height.interpolate(&target.height, t),
+        )
+    }
+}
+
+impl Tweenable for DrawParams {
+    fn interpolate(&self, target: &DrawParams, t: f32) -> DrawParams {
+        DrawParams {
+            position: self.position.interpolate(&target.position, t),
+            scale: self.scale.interpolate(&target.scale, t),
+            origin: self.origin.interpolate(&target.origin, t),
+            rotation: self.rotation.interpolate(&target.rotation, t),
+            color: self.color.interpolate(&target.color, t),
+        }
+    }
+}
+
+/// Implemented by types that describe how a value changes over a fixed length of time.
+pub trait Timeline {
+    /// The type of value that the timeline produces.
+    type Output;
+
+    /// Returns the length of the timeline.
+    fn duration(&self) -> Duration;
+
+    /// Returns the value of the timeline at the given point in time.
+    ///
+    /// Times after the end of the timeline should return the final value.
+    fn sample(&self, time: Duration) -> Self::Output;
+}
+
+impl<T> Timeline for Box<T>
+where
+    T: Timeline + ?Sized,
+{
+    type Output = T::Output;
+
+    fn duration(&self) -> Duration {
+        (**self).duration()
+    }
+
+    fn sample(&self, time: Duration) -> T::Output {
+        (**self).sample(time)
+    }
+}
+
+/// Changes a value from one point to another, over a length of time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl<T> Tween<T>
+where
+    T: Tweenable,
+{
+    /// Creates a new tween.
+    pub fn new(from: T, to: T, duration: Duration, easing: Easing) -> Tween<T> {
+        Tween {
+            from,
+            to,
+            duration,
+            easing,
+        }
+    }
+
+    /// Creates a tween that stays at the same value for a length of time.
+    ///
+    /// This is useful for adding pauses to a [`Sequence`].
+    pub fn hold(value: T, duration: Duration) -> Tween<T> {
+        Tween::new(value.clone(), value, duration, Easing::Linear)
+    }
+
+    /// Returns the value that the tween starts from.
+    pub fn from(&self) -> &T {
+        &self.from
+    }
+
+    /// Returns the value that the tween ends at.
+    pub fn to(&self) -> &T {
+        &self.to
+    }
+
+    /// Returns the easing curve that the tween follows.
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+}
+
+impl<T> Timeline for Tween<T>
+where
+    T: Tweenable,
+{
+    type Output = T;
+
+    fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    fn sample(&self, time: Duration) -> T {
+        if time >= self.duration {
+            return self.to.clone();
+        }
+
+        let t = time.as_secs_f64() / self.duration.as_secs_f64();
+
+        self.from.interpolate(&self.to, self.easing.ease(t as f32))
+    }
+}
+
+/// A series of timelines, which are played one after the other.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use tetra::tween::{Easing, Sequence, Tween};
+/// let bounce = Sequence::new(Tween::new(0.0, 64.0, Duration::from_millis(250), Easing::QuadOut))
+///     .then(Tween::new(64.0, 0.0, Duration::from_millis(250), Easing::QuadIn));
+/// ```
+pub struct Sequence<T> {
+    steps: Vec<Box<dyn Timeline<Output = T>>>,
+    duration: Duration,
+}
+
+impl<T> Sequence<T> {
+    /// Creates a new sequence, starting with the given timeline.
+    pub fn new<L>(first: L) -> Sequence<T>
+    where
+        L: Timeline<Output = T> + 'static,
+    {
+        Sequence {
+            duration: first.duration(),
+            steps: vec![Box::new(first)],
+        }
+    }
+
+    /// Adds a timeline to the end of the sequence.
+    pub fn then<L>(mut self, next: L) -> Sequence<T>
+    where
+        L: Timeline<Output = T> + 'static,
+    {
+        self.duration += next.duration();
+        self.steps.push(Box::new(next));
+        self
+    }
+}
+
+impl<T> Timeline for Sequence<T> {
+    type Output = T;
+
+    fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    fn sample(&self, mut time: Duration) -> T {
+        let (last, rest) = self
+            .steps
+            .split_last()
+            .expect("sequence should have at least one step");
+
+        for step in rest {
+            let duration = step.duration();
+
+            if time < duration {
+                return step.sample(time);
+            }
+
+            time -= duration;
+        }
+
+        last.sample(time)
+    }
+}
+
+/// Two timelines, which are played at the same time.
+///
+/// The output is a tuple of the values produced by each timeline, and the duration
+/// is that of the longer timeline. If you need to run more than two timelines at
+/// once, `Parallel`s can be nested.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use tetra::graphics::Color;
+/// # use tetra::math::Vec2;
+/// # use tetra::tween::{Easing, Parallel, Tween};
+/// let fade_and_move = Parallel(
+///     Tween::new(Color::WHITE, Color::BLACK, Duration::from_secs(1), Easing::Linear),
+///     Tween::new(Vec2::zero(), Vec2::new(32.0, 0.0), Duration::from_secs(2), Easing::SineInOut),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parallel<A, B>(pub A, pub B);
+
+impl<A, B> Timeline for Parallel<A, B>
+where
+    A: Timeline,
+    B: Timeline,
+{
+    type Output = (A::Output, B::Output);
+
+    fn duration(&self) -> Duration {
+        self.0.duration().max(self.1.duration())
+    }
+
+    fn sample(&self, time: Duration) -> Self::Output {
+        (self.0.sample(time), self.1.sample(time))
+    }
+}
+
+/// How many times a [`Tweener`] should play its timeline.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Repeat {
+    /// The timeline should be played the given number of times.
+    Times(u32),
+
+    /// The timeline should loop forever.
+    Forever,
+}
+
+/// Plays back a [`Timeline`], keeping track of how much time has passed.
+///
+/// # Looping
+///
+/// By default, the timeline will be played once. This can be changed via
+/// [`repeat`](Tweener::repeat). If [`yoyo`](Tweener::yoyo) is enabled, each
+/// repetition will play the timeline forwards and then backwards.
+#[derive(Debug, Clone)]
+pub struct Tweener<L> {
+    timeline: L,
+    elapsed: Duration,
+    repeat: Repeat,
+    yoyo: bool,
+}
+
+impl<L> Tweener<L>
+where
+    L: Timeline,
+{
+    /// Creates a new `Tweener`, which will play the given timeline once.
+    pub fn new(timeline: L) -> Tweener<L> {
+        Tweener {
+            timeline,
+            elapsed: Duration::ZERO,
+            repeat: Repeat::Times(1),
+            yoyo: false,
+        }
+    }
+
+    /// Sets how many times the timeline should be played.
+    pub fn repeat(mut self, repeat: Repeat) -> Tweener<L> {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Sets whether the timeline should be played forwards and then backwards
+    /// each time it repeats.
+    pub fn yoyo(mut self, yoyo: bool) -> Tweener<L> {
+        self.yoyo = yoyo;
+        self
+    }
+
+    /// Advances the tweener by the current [delta time](time::get_delta_time).
+    ///
+    /// This should usually be called from your [`State::update`](crate::State::update)
+    /// method, so that the tween is in step with the rest of your game logic.
+    pub fn update(&mut self, ctx: &Context) {
+        self.advance(time::get_delta_time(ctx));
+    }
+
+    /// Advances the tweener by the given amount of time.
+    pub fn advance(&mut self, delta: Duration) {
+        self.elapsed = self.elapsed.saturating_add(delta);
+
+        if let Some(total) = self.total_duration() {
+            self.elapsed = self.elapsed.min(total);
+        }
+    }
+
+    /// Returns the current value of the timeline.
+    pub fn value(&self) -> L::Output {
+        let duration = self.timeline.duration();
+
+        if duration.is_zero() {
+            return self.timeline.sample(Duration::ZERO);
+        }
+
+        let duration_nanos = duration.as_nanos();
+        let elapsed_nanos = self.elapsed.as_nanos();
+
+        let mut cycle = elapsed_nanos / duration_nanos;
+        let mut time = elapsed_nanos % duration_nanos;
+
+        // At the very end of the final cycle, the remainder wraps back to zero, so we
+        // step back to the end of the previous cycle instead.
+        if self.is_finished() && time == 0 && cycle > 0 {
+            cycle -= 1;
+            time = duration_nanos;
+        }
+
+        if self.yoyo && cycle % 2 == 1 {
+            time = duration_nanos - time;
+        }
+
+        self.timeline.sample(nanos_to_duration(time))
+    }
+
+    /// Returns `true` if the timeline has finished playing.
+    ///
+    /// This will always be `false` if the tweener is set to repeat forever.
+    pub fn is_finished(&self) -> bool {
+        match self.total_duration() {
+            Some(total) => self.elapsed >= total,
+            None => false,
+        }
+    }
+
+    /// Returns the amount of time that the tweener has been playing for.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns how far through the tweener is, as a value between `0.0` and `1.0`.
+    ///
+    /// This will always be `0.0` if the tweener is set to repeat forever.
+    pub fn progress(&self) -> f32 {
+        match self.total_duration() {
+            Some(total) if !total.is_zero() => {
+                (self.elapsed.as_secs_f64() / total.as_secs_f64()) as f32
+            }
+            Some(_) => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Restarts the timeline from the beginning.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Returns a reference to the timeline being played.
+    pub fn timeline(&self) -> &L {
+        &self.timeline
+    }
+
+    /// Returns a mutable reference to the timeline being played.
+    pub fn timeline_mut(&mut self) -> &mut L {
+        &mut self.timeline
+    }
+
+    /// Returns the total length of playback, or `None` if it will never finish (or is
+    /// too long to be represented as a `Duration`).
+    fn total_duration(&self) -> Option<Duration> {
+        let cycles = match self.repeat {
+            Repeat::Times(times) => times,
+            Repeat::Forever => return None,
+        };
+
+        let cycles = if self.yoyo {
+            cycles.checked_mul(2)?
+        } else {
+            cycles
+        };
+
+        self.timeline.duration().checked_mul(cycles)
+    }
+}
+
+fn nanos_to_duration(nanos: u128) -> Duration {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+    Duration::new(
+        (nanos / NANOS_PER_SEC) as u64,
+        (nanos % NANOS_PER_SEC) as u32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: f64) -> Duration {
+        Duration::from_secs_f64(secs)
+    }
+
+    #[test]
+    fn easing_endpoints() {
+        let curves = [
+            Easing::Linear,
+            Easing::SineIn,
+            Easing::SineOut,
+            Easing::SineInOut,
+            Easing::QuadIn,
+            Easing::QuadOut,
+            Easing::QuadInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::QuartIn,
+            Easing::QuartOut,
+            Easing::QuartInOut,
+            Easing::QuintIn,
+            Easing::QuintOut,
+            Easing::QuintInOut,
+            Easing::ExpoIn,
+            Easing::ExpoOut,
+            Easing::ExpoInOut,
+            Easing::CircIn,
+            Easing::CircOut,
+            Easing::CircInOut,
+            Easing::BackIn,
+            Easing::BackOut,
+            Easing::BackInOut,
+            Easing::ElasticIn,
+            Easing::ElasticOut,
+            Easing::ElasticInOut,
+            Easing::BounceIn,
+            Easing::BounceOut,
+            Easing::BounceInOut,
+        ];
+
+        for curve in curves {
+            assert!(
+                curve.ease(0.0).abs() < 0.001,
+                "{:?} should start at 0",
+                curve
+            );
+            assert!(
+                (curve.ease(1.0) - 1.0).abs() < 0.001,
+                "{:?} should end at 1",
+                curve
+            );
+
+            if format!("{:?}", curve).ends_with("InOut") {
+                assert!(
+                    (curve.ease(0.5) - 0.5).abs() < 0.001,
+                    "{:?} should be halfway at the midpoint",
+                    curve
+                );
+            }
+        }
+
+        assert_eq!(Easing::QuadIn.ease(0.5), 0.25);
+        assert_eq!(Easing::QuadOut.ease(0.5), 0.75);
+    }
+
+    #[test]
+    fn tween_values() {
+        let tween = Tween::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 20.0),
+            secs(2.0),
+            Easing::Linear,
+        );
+
+        assert_eq!(tween.sample(Duration::ZERO), Vec2::new(0.0, 0.0));
+        assert_eq!(tween.sample(secs(1.0)), Vec2::new(5.0, 10.0));
+        assert_eq!(tween.sample(secs(5.0)), Vec2::new(10.0, 20.0));
+
+        let color = Tween::new(Color::BLACK, Color::WHITE, secs(1.0), Easing::Linear);
+        assert_eq!(color.sample(secs(0.5)), Color::rgba(0.5, 0.5, 0.5, 1.0));
+    }
+
+    #[test]
+    fn sequences_and_parallel() {
+        let sequence = Sequence::new(Tween::new(0.0, 10.0, secs(1.0), Easing::Linear))
+            .then(Tween::hold(10.0, secs(1.0)))
+            .then(Tween::new(10.0, 0.0, secs(2.0), Easing::Linear));
+
+        assert_eq!(sequence.duration(), secs(4.0));
+        assert_eq!(sequence.sample(secs(0.5)), 5.0);
+        assert_eq!(sequence.sample(secs(1.5)), 10.0);
+        assert_eq!(sequence.sample(secs(3.0)), 5.0);
+        assert_eq!(sequence.sample(secs(10.0)), 0.0);
+
+        let parallel = Parallel(
+            Tween::new(0.0f32, 1.0, secs(1.0), Easing::Linear),
+            Tween::new(0.0f64, 4.0, secs(2.0), Easing::Linear),
+        );
+
+        assert_eq!(parallel.duration(), secs(2.0));
+        assert_eq!(parallel.sample(secs(1.0)), (1.0, 2.0));
+    }
+
+    #[test]
+    fn tweener_playback() {
+        let mut tweener = Tweener::new(Tween::new(0.0, 10.0, secs(1.0), Easing::Linear));
+
+        tweener.advance(secs(0.5));
+        assert_eq!(tweener.value(), 5.0);
+        assert!(!tweener.is_finished());
+
+        tweener.advance(secs(5.0));
+        assert_eq!(tweener.value(), 10.0);
+        assert!(tweener.is_finished());
+        assert_eq!(tweener.progress(), 1.0);
+
+        tweener.reset();
+        assert_eq!(tweener.value(), 0.0);
+    }
+
+    #[test]
+    fn tweener_looping() {
+        let tween = Tween::new(0.0, 10.0, secs(1.0), Easing::Linear);
+
+        let mut looping = Tweener::new(tween.clone()).repeat(Repeat::Forever);
+        looping.advance(secs(3.25));
+        assert_eq!(looping.value(), 2.5);
+        assert!(!looping.is_finished());
+
+        let mut yoyo = Tweener::new(tween).repeat(Repeat::Times(2)).yoyo(true);
+        yoyo.advance(secs(1.25));
+        assert_eq!(yoyo.value(), 7.5);
+
+        yoyo.advance(secs(1.0));
+        assert_eq!(yoyo.value(), 2.5);
+
+        yoyo.advance(secs(10.0));
+        assert!(yoyo.is_finished());
+        assert_eq!(yoyo.value(), 0.0);
+    }
+
+    #[test]
+    fn tweener_with_huge_repeat_count() {
+        let tween = Tween::new(0.0, 10.0, Duration::from_secs(u64::MAX / 4), Easing::Linear);
+
+        let mut tweener = Tweener::new(tween)
+            .repeat(Repeat::Times(u32::MAX))
+            .yoyo(true);
+
+        tweener.advance(secs(1.0));
+        assert!(!tweener.is_finished());
+    }
+}
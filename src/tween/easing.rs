@@ -0,0 +1,207 @@
+use std::f32::consts::PI;
+
+/// Curves that control how a tween progresses over time.
+///
+/// These are the standard easing equations created by Robert Penner. The
+/// [easings.net](https://easings.net/) website has a visualization of each one.
+///
+/// `In` curves start slowly and then speed up, `Out` curves start quickly and then
+/// slow down, and `InOut` curves do both. Note that the `Back` and `Elastic` curves
+/// overshoot the start and/or end values.
+///
+/// # Serde
+///
+/// Serialization and deserialization of this type (via [Serde](https://serde.rs/))
+/// can be enabled via the `serde_support` feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Easing {
+    /// Progresses at a constant speed.
+    #[default]
+    Linear,
+
+    /// A sinusoidal curve, accelerating from zero velocity.
+    SineIn,
+    /// A sinusoidal curve, decelerating to zero velocity.
+    SineOut,
+    /// A sinusoidal curve, accelerating and then decelerating.
+    SineInOut,
+
+    /// A quadratic curve, accelerating from zero velocity.
+    QuadIn,
+    /// A quadratic curve, decelerating to zero velocity.
+    QuadOut,
+    /// A quadratic curve, accelerating and then decelerating.
+    QuadInOut,
+
+    /// A cubic curve, accelerating from zero velocity.
+    CubicIn,
+    /// A cubic curve, decelerating to zero velocity.
+    CubicOut,
+    /// A cubic curve, accelerating and then decelerating.
+    CubicInOut,
+
+    /// A quartic curve, accelerating from zero velocity.
+    QuartIn,
+    /// A quartic curve, decelerating to zero velocity.
+    QuartOut,
+    /// A quartic curve, accelerating and then decelerating.
+    QuartInOut,
+
+    /// A quintic curve, accelerating from zero velocity.
+    QuintIn,
+    /// A quintic curve, decelerating to zero velocity.
+    QuintOut,
+    /// A quintic curve, accelerating and then decelerating.
+    QuintInOut,
+
+    /// An exponential curve, accelerating from zero velocity.
+    ExpoIn,
+    /// An exponential curve, decelerating to zero velocity.
+    ExpoOut,
+    /// An exponential curve, accelerating and then decelerating.
+    ExpoInOut,
+
+    /// A circular curve, accelerating from zero velocity.
+    CircIn,
+    /// A circular curve, decelerating to zero velocity.
+    CircOut,
+    /// A circular curve, accelerating and then decelerating.
+    CircInOut,
+
+    /// Pulls back below the start value, and then accelerates towards the end value.
+    BackIn,
+    /// Overshoots the end value, and then settles back onto it.
+    BackOut,
+    /// Pulls back below the start value and overshoots the end value.
+    BackInOut,
+
+    /// Oscillates around the start value with increasing strength.
+    ElasticIn,
+    /// Oscillates around the end value with decreasing strength.
+    ElasticOut,
+    /// Oscillates around the start value, and then around the end value.
+    ElasticInOut,
+
+    /// Bounces off the start value with increasing height.
+    BounceIn,
+    /// Bounces off the end value with decreasing height, like a dropped ball.
+    BounceOut,
+    /// Bounces off the start value, and then off the end value.
+    BounceInOut,
+}
+
+impl Easing {
+    /// Applies the curve to a progress value between `0.0` and `1.0`.
+    ///
+    /// The result will be `0.0` at the start of the curve and `1.0` at the end, but
+    /// may go outside of that range in between. Values outside of `0.0..=1.0` will
+    /// be clamped.
+    pub fn ease(self, t: f32) -> f32 {
+        const BACK: f32 = 1.70158;
+        const BACK_IN_OUT: f32 = BACK * 1.525;
+        const ELASTIC: f32 = (2.0 * PI) / 3.0;
+        const ELASTIC_IN_OUT: f32 = (2.0 * PI) / 4.5;
+
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+
+            Easing::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            Easing::SineOut => (t * PI / 2.0).sin(),
+            Easing::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+
+            Easing::QuadIn => t.powi(2),
+            Easing::QuadOut => 1.0 - (1.0 - t).powi(2),
+            Easing::QuadInOut => in_out_power(t, 2),
+
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => in_out_power(t, 3),
+
+            Easing::QuartIn => t.powi(4),
+            Easing::QuartOut => 1.0 - (1.0 - t).powi(4),
+            Easing::QuartInOut => in_out_power(t, 4),
+
+            Easing::QuintIn => t.powi(5),
+            Easing::QuintOut => 1.0 - (1.0 - t).powi(5),
+            Easing::QuintInOut => in_out_power(t, 5),
+
+            Easing::ExpoIn if t == 0.0 => 0.0,
+            Easing::ExpoIn => 2.0f32.powf(10.0 * t - 10.0),
+            Easing::ExpoOut if t == 1.0 => 1.0,
+            Easing::ExpoOut => 1.0 - 2.0f32.powf(-10.0 * t),
+            Easing::ExpoInOut if t == 0.0 || t == 1.0 => t,
+            Easing::ExpoInOut if t < 0.5 => 2.0f32.powf(20.0 * t - 10.0) / 2.0,
+            Easing::ExpoInOut => (2.0 - 2.0f32.powf(-20.0 * t + 10.0)) / 2.0,
+
+            Easing::CircIn => 1.0 - (1.0 - t.powi(2)).sqrt(),
+            Easing::CircOut => (1.0 - (t - 1.0).powi(2)).sqrt(),
+            Easing::CircInOut if t < 0.5 => (1.0 - (1.0 - (2.0 * t).powi(2)).sqrt()) / 2.0,
+            Easing::CircInOut => ((1.0 - (-2.0 * t + 2.0).powi(2)).sqrt() + 1.0) / 2.0,
+
+            Easing::BackIn => (BACK + 1.0) * t.powi(3) - BACK * t.powi(2),
+            Easing::BackOut => 1.0 + (BACK + 1.0) * (t - 1.0).powi(3) + BACK * (t - 1.0).powi(2),
+            Easing::BackInOut if t < 0.5 => {
+                ((2.0 * t).powi(2) * ((BACK_IN_OUT + 1.0) * 2.0 * t - BACK_IN_OUT)) / 2.0
+            }
+            Easing::BackInOut => {
+                ((2.0 * t - 2.0).powi(2) * ((BACK_IN_OUT + 1.0) * (t * 2.0 - 2.0) + BACK_IN_OUT)
+                    + 2.0)
+                    / 2.0
+            }
+
+            Easing::ElasticIn if t == 0.0 || t == 1.0 => t,
+            Easing::ElasticIn => {
+                -(2.0f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * ELASTIC).sin()
+            }
+            Easing::ElasticOut if t == 0.0 || t == 1.0 => t,
+            Easing::ElasticOut => {
+                2.0f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * ELASTIC).sin() + 1.0
+            }
+            Easing::ElasticInOut if t == 0.0 || t == 1.0 => t,
+            Easing::ElasticInOut if t < 0.5 => {
+                -(2.0f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin()) / 2.0
+            }
+            Easing::ElasticInOut => {
+                (2.0f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin()) / 2.0
+                    + 1.0
+            }
+
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut if t < 0.5 => (1.0 - bounce_out(1.0 - 2.0 * t)) / 2.0,
+            Easing::BounceInOut => (1.0 + bounce_out(2.0 * t - 1.0)) / 2.0,
+        }
+    }
+}
+
+fn in_out_power(t: f32, power: i32) -> f32 {
+    if t < 0.5 {
+        2.0f32.powi(power - 1) * t.powi(power)
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(power) / 2.0
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
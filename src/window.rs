@@ -1,6 +1,8 @@
 //! Functions and types relating to the game window, and the environment it is running in.
 
-use crate::{graphics::ImageData, Context, Result};
+use std::result;
+
+use crate::{graphics::ImageData, Context, Event, Result, State, TetraError};
 
 /// Quits the game, if it is currently running.
 ///
@@ -10,6 +12,26 @@ pub fn quit(ctx: &mut Context) {
     ctx.running = false;
 }
 
+/// Cancels a pending request to quit the game.
+///
+/// This is intended to be called while handling [`Event::QuitRequested`], in order to
+/// keep the game running after the user has tried to close it. It will also cancel any
+/// calls to [`quit`] that have been made during the current iteration of the game loop.
+pub fn cancel_quit(ctx: &mut Context) {
+    ctx.running = true;
+}
+
+/// Notifies the game that the user has asked for it to close, quitting unless the
+/// request is cancelled.
+pub(crate) fn request_quit<S, E>(ctx: &mut Context, state: &mut S) -> result::Result<(), E>
+where
+    S: State<E>,
+    E: From<TetraError>,
+{
+    ctx.running = false;
+    state.event(ctx, Event::QuitRequested)
+}
+
 /// Maximizes the window.
 pub fn maximize(ctx: &mut Context) {
     ctx.window.maximize();
@@ -222,6 +244,16 @@ pub fn is_visible(ctx: &mut Context) -> bool {
     ctx.window.is_visible()
 }
 
+/// Returns whether or not the window currently has input focus.
+pub fn is_focused(ctx: &Context) -> bool {
+    ctx.window.is_focused()
+}
+
+/// Returns whether or not the window is currently minimized.
+pub fn is_minimized(ctx: &Context) -> bool {
+    ctx.window.is_minimized()
+}
+
 /// Sets whether or not the window should be visible to the user.
 pub fn set_visible(ctx: &mut Context, visible: bool) {
     ctx.window.set_visible(visible);